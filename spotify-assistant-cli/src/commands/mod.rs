@@ -1 +1,2 @@
 pub mod followed_artists;
+pub mod playlists;
@@ -0,0 +1,147 @@
+use std::error::Error;
+use std::path::Path;
+
+use rspotify::model::PlaylistId;
+use rspotify::prelude::Id;
+use spotify_assistant_core::actions::exploration::playlist::PlaylistXplr;
+use spotify_assistant_core::actions::playlists::compare::PlaylistDiff;
+use spotify_assistant_core::actions::playlists::export::{ExportedTrack, PlaylistExport};
+use spotify_assistant_core::actions::playlists::user::UserPlaylists;
+use spotify_assistant_core::utilities::general::terminal_width;
+use tracing::{Level, event};
+
+/// Resolves a playlist name, ID or URI given on the command line to a playlist ID.
+///
+/// Names are matched case-insensitively against the user's playlists. Input that matches no
+/// name is parsed as an ID or URI so playlists outside the library can still be addressed.
+pub async fn resolve_playlist_id(input: &str) -> Result<PlaylistId<'static>, Box<dyn Error>> {
+    let playlists = UserPlaylists::new().await.get_user_playlists().await;
+    if let Some(playlist) = UserPlaylists::match_playlist(&playlists, input) {
+        event!(
+            Level::DEBUG,
+            "Input matches playlist (input, name): {:?}, {:?}",
+            input,
+            playlist.name
+        );
+        return Ok(playlist.id);
+    }
+    match PlaylistId::from_id_or_uri(input.trim()) {
+        Ok(playlist_id) => {
+            event!(Level::DEBUG, "Input as ID: {:?}", playlist_id.id());
+            Ok(playlist_id.into_static())
+        }
+        Err(err) => {
+            println!("The provided input does not match any playlist IDs or names in your account.");
+            let available_names = playlists
+                .iter()
+                .map(|playlist| playlist.name.as_str())
+                .collect::<Vec<&str>>();
+            println!("Available playlist names: {:?}", available_names);
+            Err(Box::new(err))
+        }
+    }
+}
+
+/// Loads one side of a playlist comparison.
+///
+/// Input naming an existing file is read as a JSON playlist export (a snapshot); anything else
+/// is resolved with [`resolve_playlist_id`] and loaded from Spotify.
+pub async fn load_playlist_export(input: &str) -> Result<PlaylistExport, Box<dyn Error>> {
+    let path = Path::new(input);
+    if path.is_file() {
+        event!(Level::DEBUG, "Loading playlist snapshot from {:?}", path);
+        return Ok(PlaylistExport::load(path)?);
+    }
+    let playlist_id = resolve_playlist_id(input).await?;
+    Ok(playlist_export(playlist_id).await)
+}
+
+/// Loads every item of a playlist into an export.
+pub async fn playlist_export(playlist_id: PlaylistId<'static>) -> PlaylistExport {
+    let (xplr, items) = PlaylistXplr::with_items(playlist_id, false).await;
+    PlaylistExport::from_playlist(&xplr, items)
+}
+
+/// Prints a playlist diff as a side-by-side table, or as JSON when `json` is set.
+pub fn print_playlist_diff(diff: &PlaylistDiff, json: bool) -> Result<(), Box<dyn Error>> {
+    if json {
+        println!("{}", diff.to_json()?);
+    } else {
+        println!("{}", diff.table(terminal_width()));
+    }
+    Ok(())
+}
+
+/// Prints one recorded playlist version with what changed since the previous version.
+pub fn print_version_changes(version: &PlaylistExport, diff: Option<&PlaylistDiff>) {
+    println!(
+        "{}  {} tracks  (snapshot {})",
+        short_timestamp(&version.exported_at),
+        version.tracks.len(),
+        version.snapshot_id.as_deref().unwrap_or("unknown")
+    );
+    let Some(diff) = diff else {
+        println!("    First recorded version");
+        return;
+    };
+    for track in &diff.only_right {
+        println!("    + {}{}", describe_track(track), added_by(track));
+    }
+    for track in &diff.only_left {
+        println!("    - {}", describe_track(track));
+    }
+    for alternate in &diff.alternate_versions {
+        println!(
+            "    ~ {} replaced by {}",
+            describe_track(&alternate.left),
+            describe_track(&alternate.right)
+        );
+    }
+    if !diff.moved.is_empty() {
+        println!("    {} tracks moved", diff.moved.len());
+    }
+    if diff.is_identical() {
+        println!("    No track changes (details or order of items only)");
+    }
+}
+
+/// Prints the tracks of a recorded playlist version with who added them and when.
+pub fn print_version(version: &PlaylistExport) {
+    println!(
+        "{} as of {} (snapshot {})",
+        version.name,
+        short_timestamp(&version.exported_at),
+        version.snapshot_id.as_deref().unwrap_or("unknown")
+    );
+    for (index, track) in version.tracks.iter().enumerate() {
+        let added_at = track
+            .added_at
+            .as_deref()
+            .map(short_timestamp)
+            .unwrap_or_default();
+        println!(
+            "    {:>4}. {}  {}{}",
+            index + 1,
+            describe_track(track),
+            added_at,
+            added_by(track)
+        );
+    }
+}
+
+fn describe_track(track: &ExportedTrack) -> String {
+    format!("{} - {}", track.artists.join(", "), track.name)
+}
+
+fn added_by(track: &ExportedTrack) -> String {
+    track
+        .added_by
+        .as_deref()
+        .map(|user| format!(" (added by {user})"))
+        .unwrap_or_default()
+}
+
+/// Shortens an RFC 3339 timestamp to `YYYY-MM-DD HH:MM`.
+fn short_timestamp(timestamp: &str) -> String {
+    timestamp.chars().take(16).collect::<String>().replace('T', " ")
+}
@@ -107,11 +107,164 @@ impl ReleaseRadarCmds {
         }
     }
 }
+pub enum PlaylistCmds {
+    Dedupe(ArgMatches),
+    Export(ArgMatches),
+    Import(ArgMatches),
+    Diff(ArgMatches),
+    Combine(ArgMatches),
+    Split(ArgMatches),
+    Sort(ArgMatches),
+    Track,
+    History(ArgMatches),
+    Show(ArgMatches),
+    Edit(ArgMatches),
+    Check(ArgMatches),
+    List(ArgMatches),
+    Create(ArgMatches),
+    Delete(ArgMatches),
+    Move(ArgMatches),
+    Empty,
+}
+
+impl PlaylistCmds {
+    pub fn from_matches(matches: &ArgMatches) -> PlaylistCmds {
+        if let Some(dedupe_arguments) = matches.subcommand_matches("dedupe") {
+            PlaylistCmds::Dedupe(dedupe_arguments.to_owned())
+        } else if let Some(export_arguments) = matches.subcommand_matches("export") {
+            PlaylistCmds::Export(export_arguments.to_owned())
+        } else if let Some(import_arguments) = matches.subcommand_matches("import") {
+            PlaylistCmds::Import(import_arguments.to_owned())
+        } else if let Some(diff_arguments) = matches.subcommand_matches("diff") {
+            PlaylistCmds::Diff(diff_arguments.to_owned())
+        } else if let Some(combine_arguments) = matches.subcommand_matches("combine") {
+            PlaylistCmds::Combine(combine_arguments.to_owned())
+        } else if let Some(split_arguments) = matches.subcommand_matches("split") {
+            PlaylistCmds::Split(split_arguments.to_owned())
+        } else if let Some(sort_arguments) = matches.subcommand_matches("sort") {
+            PlaylistCmds::Sort(sort_arguments.to_owned())
+        } else if matches.subcommand_matches("track").is_some() {
+            PlaylistCmds::Track
+        } else if let Some(history_arguments) = matches.subcommand_matches("history") {
+            PlaylistCmds::History(history_arguments.to_owned())
+        } else if let Some(show_arguments) = matches.subcommand_matches("show") {
+            PlaylistCmds::Show(show_arguments.to_owned())
+        } else if let Some(edit_arguments) = matches.subcommand_matches("edit") {
+            PlaylistCmds::Edit(edit_arguments.to_owned())
+        } else if let Some(check_arguments) = matches.subcommand_matches("check") {
+            PlaylistCmds::Check(check_arguments.to_owned())
+        } else if let Some(list_arguments) = matches.subcommand_matches("list") {
+            PlaylistCmds::List(list_arguments.to_owned())
+        } else if let Some(create_arguments) = matches.subcommand_matches("create") {
+            PlaylistCmds::Create(create_arguments.to_owned())
+        } else if let Some(delete_arguments) = matches.subcommand_matches("delete") {
+            PlaylistCmds::Delete(delete_arguments.to_owned())
+        } else if let Some(move_arguments) = matches.subcommand_matches("move") {
+            PlaylistCmds::Move(move_arguments.to_owned())
+        } else {
+            PlaylistCmds::Empty
+        }
+    }
+}
+pub enum SmartCmds {
+    Create(ArgMatches),
+    List,
+    Remove(ArgMatches),
+    Refresh(ArgMatches),
+    Empty,
+}
+
+impl SmartCmds {
+    pub fn from_matches(matches: &ArgMatches) -> SmartCmds {
+        if let Some(create_arguments) = matches.subcommand_matches("create") {
+            SmartCmds::Create(create_arguments.to_owned())
+        } else if matches.subcommand_matches("list").is_some() {
+            SmartCmds::List
+        } else if let Some(remove_arguments) = matches.subcommand_matches("remove") {
+            SmartCmds::Remove(remove_arguments.to_owned())
+        } else if let Some(refresh_arguments) = matches.subcommand_matches("refresh") {
+            SmartCmds::Refresh(refresh_arguments.to_owned())
+        } else {
+            SmartCmds::Empty
+        }
+    }
+}
+pub enum LibraryCmds {
+    Sync(ArgMatches),
+    Mirror(ArgMatches),
+    Stats(ArgMatches),
+    Albums(ArgMatches),
+    Completion(ArgMatches),
+    Backup(ArgMatches),
+    Restore(ArgMatches),
+    Prune(ArgMatches),
+    Empty,
+}
+
+impl LibraryCmds {
+    pub fn from_matches(matches: &ArgMatches) -> LibraryCmds {
+        if let Some(sync_arguments) = matches.subcommand_matches("sync") {
+            LibraryCmds::Sync(sync_arguments.to_owned())
+        } else if let Some(mirror_arguments) = matches.subcommand_matches("mirror") {
+            LibraryCmds::Mirror(mirror_arguments.to_owned())
+        } else if let Some(stats_arguments) = matches.subcommand_matches("stats") {
+            LibraryCmds::Stats(stats_arguments.to_owned())
+        } else if let Some(albums_arguments) = matches.subcommand_matches("albums") {
+            LibraryCmds::Albums(albums_arguments.to_owned())
+        } else if let Some(completion_arguments) = matches.subcommand_matches("completion") {
+            LibraryCmds::Completion(completion_arguments.to_owned())
+        } else if let Some(backup_arguments) = matches.subcommand_matches("backup") {
+            LibraryCmds::Backup(backup_arguments.to_owned())
+        } else if let Some(restore_arguments) = matches.subcommand_matches("restore") {
+            LibraryCmds::Restore(restore_arguments.to_owned())
+        } else if let Some(prune_arguments) = matches.subcommand_matches("prune") {
+            LibraryCmds::Prune(prune_arguments.to_owned())
+        } else {
+            LibraryCmds::Empty
+        }
+    }
+}
+pub enum ArtistCmds {
+    Sync(ArgMatches),
+    History(ArgMatches),
+    Changes(ArgMatches),
+    Follow(ArgMatches),
+    Unfollow(ArgMatches),
+    Suggest(ArgMatches),
+    Gaps(ArgMatches),
+    Graph(ArgMatches),
+    Empty,
+}
+
+impl ArtistCmds {
+    pub fn from_matches(matches: &ArgMatches) -> ArtistCmds {
+        if let Some(sync_arguments) = matches.subcommand_matches("sync") {
+            ArtistCmds::Sync(sync_arguments.to_owned())
+        } else if let Some(history_arguments) = matches.subcommand_matches("history") {
+            ArtistCmds::History(history_arguments.to_owned())
+        } else if let Some(changes_arguments) = matches.subcommand_matches("changes") {
+            ArtistCmds::Changes(changes_arguments.to_owned())
+        } else if let Some(follow_arguments) = matches.subcommand_matches("follow") {
+            ArtistCmds::Follow(follow_arguments.to_owned())
+        } else if let Some(unfollow_arguments) = matches.subcommand_matches("unfollow") {
+            ArtistCmds::Unfollow(unfollow_arguments.to_owned())
+        } else if let Some(suggest_arguments) = matches.subcommand_matches("suggest") {
+            ArtistCmds::Suggest(suggest_arguments.to_owned())
+        } else if let Some(gaps_arguments) = matches.subcommand_matches("gaps") {
+            ArtistCmds::Gaps(gaps_arguments.to_owned())
+        } else if let Some(graph_arguments) = matches.subcommand_matches("graph") {
+            ArtistCmds::Graph(graph_arguments.to_owned())
+        } else {
+            ArtistCmds::Empty
+        }
+    }
+}
 pub enum QueryArgs {
     QStock(bool),
     QCustom(bool),
     QBlacklist(bool),
     QLibrary(bool),
+    Duplicates(ArgMatches),
     Empty,
 }
 impl QueryArgs {
@@ -120,7 +273,9 @@ impl QueryArgs {
         let custom_rr_arg = Self::arg_exists("qcustom", matches);
         let blacklist_arg = Self::arg_exists("qblacklist", matches);
         let liked_songs_arg = Self::arg_exists("qlibrary", matches);
-        if stock_rr_arg {
+        if let Some(duplicates_arguments) = matches.subcommand_matches("duplicates") {
+            QueryArgs::Duplicates(duplicates_arguments.to_owned())
+        } else if stock_rr_arg {
             QueryArgs::QStock(stock_rr_arg)
         } else if custom_rr_arg {
             QueryArgs::QCustom(custom_rr_arg)
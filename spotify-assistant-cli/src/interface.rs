@@ -1,29 +1,74 @@
 use std::collections::HashMap;
 use std::error::Error;
+use std::path::PathBuf;
 
 use clap::builder::{BoolValueParser, BoolishValueParser, Styles, TypedValueParser, styling};
 use clap::{Arg, ArgAction, ArgGroup, ArgMatches, ColorChoice, Command, arg, value_parser};
 use clap_complete::{Shell, generate};
 use futures::{StreamExt, stream};
-use rspotify::model::{ArtistId, PlaylistId};
-use rspotify::prelude::OAuthClient;
+use regex::Regex;
+use rspotify::model::{AlbumId, ArtistId, FullTrack, PlaylistId, TrackId};
+use rspotify::prelude::{Id, OAuthClient};
 use rspotify::scopes;
 use tracing::{Level, event, info, span};
 
 use crate::commands::followed_artists::cmd_find_artists;
+use crate::commands::playlists::{
+    load_playlist_export, playlist_export, print_playlist_diff, print_version,
+    print_version_changes, resolve_playlist_id,
+};
 use crate::enums::{
-    BlacklistArgs, ConfigArgs, QueryArgs, ReleaseRadarArgs, ReleaseRadarCmds, ShellType,
+    ArtistCmds, BlacklistArgs, ConfigArgs, LibraryCmds, PlaylistCmds, QueryArgs,
+    ReleaseRadarArgs, ReleaseRadarCmds, ShellType, SmartCmds,
 };
+use crate::ui::fuzzy::{pick_multiple_artists, pick_track_match};
+use spotify_assistant_core::actions::artist_follows::ArtistFollower;
+use spotify_assistant_core::actions::collaboration_graph::CollaborationCrawler;
+use spotify_assistant_core::actions::discography::{DiscographyChecker, ReleaseCoverage};
 use spotify_assistant_core::actions::exploration::playlist::PlaylistXplr;
+use spotify_assistant_core::actions::follow_suggestions::FollowSuggester;
 use spotify_assistant_core::actions::general::FullProfiles;
-use spotify_assistant_core::actions::liked_songs::UserLibrary;
+use spotify_assistant_core::actions::library_backup::{LibraryBackup, LibraryRestore, RestoreProgress};
+use spotify_assistant_core::actions::library_prune::LibraryPruner;
+use spotify_assistant_core::actions::library_stats::LibraryAnalyzer;
+use spotify_assistant_core::actions::liked_songs::{LibraryEvent, UserLibrary};
 use spotify_assistant_core::actions::playlist_editor::Modifier;
+use spotify_assistant_core::actions::playlists::combine::{CombineSource, PlaylistCombiner};
+use spotify_assistant_core::actions::playlists::compare::PlaylistDiff;
+use spotify_assistant_core::actions::playlists::dedupe::PlaylistDeduper;
+use spotify_assistant_core::actions::playlists::duplicates::DuplicateReport;
+use spotify_assistant_core::actions::playlists::export::PlaylistExport;
+use spotify_assistant_core::actions::playlists::history::PlaylistHistory;
+use spotify_assistant_core::actions::playlists::health::PlaylistHealth;
+use spotify_assistant_core::actions::playlists::import::{ImportEntry, MatchOutcome, PlaylistImporter};
+use spotify_assistant_core::actions::playlists::metadata::{MetadataChanges, PlaylistMetadataEditor};
+use spotify_assistant_core::actions::playlists::mirror::{DEFAULT_MIRROR_TEMPLATE, LibraryMirror};
 use spotify_assistant_core::actions::playlists::query::PlaylistQuery;
-use spotify_assistant_core::actions::playlists::user::UserPlaylists;
+use spotify_assistant_core::actions::playlists::smart::SmartRefresher;
+use spotify_assistant_core::actions::playlists::sort::PlaylistSorter;
+use spotify_assistant_core::actions::playlists::user::{PlaylistSummary, UserPlaylists};
+use spotify_assistant_core::actions::playlists::split::{DEFAULT_SPLIT_TEMPLATE, PlaylistSplitter};
+use spotify_assistant_core::actions::playlists::writer::PlaylistWriter;
+use spotify_assistant_core::actions::saved_albums::{CompletionCriteria, SavedAlbums};
 use spotify_assistant_core::actions::user::UserData;
+use spotify_assistant_core::enums::combine::{CombineOrder, SetOperation};
+use spotify_assistant_core::enums::duplication::DedupeMode;
 use spotify_assistant_core::enums::fs::ProjectDirectories;
+use spotify_assistant_core::enums::graph_format::GraphFormat;
+use spotify_assistant_core::enums::mirror::MirrorPeriod;
+use spotify_assistant_core::enums::pl::PlaylistType;
+use spotify_assistant_core::enums::playlist_format::{ImportFormat, PlaylistFormat};
+use spotify_assistant_core::enums::prune::PruneRule;
+use spotify_assistant_core::enums::sort::SortKey;
+use spotify_assistant_core::enums::split::SplitBy;
+use spotify_assistant_core::models::smart_playlist::{SmartPlaylist, SmartPlaylists, SmartRule};
+use spotify_assistant_core::enums::report::ReportFormat;
 use spotify_assistant_core::models::blacklist::{Blacklist, BlacklistArtist};
+use spotify_assistant_core::models::configuration::Duplicates;
+use spotify_assistant_core::models::followed_artists::{ArtistChange, ArtistChangeKind, ArtistSnapshot};
 use spotify_assistant_core::traits::apis::Api;
+use spotify_assistant_database::sqlite::followed_artists::FollowedArtistsHistory;
+use spotify_assistant_database::sqlite::playlist_versions::PlaylistVersions;
 
 /// Generates auto-complete scripts for different shell types.
 ///
@@ -107,6 +152,9 @@ impl TerminalApp {
                 Self::config_command(),
                 Self::listening_history_command(),
                 Self::query_command(),
+                Self::smart_command(),
+                Self::library_command(),
+                Self::artists_command(),
             ]);
 
         TerminalApp { command: app_cmd }
@@ -165,6 +213,7 @@ impl TerminalApp {
                                 subcommand
                             );
                             self.run_playlist_command(subcommand)
+                                .await
                                 .expect("Couldn't complete the playlist command execution");
                         }
                         "config" => {
@@ -200,6 +249,39 @@ impl TerminalApp {
                                 .await
                                 .expect("Couldn't complete the query command execution");
                         }
+                        "smart" => {
+                            event!(
+                                Level::TRACE,
+                                "Subcommand 'smart' detected; executing run_smart_command \
+                            with the following arguments:\n{:?}",
+                                subcommand
+                            );
+                            self.run_smart_command(subcommand)
+                                .await
+                                .expect("Couldn't complete the smart playlist command execution");
+                        }
+                        "library" => {
+                            event!(
+                                Level::TRACE,
+                                "Subcommand 'library' detected; executing run_library_command \
+                            with the following arguments:\n{:?}",
+                                subcommand
+                            );
+                            self.run_library_command(subcommand)
+                                .await
+                                .expect("Couldn't complete the library command execution");
+                        }
+                        "artists" => {
+                            event!(
+                                Level::TRACE,
+                                "Subcommand 'artists' detected; executing run_artists_command \
+                            with the following arguments:\n{:?}",
+                                subcommand
+                            );
+                            self.run_artists_command(subcommand)
+                                .await
+                                .expect("Couldn't complete the artists command execution");
+                        }
                         "listeninghistory" => {
                             event!(
                                 Level::TRACE,
@@ -223,327 +305,1974 @@ impl TerminalApp {
 
     /// Scans and processes the playlists subcommand.
     ///
-    /// This function handles the arguments for the playlists subcommand and logs information as needed.
+    /// This function dispatches the playlists subcommands.
     ///
     /// # Arguments
     /// * `matches` - The argument matches for the playlists subcommand.
-    fn run_playlist_command(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    async fn run_playlist_command(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
         let span = span!(Level::INFO, "TerminalApp.run_playlist_command");
         let _enter = span.enter();
 
-        let plist = matches
-            .get_one::<bool>("plist")
-            .unwrap_or(&false)
-            .to_string();
-        let pcreate = matches
-            .get_one::<bool>("pcreate")
-            .unwrap_or(&false)
-            .to_string();
-        let pmove = matches
-            .get_one::<String>("pmove")
-            .unwrap_or(&"none".to_string())
-            .to_string();
-        let pdelete = matches
-            .get_one::<bool>("pdelete")
-            .unwrap_or(&false)
-            .to_string();
-        event!(
-            Level::DEBUG,
-            "Playlist list: {:?}; Playlist create: {:?}; Playlist move: {:?}; Playlist delete: {:?}",
-            &plist,
-            &pcreate,
-            &pmove,
-            &pdelete
-        );
-        Ok(())
+        match PlaylistCmds::from_matches(matches) {
+            PlaylistCmds::Dedupe(dedupe_arguments) => {
+                self.run_dedupe_subcommand(&dedupe_arguments).await
+            }
+            PlaylistCmds::Export(export_arguments) => {
+                self.run_export_subcommand(&export_arguments).await
+            }
+            PlaylistCmds::Import(import_arguments) => {
+                self.run_import_subcommand(&import_arguments).await
+            }
+            PlaylistCmds::Diff(diff_arguments) => {
+                self.run_diff_subcommand(&diff_arguments).await
+            }
+            PlaylistCmds::Combine(combine_arguments) => {
+                self.run_combine_subcommand(&combine_arguments).await
+            }
+            PlaylistCmds::Split(split_arguments) => {
+                self.run_split_subcommand(&split_arguments).await
+            }
+            PlaylistCmds::Sort(sort_arguments) => {
+                self.run_sort_subcommand(&sort_arguments).await
+            }
+            PlaylistCmds::Track => self.run_track_subcommand().await,
+            PlaylistCmds::History(history_arguments) => {
+                self.run_history_subcommand(&history_arguments).await
+            }
+            PlaylistCmds::Show(show_arguments) => {
+                self.run_show_subcommand(&show_arguments).await
+            }
+            PlaylistCmds::Edit(edit_arguments) => {
+                self.run_edit_subcommand(&edit_arguments).await
+            }
+            PlaylistCmds::Check(check_arguments) => {
+                self.run_check_subcommand(&check_arguments).await
+            }
+            PlaylistCmds::List(list_arguments) => {
+                self.run_list_subcommand(&list_arguments).await
+            }
+            PlaylistCmds::Create(create_arguments) => {
+                self.run_create_subcommand(&create_arguments).await
+            }
+            PlaylistCmds::Delete(delete_arguments) => {
+                self.run_delete_subcommand(&delete_arguments).await
+            }
+            PlaylistCmds::Move(move_arguments) => {
+                self.run_move_subcommand(&move_arguments).await
+            }
+            PlaylistCmds::Empty => {
+                event!(Level::DEBUG, "No playlists subcommand given");
+                Ok(())
+            }
+        }
     }
 
-    /// Scans and processes the config subcommand.
+    /// Runs the dedupe subcommand of the playlists command.
     ///
-    /// This function handles the arguments for the config subcommand, performs required actions,
-    /// and executes blacklist-related functions or shell script generation when required.
+    /// This function finds every later occurrence of a duplicated track in the playlist, removes
+    /// them by position unless a dry run was requested, and prints what was (or would be) removed.
     ///
     /// # Arguments
-    /// * `config_arguments` - The argument matches for the config subcommand.
-    async fn run_config_command(
+    /// * `dedupe_arguments` - The argument matches for the dedupe subcommand.
+    async fn run_dedupe_subcommand(
         &self,
-        config_arguments: &ArgMatches,
+        dedupe_arguments: &ArgMatches,
     ) -> Result<(), Box<dyn Error>> {
-        let span = span!(Level::INFO, "TerminalApp.run_config_command");
+        let span = span!(Level::INFO, "TerminalApp.run_dedupe_subcommand");
         let _enter = span.enter();
 
-        match ConfigArgs::from_matches(config_arguments) {
-            ConfigArgs::Set(key, value) => {
-                println!("Setting config value: {} = {}", key, value);
-                Ok(())
-            }
-            ConfigArgs::Unset(key) => {
-                println!("Unsetting config value: {}", key);
-                Ok(())
-            }
-            ConfigArgs::Get(key) => {
-                println!("Getting config value: {}", key);
-                Ok(())
-            }
-            ConfigArgs::Shell(shell) => {
-                let mut cmd = self.command.clone();
-                generate_auto_complete!(shell, cmd);
-                Ok(())
-            }
-            ConfigArgs::Blacklist(args) => match self.run_blacklist_subcommand(&args).await {
-                Ok(_) => Ok(()),
-                Err(err) => Err(err),
-            },
-            ConfigArgs::Unfollowed(playlist_name) => {
-                let filename = format!(
-                    "playlist-artists/{playlist_name}_artists_not_followed-10-20-2025.json"
-                );
-                event!(Level::DEBUG, "Filename: {:?}", filename);
-                let file_path = ProjectDirectories::Data.path().join(filename);
-                let selected_artists = cmd_find_artists(file_path)?;
-                let scope = scopes!("user-follow-modify");
-                let client = PlaylistQuery::set_up_client(false, Some(scope)).await;
-                let artist_ids: Vec<ArtistId> = selected_artists.into_iter().filter_map(|artist| match ArtistId::from_id(artist.id().to_string()) {
-                    Ok(id) => Some(id),
-                    Err(_) => {
-                        let id_str = artist.id();
-                        event!(Level::ERROR, %id_str, "Could not instantiate an ArtistID object for {}", artist.name());
-                        None
-                    }
-                }).collect();
-                event!(
-                    Level::DEBUG,
-                    "About to add {} Artist IDs: {:?}",
-                    artist_ids.len(),
-                    artist_ids
-                );
-                client
-                    .user_follow_artists(artist_ids)
-                    .await
-                    .expect("Couldn't add all artists");
-                let user_data = UserData::new().await;
-                user_data.update_followed_artists().await;
+        let playlist = dedupe_arguments
+            .get_one::<String>("ddplaylist")
+            .expect("The playlist argument is required");
+        let mode = dedupe_arguments
+            .get_one::<String>("ddmode")
+            .map(|mode| mode.parse::<DedupeMode>())
+            .transpose()?
+            .unwrap_or_default();
+        let dry_run = dedupe_arguments.get_flag("dddryrun");
 
-                Ok(())
-            }
-            ConfigArgs::Empty => {
-                println!("No config subcommand");
-                Ok(())
-            }
+        let playlist_id = resolve_playlist_id(playlist).await?;
+        let deduper = PlaylistDeduper::new(playlist_id, mode).await;
+        let removed = deduper.later_occurrences().await;
+        if !dry_run && !removed.is_empty() {
+            deduper.remove(&removed).await?;
         }
+        PlaylistDeduper::print_removed(&deduper.name(), &removed, dry_run);
+        Ok(())
     }
 
-    /// Scans and processes the blacklist subcommand.
+    /// Runs the export subcommand of the playlists command.
     ///
-    /// This function performs operations on the blacklist, such as adding or removing artists. It supports
-    /// operations like adding artists directly, selecting from a playlist, and removing artists by name or selection.
+    /// This function writes a single playlist to a file, or every owned playlist into a
+    /// directory when `--all` is given.
     ///
     /// # Arguments
-    /// * `blacklist_arguments` - The argument matches for the blacklist subcommand.
-    async fn run_blacklist_subcommand(
+    /// * `export_arguments` - The argument matches for the export subcommand.
+    async fn run_export_subcommand(
         &self,
-        blacklist_arguments: &ArgMatches,
+        export_arguments: &ArgMatches,
     ) -> Result<(), Box<dyn Error>> {
-        let span = span!(Level::INFO, "TerminalApp.run_config_command");
+        let span = span!(Level::INFO, "TerminalApp.run_export_subcommand");
         let _enter = span.enter();
 
-        let mut blacklist = Blacklist::default();
-        match BlacklistArgs::from_matches(blacklist_arguments) {
-            BlacklistArgs::Add(artist_id) => {
-                let full_artist = FullProfiles::new().await.artist(artist_id).await;
-                let artist_name = full_artist.name;
-                let artist_id =
-                    full_artist.id.to_string().split(':').collect::<Vec<&str>>()[2].to_string();
-                let artist = BlacklistArtist::new(artist_name, artist_id);
-                println!(
-                    "Adding {}'s ID to blacklist: {}",
-                    artist.name(),
-                    artist.id()
-                );
-                blacklist.add_artist(artist);
-                Ok(())
-            }
-            BlacklistArgs::AddFromPlaylist(playlist) => {
-                let normalized_input = playlist.clone().trim().to_lowercase();
-                let user_playlists = UserPlaylists::new().await;
-                let playlist_names_and_ids =
-                    user_playlists.get_user_playlist_ids_as_hashmap().await;
-                let playlist_id = match playlist_names_and_ids.iter().find_map(|(name, id)| {
-                    event!(Level::DEBUG, "Testing input as name: {:?}", &playlist);
-                    if name.clone().to_lowercase() == normalized_input {
-                        event!(
-                            Level::DEBUG,
-                            "Input matches a name (playlist, input): {:?}, {:?}",
-                            &playlist,
-                            &name.to_lowercase()
-                        );
-                        let new_id_string = id
-                            .to_string()
-                            .split(':')
-                            .collect::<Vec<&str>>()
-                            .iter()
-                            .map(|slice| slice.to_string())
-                            .collect::<Vec<String>>();
-                        event!(Level::DEBUG, "ID string: {:?}", new_id_string);
-                        match PlaylistId::from_id(new_id_string[2].clone()) {
-                            Ok(id) => Some(id),
-                            Err(err) => {
-                                event!(
-                                    Level::ERROR,
-                                    "Failed to parse playlist ID '{}': {:?}",
-                                    &playlist,
-                                    err
-                                );
-                                None
-                            }
-                        }
-                    } else {
-                        None
-                    }
-                }) {
-                    Some(id) => id,
-                    None => {
-                        println!("Could not find a playlist matching the provided name.");
-                        match PlaylistId::from_id(playlist.clone()) {
-                            Ok(unwrapped_playlist_id) => {
-                                event!(Level::DEBUG, "Input as ID: {:?}", playlist.clone());
-                                unwrapped_playlist_id
-                            }
-                            Err(err) => {
-                                println!(
-                                    "The provided input does not match any playlist IDs or names in your account."
-                                );
-                                let available_names: Vec<_> = playlist_names_and_ids
-                                    .keys()
-                                    .map(|name| name.as_str())
-                                    .collect();
-                                println!("Available playlist names: {:?}", available_names);
-                                eprintln!("ID Error: {:?}", err);
-                                return Err(Box::new(err));
-                            }
-                        }
-                    }
-                };
-                let playlist = PlaylistXplr::new(playlist_id, false).await;
-                let artists = playlist.artists_by_album().await;
-                let selected = match blacklist.select_artist_to_add_by_album(artists) {
-                    Some(blacklisted_artist) => blacklisted_artist,
-                    None => {
-                        println!("No artist selected to add to the blacklist.");
-                        return Ok(());
-                    }
-                };
-                println!("Selected: {:?}", selected);
-                blacklist.add_artist(selected);
-                Ok(())
-            }
-            BlacklistArgs::RemoveByName(name) => {
-                match blacklist
-                    .artists()
-                    .iter()
-                    .find(|artist| blacklist.are_names_equal(name.as_str(), artist.name()))
-                {
-                    Some(artist) => {
-                        let artist_name = artist.name();
-                        let artist_id = artist.id();
-                        blacklist.remove_artist(artist_name.as_str(), artist_id.as_str());
-                    }
-                    None => {
-                        println!("{} not found in the blacklist.", name);
-                        return Ok(());
-                    }
-                }
-                println!("{}'s ID was removed from the blacklist", name);
-                Ok(())
-            }
-            BlacklistArgs::RemoveBySelect => {
-                blacklist.select_artist_to_remove();
-                Ok(())
-            }
-            BlacklistArgs::Empty => {
-                blacklist.print_blacklist();
-                Ok(())
-            }
+        let format = export_arguments
+            .get_one::<String>("exformat")
+            .expect("The format argument has a default value")
+            .parse::<PlaylistFormat>()?;
+        let output = export_arguments
+            .get_one::<String>("exoutput")
+            .map(std::path::PathBuf::from);
+
+        if export_arguments.get_flag("exall") {
+            let directory = output.unwrap_or_else(PlaylistExport::default_export_dir);
+            let written = PlaylistExport::export_all(&directory, format).await?;
+            println!(
+                "Exported {} playlists to {}",
+                written.len(),
+                directory.display()
+            );
+            return Ok(());
         }
+
+        let playlist = export_arguments
+            .get_one::<String>("explaylist")
+            .expect("A playlist is required unless --all is given");
+        let playlist_id = resolve_playlist_id(playlist).await?;
+        let (xplr, items) = PlaylistXplr::with_items(playlist_id, false).await;
+        let export = PlaylistExport::from_playlist(&xplr, items);
+        let path = output.unwrap_or_else(|| export.default_path(format));
+        export.export(&path, format)?;
+        println!(
+            "Exported {} tracks from {} to {}",
+            export.tracks.len(),
+            export.name,
+            path.display()
+        );
+        Ok(())
     }
 
-    /// Runs the Release Radar subcommand.
+    /// Runs the import subcommand of the playlists command.
     ///
-    /// This function handles the arguments for the Release Radar subcommand and executes the corresponding logic to
-    /// update or compare playlists.
+    /// This function parses the import file, resolves every entry to a Spotify track (asking
+    /// about ambiguous matches unless `--min-confidence` is given), appends the matched tracks
+    /// to the target playlist and writes the unmatched entries to a report file.
     ///
     /// # Arguments
-    /// * `release_radar_arguments` - The argument matches for the Release Radar subcommand.
-    async fn run_rr_command(
+    /// * `import_arguments` - The argument matches for the import subcommand.
+    async fn run_import_subcommand(
         &self,
-        release_radar_arguments: &ArgMatches,
+        import_arguments: &ArgMatches,
     ) -> Result<(), Box<dyn Error>> {
-        match ReleaseRadarCmds::from_matches(release_radar_arguments) {
-            ReleaseRadarCmds::Update(update_value) => {
-                match ReleaseRadarArgs::from_update_matches(&update_value) {
-                    ReleaseRadarArgs::UPrint(print) => {
-                        println!("Printing Release Radar Progress: {:?}", print);
-                        Ok(())
-                    }
-                    ReleaseRadarArgs::Empty => {
-                        info!(
-                            "Backing up the current Release Radar playlist into the Lagging Release Radar playlist"
-                        );
-                        Modifier::lagging_release_radar()
-                            .await
-                            .update_playlist()
-                            .await;
-                        info!("Updating Release Radar playlist");
-                        Modifier::release_radar().await.update_playlist().await;
-                        Ok(())
-                    }
-                    _ => Ok(()),
-                }
-            }
-            ReleaseRadarCmds::Compare(compare_value) => {
-                match ReleaseRadarArgs::from_compare_matches(&compare_value) {
-                    ReleaseRadarArgs::CPlaylists(playlist) => {
-                        println!("Comparing Release Radar playlists: {:?}", playlist);
-                        Ok(())
-                    }
-                    ReleaseRadarArgs::Empty => {
-                        println!("No Release Radar compare argument");
-                        Ok(())
-                    }
-                    _ => Ok(()),
-                }
-            }
-            _ => {
-                println!("No Release Radar subcommand");
-                Ok(())
-            }
+        let span = span!(Level::INFO, "TerminalApp.run_import_subcommand");
+        let _enter = span.enter();
+
+        let file = import_arguments
+            .get_one::<String>("imfile")
+            .map(PathBuf::from)
+            .expect("The file argument is required");
+        let target = import_arguments
+            .get_one::<String>("iminto")
+            .expect("The target playlist argument is required");
+        let format = match import_arguments.get_one::<String>("imformat") {
+            Some(format) => format.parse::<ImportFormat>()?,
+            None => ImportFormat::from_path(&file),
+        };
+        let min_confidence = import_arguments.get_one::<f64>("immincon").copied();
+        let unmatched_path = import_arguments
+            .get_one::<String>("imunmatched")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PlaylistImporter::default_unmatched_path(&file));
+
+        let contents = std::fs::read_to_string(&file)?;
+        let entries = ImportEntry::parse(&contents, format);
+        println!("Read {} entries from {} ({format})", entries.len(), file.display());
+
+        let importer = PlaylistImporter::new().await;
+        let report = importer
+            .resolve(entries, min_confidence, pick_track_match)
+            .await;
+        if report.matched.is_empty() {
+            println!("No entries could be matched, {target} was not changed");
+        } else {
+            let written = PlaylistWriter::new("Imported by spotify-assistant")
+                .await
+                .append(target, report.tracks())
+                .await?;
+            println!(
+                "Matched {} entries, added {} tracks to {}",
+                report.matched.len(),
+                written.written,
+                written.name
+            );
+        }
+        if !report.unmatched.is_empty() {
+            PlaylistImporter::write_unmatched(&unmatched_path, &file, &report.unmatched)?;
+            println!(
+                "{} entries could not be matched, see {}",
+                report.unmatched.len(),
+                unmatched_path.display()
+            );
         }
+        Ok(())
     }
 
-    /// Runs the query subcommand.
+    /// Runs the diff subcommand of the playlists command.
     ///
-    /// This function handles the arguments for the query subcommand, allowing users to interact with playlists
-    /// and the blacklist to retrieve data.
+    /// This function compares two playlists, or two JSON snapshots of a playlist, track by
+    /// track and prints the result as a side-by-side table or as JSON.
     ///
     /// # Arguments
-    /// * `query_arguments` - The argument matches for the query subcommand.
-    async fn run_query_command(&self, query_arguments: &ArgMatches) -> Result<(), Box<dyn Error>> {
-        let span = span!(Level::TRACE, "TerminalApp.run_query_command");
+    /// * `diff_arguments` - The argument matches for the diff subcommand.
+    async fn run_diff_subcommand(&self, diff_arguments: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_diff_subcommand");
         let _enter = span.enter();
 
-        event!(
-            Level::DEBUG,
-            "Stock RR: {:?} | Custom RR: {:?} | Blacklist: {:?} | Library: {:?}",
-            query_arguments
-                .get_one::<bool>("qstock")
-                .unwrap_or(&false)
-                .to_string(),
-            query_arguments
-                .get_one::<bool>("qcustom")
-                .unwrap_or(&false)
-                .to_string(),
-            query_arguments
-                .get_one::<bool>("qblacklist")
+        let left = diff_arguments
+            .get_one::<String>("dfleft")
+            .expect("The left playlist argument is required");
+        let right = diff_arguments
+            .get_one::<String>("dfright")
+            .expect("The right playlist argument is required");
+        let json = diff_arguments
+            .get_one::<String>("dfformat")
+            .is_some_and(|format| format == "json");
+
+        let left = load_playlist_export(left).await?;
+        let right = load_playlist_export(right).await?;
+        print_playlist_diff(&PlaylistDiff::new(&left, &right), json)
+    }
+
+    /// Runs the combine subcommand of the playlists command.
+    ///
+    /// This function loads the source playlists (and the liked songs for `@liked`), applies the
+    /// set operation and writes the result, in the requested order, into the target playlist.
+    ///
+    /// # Arguments
+    /// * `combine_arguments` - The argument matches for the combine subcommand.
+    async fn run_combine_subcommand(
+        &self,
+        combine_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_combine_subcommand");
+        let _enter = span.enter();
+
+        let operation = combine_arguments
+            .get_one::<String>("cboperation")
+            .expect("The operation argument is required")
+            .parse::<SetOperation>()?;
+        let mode = combine_arguments
+            .get_one::<String>("cbmode")
+            .expect("The mode argument has a default value")
+            .parse::<DedupeMode>()?;
+        let order = combine_arguments
+            .get_one::<String>("cborder")
+            .expect("The order argument has a default value")
+            .parse::<CombineOrder>()?;
+        let append = combine_arguments.get_flag("cbappend");
+        let force = combine_arguments.get_flag("cbforce");
+        let dry_run = combine_arguments.get_flag("cbdryrun");
+
+        let mut sources = Vec::new();
+        for input in combine_arguments
+            .get_many::<String>("cbsources")
+            .expect("At least two sources are required")
+        {
+            let source = if CombineSource::is_liked_songs(input) {
+                CombineSource::liked_songs().await
+            } else {
+                CombineSource::playlist(resolve_playlist_id(input).await?).await
+            };
+            sources.push(source);
+        }
+
+        let combined = PlaylistCombiner::combine(&sources, operation, mode);
+        let tracks = PlaylistCombiner::order(combined, order);
+        let source_names = sources
+            .iter()
+            .map(|source| source.name.as_str())
+            .collect::<Vec<&str>>();
+        println!(
+            "{} of {}: {} tracks",
+            operation,
+            source_names.join(", "),
+            tracks.len()
+        );
+        if dry_run {
+            for (index, track) in tracks.iter().enumerate() {
+                let artists = track
+                    .artists
+                    .iter()
+                    .map(|artist| artist.name.as_str())
+                    .collect::<Vec<&str>>();
+                println!("    {:>4}. {} - {}", index + 1, artists.join(", "), track.name);
+            }
+            return Ok(());
+        }
+
+        let target = combine_arguments
+            .get_one::<String>("cbinto")
+            .expect("The target playlist is required unless --dry-run is given");
+        let description = format!("{} of {}", operation, source_names.join(", "));
+        let writer = PlaylistWriter::new(&description).await;
+        let written = if append {
+            writer.append(target, tracks).await?
+        } else {
+            writer.replace(target, tracks, force).await?
+        };
+        println!(
+            "{} {} tracks to {}{}",
+            if append { "Added" } else { "Wrote" },
+            written.written,
+            written.name,
+            if written.created { " (new playlist)" } else { "" }
+        );
+        Ok(())
+    }
+
+    /// Runs the split subcommand of the playlists command.
+    ///
+    /// This function groups the tracks of a playlist, prints the size of every group and writes
+    /// one child playlist per group that reaches the minimum size.
+    ///
+    /// # Arguments
+    /// * `split_arguments` - The argument matches for the split subcommand.
+    async fn run_split_subcommand(
+        &self,
+        split_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_split_subcommand");
+        let _enter = span.enter();
+
+        let input = split_arguments
+            .get_one::<String>("spplaylist")
+            .expect("The playlist argument is required");
+        let by = split_arguments
+            .get_one::<String>("spby")
+            .expect("The split key is required")
+            .parse::<SplitBy>()?;
+        let min_size = *split_arguments
+            .get_one::<usize>("spminsize")
+            .expect("The minimum size has a default value");
+        let template = split_arguments
+            .get_one::<String>("spname")
+            .expect("The naming template has a default value");
+        let dry_run = split_arguments.get_flag("spdryrun");
+        let force = split_arguments.get_flag("spforce");
+
+        let source = if CombineSource::is_liked_songs(input) {
+            CombineSource::liked_songs().await
+        } else {
+            CombineSource::playlist(resolve_playlist_id(input).await?).await
+        };
+        let genres = match by {
+            SplitBy::Genre => {
+                PlaylistSplitter::lead_artist_genres(&FullProfiles::new().await, &source.tracks)
+                    .await
+            }
+            _ => HashMap::new(),
+        };
+        let groups = PlaylistSplitter::group(&source.tracks, by, &genres);
+        let (kept, skipped) = PlaylistSplitter::partition(groups, min_size);
+
+        println!(
+            "{} split by {}: {} groups of at least {} tracks",
+            source.name,
+            by,
+            kept.len(),
+            min_size
+        );
+        for group in &kept {
+            println!(
+                "    {:>5}  {}",
+                group.tracks.len(),
+                group.name(template, &source.name, by)
+            );
+        }
+        if !skipped.is_empty() {
+            let skipped_tracks = skipped
+                .iter()
+                .map(|group| group.tracks.len())
+                .sum::<usize>();
+            println!(
+                "Skipping {} smaller groups ({} tracks)",
+                skipped.len(),
+                skipped_tracks
+            );
+        }
+        if dry_run {
+            return Ok(());
+        }
+
+        let description = format!("{} split by {}", source.name, by);
+        let writer = PlaylistWriter::new(&description).await;
+        for group in kept {
+            let name = group.name(template, &source.name, by);
+            let written = writer.replace(&name, group.tracks, force).await?;
+            println!(
+                "Wrote {} tracks to {}{}",
+                written.written,
+                written.name,
+                if written.created { " (new playlist)" } else { "" }
+            );
+        }
+        Ok(())
+    }
+
+    /// Runs the sort subcommand of the playlists command.
+    ///
+    /// This function plans the reorder moves that sort the playlist and applies them unless a
+    /// dry run was requested. Items are moved, not re-added, so their addition dates are kept.
+    ///
+    /// # Arguments
+    /// * `sort_arguments` - The argument matches for the sort subcommand.
+    async fn run_sort_subcommand(
+        &self,
+        sort_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_sort_subcommand");
+        let _enter = span.enter();
+
+        let playlist = sort_arguments
+            .get_one::<String>("soplaylist")
+            .expect("The playlist argument is required");
+        let key = sort_arguments
+            .get_one::<String>("soby")
+            .expect("The sort key is required")
+            .parse::<SortKey>()?;
+        let descending = sort_arguments.get_flag("sodesc");
+        let dry_run = sort_arguments.get_flag("sodryrun");
+
+        let playlist_id = resolve_playlist_id(playlist).await?;
+        let sorter = PlaylistSorter::new(playlist_id, key, descending).await;
+        let moves = sorter.plan().await;
+        let direction = if descending { "descending" } else { "ascending" };
+        if moves.is_empty() {
+            println!("{} is already sorted by {} ({})", sorter.name(), key, direction);
+            return Ok(());
+        }
+        if dry_run {
+            println!(
+                "Sorting {} by {} ({}) would take {} moves",
+                sorter.name(),
+                key,
+                direction,
+                moves.len()
+            );
+            return Ok(());
+        }
+        sorter.apply(&moves).await?;
+        println!(
+            "Sorted {} by {} ({}) with {} moves",
+            sorter.name(),
+            key,
+            direction,
+            moves.len()
+        );
+        Ok(())
+    }
+
+    /// Runs the track subcommand of the playlists command.
+    ///
+    /// This function records a new version of every owned playlist whose snapshot changed since
+    /// the last run and prints what changed. It is meant to be run regularly, e.g. from cron.
+    async fn run_track_subcommand(&self) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_track_subcommand");
+        let _enter = span.enter();
+
+        let tracked = PlaylistVersions::open().await?.track_owned().await?;
+        let recorded = tracked.iter().filter(|playlist| playlist.recorded).count();
+        for playlist in tracked.iter().filter(|playlist| playlist.recorded) {
+            match &playlist.diff {
+                Some(diff) => println!(
+                    "{}: +{} -{} ({} moved)",
+                    playlist.name,
+                    diff.only_right.len(),
+                    diff.only_left.len(),
+                    diff.moved.len()
+                ),
+                None => println!("{}: first version recorded", playlist.name),
+            }
+        }
+        println!(
+            "Recorded new versions of {} of {} playlists",
+            recorded,
+            tracked.len()
+        );
+        Ok(())
+    }
+
+    /// Runs the history subcommand of the playlists command.
+    ///
+    /// This function prints every recorded version of a playlist with the tracks added (and by
+    /// whom) and removed since the previous version.
+    ///
+    /// # Arguments
+    /// * `history_arguments` - The argument matches for the history subcommand.
+    async fn run_history_subcommand(
+        &self,
+        history_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_history_subcommand");
+        let _enter = span.enter();
+
+        let playlist = history_arguments
+            .get_one::<String>("hiplaylist")
+            .expect("The playlist argument is required");
+        let playlist_id = resolve_playlist_id(playlist).await?;
+        let history = PlaylistVersions::open().await?.history(playlist_id.id())?;
+        let versions = history.versions();
+        let Some(first) = versions.first() else {
+            println!("No versions of {playlist} recorded yet. Run `playlists track` first.");
+            return Ok(());
+        };
+        println!("{} recorded versions of {}", versions.len(), first.name);
+        print_version_changes(first, None);
+        for (version, diff) in history.changes() {
+            print_version_changes(&version, Some(&diff));
+        }
+        Ok(())
+    }
+
+    /// Runs the show subcommand of the playlists command.
+    ///
+    /// This function prints the tracks of the playlist version recorded at or before a date.
+    ///
+    /// # Arguments
+    /// * `show_arguments` - The argument matches for the show subcommand.
+    async fn run_show_subcommand(
+        &self,
+        show_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_show_subcommand");
+        let _enter = span.enter();
+
+        let playlist = show_arguments
+            .get_one::<String>("shplaylist")
+            .expect("The playlist argument is required");
+        let moment = PlaylistHistory::parse_moment(
+            show_arguments
+                .get_one::<String>("shat")
+                .expect("The date argument is required"),
+        )?;
+        let playlist_id = resolve_playlist_id(playlist).await?;
+        match PlaylistVersions::open().await?.history(playlist_id.id())?.at(moment) {
+            Some(version) => print_version(version),
+            None => println!("No version of {playlist} was recorded by {moment}"),
+        }
+        Ok(())
+    }
+
+    /// Runs the edit subcommand of the playlists command.
+    ///
+    /// This function plans the metadata changes for every owned playlist whose name matches the
+    /// pattern, prints them as a table and applies them after confirmation.
+    ///
+    /// # Arguments
+    /// * `edit_arguments` - The argument matches for the edit subcommand.
+    async fn run_edit_subcommand(
+        &self,
+        edit_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_edit_subcommand");
+        let _enter = span.enter();
+
+        let pattern = Regex::new(
+            edit_arguments
+                .get_one::<String>("edmatch")
+                .expect("The match argument is required"),
+        )?;
+        let changes = MetadataChanges {
+            rename: edit_arguments.get_one::<String>("edrename").cloned(),
+            description: edit_arguments.get_one::<String>("eddescription").cloned(),
+            public: edit_arguments.get_one::<bool>("edpublic").copied(),
+            collaborative: edit_arguments.get_one::<bool>("edcollaborative").copied(),
+        };
+        if changes.is_empty() {
+            return Err("Nothing to change: give --rename, --description, --public or --collaborative".into());
+        }
+        let skip_confirmation = edit_arguments.get_flag("edyes");
+
+        let editor = PlaylistMetadataEditor::new().await;
+        let planned = editor.plan(&pattern, &changes).await;
+        if planned.is_empty() {
+            println!("No owned playlist matching {} needs changes", pattern);
+            return Ok(());
+        }
+        let flag = |value: Option<bool>| match value {
+            Some(true) => "yes",
+            Some(false) => "no",
+            None => "-",
+        };
+        let name_width = planned
+            .iter()
+            .map(|edit| edit.name.chars().count())
+            .max()
+            .unwrap_or_default()
+            .max("Playlist".len());
+        println!(
+            "{:<name_width$}  {:<name_width$}  {:<6}  {:<6}  Description",
+            "Playlist", "New name", "Public", "Collab"
+        );
+        for edit in &planned {
+            println!(
+                "{:<name_width$}  {:<name_width$}  {:<6}  {:<6}  {}",
+                edit.name,
+                edit.new_name.as_deref().unwrap_or("-"),
+                flag(edit.public),
+                flag(edit.collaborative),
+                edit.description.as_deref().unwrap_or("-")
+            );
+        }
+        let confirmed = skip_confirmation
+            || dialoguer::Confirm::with_theme(&dialoguer::theme::ColorfulTheme::default())
+                .with_prompt(format!("Apply these changes to {} playlists?", planned.len()))
+                .default(false)
+                .interact()?;
+        if !confirmed {
+            println!("No playlists were changed");
+            return Ok(());
+        }
+        let updated = editor.apply(&planned).await;
+        println!("Updated {} of {} playlists", updated, planned.len());
+        Ok(())
+    }
+
+    /// Runs the check subcommand of the playlists command.
+    ///
+    /// This function reports the items of one playlist, or of every owned playlist, that are
+    /// unavailable, unplayable, restricted, local files, episodes or relinked. With `--replace`,
+    /// playable versions of the affected tracks are searched and swapped in at the same
+    /// position, either picked by the user or, with `--yes`, taken when the match is clear.
+    ///
+    /// # Arguments
+    /// * `check_arguments` - The argument matches for the check subcommand.
+    async fn run_check_subcommand(
+        &self,
+        check_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_check_subcommand");
+        let _enter = span.enter();
+
+        let replace = check_arguments.get_flag("chreplace");
+        let automatic = check_arguments.get_flag("chyes");
+        let playlist_ids = if check_arguments.get_flag("chall") {
+            UserPlaylists::new()
+                .await
+                .get_owned_playlists()
+                .await
+                .into_iter()
+                .map(|playlist| playlist.id)
+                .collect::<Vec<PlaylistId<'static>>>()
+        } else {
+            let input = check_arguments
+                .get_one::<String>("chplaylist")
+                .expect("Either a playlist or --all is required");
+            vec![resolve_playlist_id(input).await?]
+        };
+
+        let mut problem_count = 0;
+        let mut swapped = 0;
+        for playlist_id in playlist_ids {
+            let mut health = PlaylistHealth::new(playlist_id).await;
+            let problems = health.problems().await;
+            if problems.is_empty() {
+                println!("{}: all items are playable", health.name());
+                continue;
+            }
+            problem_count += problems.len();
+            println!("{}: {} items with issues", health.name(), problems.len());
+            for problem in &problems {
+                println!(
+                    "    {:>4}. {}  [{}]",
+                    problem.position + 1,
+                    problem.description,
+                    problem.issues_summary()
+                );
+            }
+            if !replace {
+                continue;
+            }
+            for problem in problems.iter().filter(|problem| problem.is_replaceable()) {
+                let candidates = health.replacements(problem).await;
+                if candidates.is_empty() {
+                    println!("No replacement found for {}", problem.description);
+                    continue;
+                }
+                let chosen = if automatic {
+                    match PlaylistImporter::decide(candidates, None) {
+                        MatchOutcome::Matched(candidate) => Some(candidate.track),
+                        _ => {
+                            println!("No clear replacement for {}, skipped", problem.description);
+                            None
+                        }
+                    }
+                } else {
+                    let entry = problem.entry().expect("Replaceable problems are tracks");
+                    pick_track_match(&entry, &candidates)
+                        .map(|index| candidates[index].track.clone())
+                };
+                if let Some(replacement) = chosen {
+                    if health.swap(problem, &replacement).await? {
+                        swapped += 1;
+                    } else {
+                        println!("{} is already in the playlist, {} kept", replacement.name, problem.description);
+                    }
+                }
+            }
+        }
+        if replace {
+            println!("{} items with issues, {} replaced", problem_count, swapped);
+        } else {
+            println!("{} items with issues", problem_count);
+        }
+        Ok(())
+    }
+
+    /// Runs the list subcommand of the playlists command.
+    ///
+    /// This function prints the playlists in the user's library with their owner, track count,
+    /// visibility and snapshot, as a table or as JSON.
+    ///
+    /// # Arguments
+    /// * `list_arguments` - The argument matches for the list subcommand.
+    async fn run_list_subcommand(&self, list_arguments: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_list_subcommand");
+        let _enter = span.enter();
+
+        let json = list_arguments
+            .get_one::<String>("lsformat")
+            .is_some_and(|format| format == "json");
+        let user_playlists = UserPlaylists::new().await;
+        let playlists = if list_arguments.get_flag("lsowned") {
+            user_playlists.get_owned_playlists().await
+        } else {
+            user_playlists.get_user_playlists().await
+        };
+        let summaries = playlists
+            .iter()
+            .map(PlaylistSummary::from)
+            .collect::<Vec<PlaylistSummary>>();
+        if json {
+            println!("{}", serde_json::to_string_pretty(&summaries)?);
+            return Ok(());
+        }
+        let name_width = summaries
+            .iter()
+            .map(|summary| summary.name.chars().count())
+            .max()
+            .unwrap_or_default()
+            .max("Playlist".len());
+        let owner_width = summaries
+            .iter()
+            .map(|summary| summary.owner.chars().count())
+            .max()
+            .unwrap_or_default()
+            .max("Owner".len());
+        println!(
+            "{:<name_width$}  {:<owner_width$}  {:>6}  {:<6}  {:<22}  Snapshot",
+            "Playlist", "Owner", "Tracks", "Public", "ID"
+        );
+        for summary in &summaries {
+            let public = match (summary.collaborative, summary.public) {
+                (true, _) => "collab",
+                (false, Some(true)) => "yes",
+                (false, Some(false)) => "no",
+                (false, None) => "-",
+            };
+            println!(
+                "{:<name_width$}  {:<owner_width$}  {:>6}  {:<6}  {:<22}  {}",
+                summary.name, summary.owner, summary.tracks, public, summary.id, summary.snapshot_id
+            );
+        }
+        println!("{} playlists", summaries.len());
+        Ok(())
+    }
+
+    /// Runs the create subcommand of the playlists command.
+    ///
+    /// This function creates a playlist and, when a seed file is given, fills it with the
+    /// tracks matched from the file the way the import subcommand matches them.
+    ///
+    /// # Arguments
+    /// * `create_arguments` - The argument matches for the create subcommand.
+    async fn run_create_subcommand(
+        &self,
+        create_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_create_subcommand");
+        let _enter = span.enter();
+
+        let name = create_arguments
+            .get_one::<String>("crname")
+            .expect("The name argument is required");
+        let description = create_arguments.get_one::<String>("crdescription");
+        let public = create_arguments.get_flag("crpublic");
+        let json = create_arguments
+            .get_one::<String>("crformat")
+            .is_some_and(|format| format == "json");
+        let seed = match create_arguments.get_one::<String>("crseed").map(PathBuf::from) {
+            Some(file) => {
+                let contents = std::fs::read_to_string(&file)?;
+                let entries = ImportEntry::parse(&contents, ImportFormat::from_path(&file));
+                let report = PlaylistImporter::new()
+                    .await
+                    .resolve(entries, None, pick_track_match)
+                    .await;
+                if !report.unmatched.is_empty() {
+                    println!(
+                        "{} entries of {} could not be matched",
+                        report.unmatched.len(),
+                        file.display()
+                    );
+                }
+                report.tracks()
+            }
+            None => Vec::new(),
+        };
+
+        let user_playlists = UserPlaylists::new().await;
+        let playlist = user_playlists
+            .create_playlist(name, description.map(String::as_str), public)
+            .await?;
+        let seed = if seed.is_empty() {
+            seed
+        } else {
+            user_playlists.add_tracks(&playlist.id, seed, &Duplicates::load()).await?
+        };
+        if json {
+            let created = serde_json::json!({
+                "id": playlist.id.id(),
+                "name": playlist.name,
+                "public": public,
+                "tracks": seed.len(),
+            });
+            println!("{}", serde_json::to_string_pretty(&created)?);
+        } else {
+            println!(
+                "Created {} ({}) with {} tracks",
+                playlist.name,
+                playlist.id.id(),
+                seed.len()
+            );
+        }
+        Ok(())
+    }
+
+    /// Runs the delete subcommand of the playlists command.
+    ///
+    /// This function removes a playlist from the user's library after confirmation. Spotify
+    /// deletes a playlist by having its owner unfollow it; playlists owned by someone else are
+    /// only unfollowed.
+    ///
+    /// # Arguments
+    /// * `delete_arguments` - The argument matches for the delete subcommand.
+    async fn run_delete_subcommand(
+        &self,
+        delete_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_delete_subcommand");
+        let _enter = span.enter();
+
+        let input = delete_arguments
+            .get_one::<String>("deplaylist")
+            .expect("The playlist argument is required");
+        let skip_confirmation = delete_arguments.get_flag("deyes");
+
+        let user_playlists = UserPlaylists::new().await;
+        let Some(playlist) = user_playlists.find_playlist(input).await else {
+            return Err(format!("No playlist in your library matches {input}").into());
+        };
+        let owned = user_playlists
+            .get_owned_playlists()
+            .await
+            .iter()
+            .any(|owned| owned.id == playlist.id);
+        let prompt = if owned {
+            format!("Delete {} ({} tracks)?", playlist.name, playlist.tracks.total)
+        } else {
+            format!(
+                "Unfollow {} by {}?",
+                playlist.name,
+                PlaylistSummary::from(&playlist).owner
+            )
+        };
+        let confirmed = skip_confirmation
+            || dialoguer::Confirm::with_theme(&dialoguer::theme::ColorfulTheme::default())
+                .with_prompt(prompt)
+                .default(false)
+                .interact()?;
+        if !confirmed {
+            println!("{} was kept", playlist.name);
+            return Ok(());
+        }
+        user_playlists.unfollow(&playlist.id).await?;
+        println!(
+            "{} {}",
+            if owned { "Deleted" } else { "Unfollowed" },
+            playlist.name
+        );
+        Ok(())
+    }
+
+    /// Runs the move subcommand of the playlists command.
+    ///
+    /// This function moves tracks, given as IDs or URIs on the command line or in a file, or all
+    /// tracks with `--all`, from one playlist to another. Tracks that are not in the source
+    /// playlist are reported and left alone.
+    ///
+    /// # Arguments
+    /// * `move_arguments` - The argument matches for the move subcommand.
+    async fn run_move_subcommand(&self, move_arguments: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_move_subcommand");
+        let _enter = span.enter();
+
+        let from = move_arguments
+            .get_one::<String>("mvfrom")
+            .expect("The source playlist argument is required");
+        let to = move_arguments
+            .get_one::<String>("mvto")
+            .expect("The target playlist argument is required");
+        let mut requested = move_arguments
+            .get_many::<String>("mvtracks")
+            .map(|tracks| tracks.cloned().collect::<Vec<String>>())
+            .unwrap_or_default();
+        if let Some(file) = move_arguments.get_one::<String>("mvfile") {
+            requested.extend(
+                std::fs::read_to_string(file)?
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_string),
+            );
+        }
+        let json = move_arguments
+            .get_one::<String>("mvformat")
+            .is_some_and(|format| format == "json");
+
+        let from_id = resolve_playlist_id(from).await?;
+        let to_id = resolve_playlist_id(to).await?;
+        let source_tracks = PlaylistXplr::new(from_id.clone(), false).await.tracks();
+        let source_ids = source_tracks
+            .iter()
+            .filter_map(|track| track.id.clone())
+            .collect::<Vec<TrackId<'static>>>();
+        let (track_ids, missing) = if move_arguments.get_flag("mvall") {
+            let mut seen = std::collections::HashSet::new();
+            let unique = source_ids
+                .into_iter()
+                .filter(|track_id| seen.insert(track_id.clone()))
+                .collect::<Vec<TrackId<'static>>>();
+            (unique, Vec::new())
+        } else {
+            let mut track_ids = Vec::new();
+            let mut missing = Vec::new();
+            for input in requested {
+                match TrackId::from_id_or_uri(&input) {
+                    Ok(track_id) if source_ids.contains(&track_id) => {
+                        let track_id = track_id.into_static();
+                        if !track_ids.contains(&track_id) {
+                            track_ids.push(track_id);
+                        }
+                    }
+                    _ => missing.push(input),
+                }
+            }
+            (track_ids, missing)
+        };
+        if !track_ids.is_empty() {
+            let tracks = track_ids
+                .iter()
+                .filter_map(|track_id| {
+                    source_tracks
+                        .iter()
+                        .find(|track| track.id.as_ref() == Some(track_id))
+                        .cloned()
+                })
+                .collect::<Vec<FullTrack>>();
+            UserPlaylists::new()
+                .await
+                .move_tracks(&from_id, &to_id, tracks, &Duplicates::load())
+                .await?;
+        }
+        if json {
+            let moved = serde_json::json!({
+                "from": from_id.id(),
+                "to": to_id.id(),
+                "moved": track_ids.iter().map(|track_id| track_id.id()).collect::<Vec<&str>>(),
+                "not_found": missing,
+            });
+            println!("{}", serde_json::to_string_pretty(&moved)?);
+        } else {
+            println!("Moved {} tracks from {} to {}", track_ids.len(), from, to);
+            for input in &missing {
+                println!("    Not in {}: {}", from, input);
+            }
+        }
+        Ok(())
+    }
+
+    /// Scans and processes the library command.
+    ///
+    /// This function synchronizes the cached liked songs, mirrors them into dated playlists,
+    /// prints statistics about them, backs them up, restores and prunes them, manages the saved
+    /// albums and reports album completion.
+    ///
+    /// # Arguments
+    /// * `matches` - The argument matches for the library command.
+    async fn run_library_command(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_library_command");
+        let _enter = span.enter();
+
+        match LibraryCmds::from_matches(matches) {
+            LibraryCmds::Sync(sync_arguments) => {
+                let full = sync_arguments.get_flag("lbfull");
+                let mut library = UserLibrary::from_cache().await;
+                let report = library.sync(full).await?;
+                println!(
+                    "{} liked songs ({} sync): {} added, {} removed",
+                    report.total,
+                    if report.full { "full" } else { "incremental" },
+                    report.added.len(),
+                    report.removed.len()
+                );
+                Self::print_library_events("+", &report.added);
+                Self::print_library_events("-", &report.removed);
+            }
+            LibraryCmds::Mirror(mirror_arguments) => {
+                let period = mirror_arguments
+                    .get_one::<String>("lbby")
+                    .expect("The by argument has a default value")
+                    .parse::<MirrorPeriod>()?;
+                let template = mirror_arguments
+                    .get_one::<String>("lbname")
+                    .map(String::as_str)
+                    .unwrap_or(DEFAULT_MIRROR_TEMPLATE);
+                let since = mirror_arguments.get_one::<String>("lbsince").map(String::as_str);
+                let dry_run = mirror_arguments.get_flag("lbdryrun");
+
+                let mut mirror = LibraryMirror::new(period, template).await;
+                mirror.set_force(mirror_arguments.get_flag("lbforce"));
+                let groups = mirror.plan(since).await;
+                if dry_run {
+                    for group in &groups {
+                        println!("{}: {} tracks", group.name(template), group.tracks.len());
+                    }
+                    println!("{} playlists would be written", groups.len());
+                    return Ok(());
+                }
+                for refresh in mirror.write(groups).await? {
+                    println!("{}", refresh.summary());
+                }
+            }
+            LibraryCmds::Stats(stats_arguments) => {
+                let top = *stats_arguments
+                    .get_one::<usize>("lbtop")
+                    .expect("The top argument has a default value");
+                let with_genres = !stats_arguments.get_flag("lbnogenres");
+                let json = stats_arguments
+                    .get_one::<String>("lbformat")
+                    .is_some_and(|format| format == "json");
+
+                let stats = LibraryAnalyzer::new().await.stats(top, with_genres).await;
+                if json {
+                    println!("{}", stats.to_json()?);
+                } else {
+                    stats.print_tables();
+                }
+            }
+            LibraryCmds::Albums(albums_arguments) => {
+                let mut saved_albums = SavedAlbums::new().await;
+                match albums_arguments.subcommand() {
+                    Some(("list", list_arguments)) => {
+                        if list_arguments
+                            .get_one::<String>("lbformat")
+                            .is_some_and(|format| format == "json")
+                        {
+                            println!("{}", serde_json::to_string_pretty(saved_albums.albums())?);
+                            return Ok(());
+                        }
+                        for saved_album in saved_albums.albums() {
+                            let artists = saved_album
+                                .album
+                                .artists
+                                .iter()
+                                .map(|artist| artist.name.as_str())
+                                .collect::<Vec<&str>>()
+                                .join(", ");
+                            println!(
+                                "{}  {} - {} ({} tracks)",
+                                saved_album.added_at.format("%Y-%m-%d"),
+                                artists,
+                                saved_album.album.name,
+                                saved_album.album.tracks.total
+                            );
+                        }
+                        println!("{} saved albums", saved_albums.albums().len());
+                    }
+                    Some((action @ ("save" | "remove"), change_arguments)) => {
+                        let album_ids = change_arguments
+                            .get_many::<String>("lbalbum")
+                            .unwrap_or_default()
+                            .map(|input| {
+                                AlbumId::from_id_or_uri(input.trim())
+                                    .map(AlbumId::into_static)
+                                    .map_err(|_| format!("Not an album ID or URI: {input}"))
+                            })
+                            .collect::<Result<Vec<AlbumId<'static>>, String>>()?;
+                        if action == "save" {
+                            saved_albums.save(&album_ids).await?;
+                            println!("Saved {} albums", album_ids.len());
+                        } else {
+                            saved_albums.remove(&album_ids).await?;
+                            println!("Removed {} albums", album_ids.len());
+                        }
+                    }
+                    Some(("sync", _)) => {
+                        let total = saved_albums.sync().await?;
+                        println!("{total} saved albums");
+                    }
+                    _ => event!(Level::DEBUG, "No albums subcommand given"),
+                }
+            }
+            LibraryCmds::Completion(completion_arguments) => {
+                let criteria = CompletionCriteria {
+                    min_tracks: *completion_arguments
+                        .get_one::<usize>("lbmintracks")
+                        .expect("The min-tracks argument has a default value"),
+                    min_fraction: *completion_arguments
+                        .get_one::<f64>("lbminfraction")
+                        .expect("The min-fraction argument has a default value"),
+                };
+                let json = completion_arguments
+                    .get_one::<String>("lbformat")
+                    .is_some_and(|format| format == "json");
+
+                let liked = UserLibrary::new().await.tracks();
+                let mut saved_albums = SavedAlbums::new().await;
+                let report = saved_albums.completion(&liked, criteria).await;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                } else {
+                    for completion in &report {
+                        println!(
+                            "{:>4.0}%  {:>3}/{:<3} {}{} - {}",
+                            completion.fraction() * 100.0,
+                            completion.liked,
+                            completion.total,
+                            if completion.saved { "[saved] " } else { "" },
+                            completion.artist,
+                            completion.name
+                        );
+                    }
+                    println!("{} albums reach the criteria", report.len());
+                }
+
+                if completion_arguments.get_flag("lbsave") {
+                    let unsaved = report
+                        .iter()
+                        .filter(|completion| !completion.saved)
+                        .filter_map(|completion| AlbumId::from_id(completion.album_id.clone()).ok())
+                        .collect::<Vec<AlbumId<'static>>>();
+                    let confirmed = unsaved.is_empty()
+                        || completion_arguments.get_flag("lbyes")
+                        || dialoguer::Confirm::with_theme(&dialoguer::theme::ColorfulTheme::default())
+                            .with_prompt(format!("Save {} albums to your library?", unsaved.len()))
+                            .default(false)
+                            .interact()?;
+                    if confirmed && !unsaved.is_empty() {
+                        saved_albums.save(&unsaved).await?;
+                        println!("Saved {} albums", unsaved.len());
+                    }
+                }
+                if let Some(playlist) = completion_arguments.get_one::<String>("lbqueue") {
+                    let tracks = saved_albums.missing_tracks(&report).await;
+                    let written = PlaylistWriter::new("Album completion queue by spotify-assistant")
+                        .await
+                        .append(playlist, tracks)
+                        .await?;
+                    println!("Queued {} tracks into {}", written.written, written.name);
+                }
+            }
+            LibraryCmds::Backup(backup_arguments) => {
+                let backup = LibraryBackup::from_saved(&UserLibrary::new().await.tracks());
+                let path = backup_arguments
+                    .get_one::<PathBuf>("lboutput")
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        ProjectDirectories::Data
+                            .path()
+                            .join(format!("liked_songs_backup_{}.json", backup.created_at.format("%Y-%m-%d")))
+                    });
+                backup.write(&path)?;
+                println!("Backed up {} liked songs to {}", backup.tracks.len(), path.display());
+            }
+            LibraryCmds::Restore(restore_arguments) => {
+                let path = restore_arguments
+                    .get_one::<PathBuf>("lbfile")
+                    .expect("The file argument is required");
+                let profile = restore_arguments.get_one::<String>("lbprofile").map(String::as_str);
+                let backup = LibraryBackup::read(path)?;
+                let progress = RestoreProgress::load(path);
+                if progress.done > 0 {
+                    println!("Resuming after {} of {} songs", progress.done, backup.tracks.len());
+                }
+                let confirmed = restore_arguments.get_flag("lbyes")
+                    || dialoguer::Confirm::with_theme(&dialoguer::theme::ColorfulTheme::default())
+                        .with_prompt(format!(
+                            "Like {} songs from a backup of {} on {}?",
+                            backup.tracks.len(),
+                            backup.created_at.format("%Y-%m-%d"),
+                            profile.map_or("your account".to_string(), |profile| format!("the {profile} profile"))
+                        ))
+                        .default(false)
+                        .interact()?;
+                if !confirmed {
+                    return Ok(());
+                }
+
+                let report = LibraryRestore::new(profile).await.restore(&backup, path).await?;
+                println!(
+                    "{} songs liked, {} were liked already",
+                    report.restored, report.already_liked
+                );
+                for (entry, track) in &report.replaced {
+                    let artists = track
+                        .artists
+                        .iter()
+                        .map(|artist| artist.name.as_str())
+                        .collect::<Vec<&str>>()
+                        .join(", ");
+                    println!("    ~ {} -> {} - {}", entry.describe(), artists, track.name);
+                }
+                if !report.unresolved.is_empty() {
+                    println!("{} songs could not be found:", report.unresolved.len());
+                    for entry in &report.unresolved {
+                        println!("    ! {}", entry.describe());
+                    }
+                }
+            }
+            LibraryCmds::Prune(prune_arguments) => {
+                let rules = prune_arguments
+                    .get_many::<String>("lbrule")
+                    .unwrap_or_default()
+                    .map(|rule| rule.parse::<PruneRule>())
+                    .collect::<Result<Vec<PruneRule>, String>>()?;
+                let pruner = LibraryPruner::new().await;
+                let candidates = pruner.candidates(&rules).await?;
+                for candidate in &candidates {
+                    println!("    - {}", candidate.describe());
+                }
+                println!("{} liked songs selected", candidates.len());
+                if candidates.is_empty() || prune_arguments.get_flag("lbdryrun") {
+                    return Ok(());
+                }
+                let confirmed = prune_arguments.get_flag("lbyes")
+                    || dialoguer::Confirm::with_theme(&dialoguer::theme::ColorfulTheme::default())
+                        .with_prompt(format!("Unlike {} songs?", candidates.len()))
+                        .default(false)
+                        .interact()?;
+                if !confirmed {
+                    return Ok(());
+                }
+                let backup_path = prune_arguments
+                    .get_one::<PathBuf>("lbbackup")
+                    .cloned()
+                    .unwrap_or_else(LibraryPruner::default_backup_path);
+                let removed = pruner.prune(&candidates, &backup_path).await?;
+                println!("Unliked {} songs, backed up to {}", removed, backup_path.display());
+                println!("Undo with: library restore {}", backup_path.display());
+            }
+            LibraryCmds::Empty => {
+                event!(Level::DEBUG, "No library subcommand given");
+            }
+        }
+        Ok(())
+    }
+
+    /// Prints up to twenty library events, one per line, prefixed with `marker`.
+    fn print_library_events(marker: &str, events: &[LibraryEvent]) {
+        const SHOWN: usize = 20;
+        for library_event in events.iter().take(SHOWN) {
+            println!(
+                "    {} {} - {}",
+                marker,
+                library_event.artists.join(", "),
+                library_event.name
+            );
+        }
+        if events.len() > SHOWN {
+            println!("    ... and {} more", events.len() - SHOWN);
+        }
+    }
+
+    /// Scans and processes the artists command.
+    ///
+    /// This function records the followed artists in the followed artists history, prints
+    /// how they changed over time, follows or unfollows artists in bulk, suggests artists to
+    /// follow, reports the gaps in their discographies, and exports the graph of their
+    /// collaborations.
+    ///
+    /// # Arguments
+    /// * `matches` - The argument matches for the artists command.
+    async fn run_artists_command(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_artists_command");
+        let _enter = span.enter();
+
+        match ArtistCmds::from_matches(matches) {
+            ArtistCmds::Sync(_) => {
+                let mut history = FollowedArtistsHistory::open()?;
+                let (synced_at, artists) = history.sync().await?;
+                println!(
+                    "Recorded {} followed artists at {}",
+                    artists.len(),
+                    synced_at.format("%Y-%m-%d %H:%M")
+                );
+                let syncs = history.syncs()?;
+                if let [.., previous, _] = syncs.as_slice() {
+                    let before = history.snapshot(*previous)?;
+                    let changes = ArtistSnapshot::changes(&before, &artists);
+                    let follows = changes
+                        .iter()
+                        .filter(|change| {
+                            matches!(change.kind, ArtistChangeKind::Followed | ArtistChangeKind::Unfollowed)
+                        })
+                        .collect::<Vec<&ArtistChange>>();
+                    println!(
+                        "Since {}: {} follows and unfollows, {} other changes",
+                        previous.format("%Y-%m-%d %H:%M"),
+                        follows.len(),
+                        changes.len() - follows.len()
+                    );
+                    for change in follows {
+                        println!("    {} {}", change.kind, change.name);
+                    }
+                }
+            }
+            ArtistCmds::History(history_arguments) => {
+                let query = history_arguments
+                    .get_one::<String>("arartist")
+                    .expect("The artist argument is required");
+                let records = FollowedArtistsHistory::open()?.artist_history(query)?;
+                if records.is_empty() {
+                    println!("No recorded artist matches {query}");
+                }
+                let mut current = None;
+                for (synced_at, artist) in &records {
+                    if current != Some(&artist.artist_id) {
+                        println!("{} ({})", artist.name, artist.artist_id);
+                        current = Some(&artist.artist_id);
+                    }
+                    println!(
+                        "    {}  popularity {:>3}  followers {:>10}  {}",
+                        synced_at.format("%Y-%m-%d %H:%M"),
+                        artist.popularity,
+                        artist.followers,
+                        artist.genres.join(", ")
+                    );
+                }
+            }
+            ArtistCmds::Changes(changes_arguments) => {
+                let since = changes_arguments
+                    .get_one::<String>("arsince")
+                    .expect("The since argument is required");
+                let since = PlaylistHistory::parse_moment(since)?;
+                let json = changes_arguments
+                    .get_one::<String>("arformat")
+                    .is_some_and(|format| format == "json");
+                let Some(changes) = FollowedArtistsHistory::open()?.changes_since(since)? else {
+                    println!("Fewer than two syncs are recorded since then; run artists sync to record one");
+                    return Ok(());
+                };
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&changes.changes)?);
+                    return Ok(());
+                }
+                println!(
+                    "Changes from {} to {}",
+                    changes.from.format("%Y-%m-%d %H:%M"),
+                    changes.to.format("%Y-%m-%d %H:%M")
+                );
+                for change in &changes.changes {
+                    println!("    {}: {}", change.name, change.kind);
+                }
+                if !changes.genre_shift.is_empty() {
+                    println!("Genre shift:");
+                    for (genre, difference) in &changes.genre_shift {
+                        println!("    {difference:+} {genre}");
+                    }
+                }
+            }
+            ArtistCmds::Follow(follow_arguments) => {
+                self.run_artist_follow_subcommand(&follow_arguments, true).await?;
+            }
+            ArtistCmds::Unfollow(unfollow_arguments) => {
+                self.run_artist_follow_subcommand(&unfollow_arguments, false).await?;
+            }
+            ArtistCmds::Suggest(suggest_arguments) => {
+                let limit = *suggest_arguments
+                    .get_one::<usize>("arlimit")
+                    .expect("The limit argument has a default value");
+                let suggester = FollowSuggester {
+                    liked_songs: !suggest_arguments.get_flag("arnoliked"),
+                    playlists: !suggest_arguments.get_flag("arnoplaylists"),
+                    plays: !suggest_arguments.get_flag("arnohistory"),
+                };
+                let suggestions = suggester.suggest(limit).await?;
+                if suggest_arguments
+                    .get_one::<String>("arformat")
+                    .is_some_and(|format| format == "json")
+                {
+                    println!("{}", serde_json::to_string_pretty(&suggestions)?);
+                    return Ok(());
+                }
+                if suggestions.is_empty() {
+                    println!("No artists to suggest");
+                    return Ok(());
+                }
+                for (index, suggestion) in suggestions.iter().enumerate() {
+                    println!("{:>4}. {}", index + 1, suggestion.describe());
+                }
+                let follower = ArtistFollower::new().await;
+                let candidates = FollowSuggester::artists(&follower, &suggestions).await;
+                let selected = pick_multiple_artists(&candidates)
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if selected.is_empty() {
+                    println!("No artists selected");
+                    return Ok(());
+                }
+                let summary = follower.follow(&selected).await;
+                for artist in &summary.failed {
+                    println!("    ! {}", artist.name());
+                }
+                println!("Followed {} artists, {} failed", summary.changed.len(), summary.failed.len());
+                if !summary.changed.is_empty() {
+                    let (synced_at, _) = FollowedArtistsHistory::open()?.sync().await?;
+                    println!("Recorded the followed artists at {}", synced_at.format("%Y-%m-%d %H:%M"));
+                }
+            }
+            ArtistCmds::Gaps(gaps_arguments) => {
+                let artist_ids = match gaps_arguments.get_one::<String>("arartist") {
+                    Some(artist) => vec![Self::resolve_artist_id(artist).await?],
+                    None => UserData::new()
+                        .await
+                        .artists()
+                        .await
+                        .into_iter()
+                        .map(|artist| artist.id)
+                        .collect(),
+                };
+                let checker = DiscographyChecker::new(!gaps_arguments.get_flag("arnoplaylists")).await;
+                let mut report = Vec::with_capacity(artist_ids.len());
+                for artist_id in artist_ids {
+                    match checker.gaps(artist_id.clone()).await {
+                        Ok(gaps) => report.push(gaps),
+                        Err(err) => event!(Level::ERROR, "Could not check {}: {:?}", artist_id.id(), err),
+                    }
+                }
+                if gaps_arguments
+                    .get_one::<String>("arformat")
+                    .is_some_and(|format| format == "json")
+                {
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                } else {
+                    for gaps in &report {
+                        println!(
+                            "{}: {} full, {} partial, {} missing",
+                            gaps.artist,
+                            gaps.count(ReleaseCoverage::Full),
+                            gaps.count(ReleaseCoverage::Partial),
+                            gaps.count(ReleaseCoverage::Missing)
+                        );
+                        for release in &gaps.releases {
+                            println!(
+                                "    {:<8} {:>3}/{:<3} {} {} ({})",
+                                release.coverage,
+                                release.in_library,
+                                release.total,
+                                release.release_date,
+                                release.name,
+                                release.album_type
+                            );
+                        }
+                    }
+                }
+                if let Some(playlist) = gaps_arguments.get_one::<String>("arqueue") {
+                    let tracks = checker.missing_tracks(&report).await;
+                    let written = PlaylistWriter::new("Discography catch-up by spotify-assistant")
+                        .await
+                        .append(playlist, tracks)
+                        .await?;
+                    println!("Queued {} tracks into {}", written.written, written.name);
+                }
+            }
+            ArtistCmds::Graph(graph_arguments) => {
+                let depth = *graph_arguments.get_one::<u32>("ardepth").unwrap_or(&1) as usize;
+                let budget = *graph_arguments.get_one::<u32>("arbudget").unwrap_or(&200) as usize;
+                let crawler = CollaborationCrawler::new(depth, budget).await;
+                let seeds = match graph_arguments.get_one::<String>("arartist") {
+                    Some(artist) => vec![(Self::resolve_artist_id(artist).await?, String::new())],
+                    None => crawler.followed_seeds().await?,
+                };
+                let output = graph_arguments.get_one::<PathBuf>("aroutput").cloned();
+                let format = match graph_arguments.get_one::<String>("arformat") {
+                    Some(format) => format.parse::<GraphFormat>()?,
+                    None => output.as_deref().map(GraphFormat::from_path).unwrap_or(GraphFormat::GraphMl),
+                };
+                let path = output.unwrap_or_else(|| {
+                    ProjectDirectories::Data.path().join(format!(
+                        "collaborations_{}.{}",
+                        chrono::Local::now().format("%Y-%m-%d"),
+                        format.extension()
+                    ))
+                });
+
+                let graph = crawler.crawl(seeds).await;
+                std::fs::write(&path, graph.export(format))?;
+                println!(
+                    "Wrote {} artists and {} collaborations to {} ({} requests)",
+                    graph.nodes().len(),
+                    graph.edges().len(),
+                    path.display(),
+                    graph.requests
+                );
+                if graph.truncated {
+                    println!("The request budget of {budget} ran out; raise --budget to crawl further");
+                }
+            }
+            ArtistCmds::Empty => {
+                event!(Level::DEBUG, "No artists subcommand given");
+            }
+        }
+        Ok(())
+    }
+
+    /// Follows or unfollows the artists collected from the given sources.
+    ///
+    /// The artists that would change are offered for selection unless `--all` is given. The
+    /// changes are recorded as a followed artists sync afterwards.
+    ///
+    /// # Arguments
+    /// * `arguments` - The argument matches for the follow or unfollow subcommand.
+    /// * `follow` - Whether to follow or unfollow the artists.
+    async fn run_artist_follow_subcommand(
+        &self,
+        arguments: &ArgMatches,
+        follow: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let follower = ArtistFollower::new().await;
+        let mut artists = Vec::new();
+        for playlist in arguments.get_many::<String>("arplaylist").unwrap_or_default() {
+            let playlist_id = resolve_playlist_id(playlist).await?;
+            artists.extend(follower.from_playlist(playlist_id).await);
+        }
+        if arguments.get_flag("arliked") {
+            artists.extend(follower.from_liked_songs().await);
+        }
+        if let Some(path) = arguments.get_one::<PathBuf>("arjson") {
+            artists.extend(ArtistFollower::from_json(path)?);
+        }
+        if arguments.get_flag("arblacklist") {
+            artists.extend(follower.from_blacklist().await?);
+        }
+        if let Some(path) = arguments.get_one::<PathBuf>("arnames") {
+            let (found, unresolved) = follower.from_names(&std::fs::read_to_string(path)?).await;
+            for name in &unresolved {
+                println!("    ! No artist found for {name}");
+            }
+            artists.extend(found);
+        }
+        let mut seen = std::collections::HashSet::new();
+        artists.retain(|artist| seen.insert(artist.id().to_string()));
+
+        let (followed, not_followed) = follower.partition_followed(artists).await?;
+        let (candidates, unchanged) = if follow {
+            (not_followed, followed)
+        } else {
+            (followed, not_followed)
+        };
+        println!(
+            "{} artists to {}, {} already {}",
+            candidates.len(),
+            if follow { "follow" } else { "unfollow" },
+            unchanged.len(),
+            if follow { "followed" } else { "not followed" }
+        );
+        if candidates.is_empty() {
+            return Ok(());
+        }
+        let selected = if arguments.get_flag("arall") {
+            candidates
+        } else {
+            pick_multiple_artists(&candidates).into_iter().cloned().collect()
+        };
+        if selected.is_empty() {
+            println!("No artists selected");
+            return Ok(());
+        }
+
+        let summary = if follow {
+            follower.follow(&selected).await
+        } else {
+            follower.unfollow(&selected).await
+        };
+        for artist in &summary.changed {
+            println!("    {} {}", if follow { "+" } else { "-" }, artist.name());
+        }
+        for artist in &summary.failed {
+            println!("    ! {}", artist.name());
+        }
+        println!(
+            "{} {} artists, {} failed",
+            if follow { "Followed" } else { "Unfollowed" },
+            summary.changed.len(),
+            summary.failed.len()
+        );
+        if !summary.changed.is_empty() {
+            let (synced_at, _) = FollowedArtistsHistory::open()?.sync().await?;
+            println!("Recorded the followed artists at {}", synced_at.format("%Y-%m-%d %H:%M"));
+        }
+        Ok(())
+    }
+
+    /// Resolves an artist ID, URI or name. Names are searched for, preferring an exact match.
+    async fn resolve_artist_id(input: &str) -> Result<ArtistId<'static>, Box<dyn Error>> {
+        if let Ok(artist_id) = ArtistId::from_id_or_uri(input.trim()) {
+            return Ok(artist_id.into_static());
+        }
+        let (artists, _) = ArtistFollower::new().await.from_names(input).await;
+        let artist = artists
+            .first()
+            .ok_or_else(|| format!("No artist found for {input}"))?;
+        println!("Using {} ({})", artist.name(), artist.id());
+        Ok(ArtistId::from_id(artist.id().to_string())?)
+    }
+
+    /// Scans and processes the smart playlists command.
+    ///
+    /// This function saves, lists and removes smart playlist definitions, and regenerates the
+    /// Spotify playlists they describe.
+    ///
+    /// # Arguments
+    /// * `matches` - The argument matches for the smart command.
+    async fn run_smart_command(&self, matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_smart_command");
+        let _enter = span.enter();
+
+        let mut definitions = SmartPlaylists::load()?;
+        match SmartCmds::from_matches(matches) {
+            SmartCmds::Create(create_arguments) => {
+                let name = create_arguments
+                    .get_one::<String>("smname")
+                    .expect("The name argument is required");
+                let source = create_arguments
+                    .get_one::<String>("smsource")
+                    .expect("The source argument has a default value");
+                let rules = create_arguments
+                    .get_many::<String>("smrule")
+                    .unwrap_or_default()
+                    .map(|rule| rule.parse::<SmartRule>())
+                    .collect::<Result<Vec<SmartRule>, String>>()?;
+                let mut definition = SmartPlaylist::new(name.to_string(), source.to_string(), rules);
+                definition.order = create_arguments
+                    .get_one::<String>("smorder")
+                    .expect("The order argument has a default value")
+                    .parse::<CombineOrder>()?;
+                definition.limit = create_arguments.get_one::<usize>("smlimit").copied();
+
+                definitions.upsert(definition.clone());
+                definitions.save()?;
+                println!("Saved {}", definition.describe());
+                let mut refresher = SmartRefresher::new().await;
+                refresher.set_force(create_arguments.get_flag("smforce"));
+                let refresh = refresher.refresh(&definition).await?;
+                println!("{}", refresh.summary());
+            }
+            SmartCmds::List => {
+                if definitions.playlists().is_empty() {
+                    println!("No smart playlists defined");
+                }
+                for definition in definitions.playlists() {
+                    let limit = definition
+                        .limit
+                        .map(|limit| format!(", at most {limit} tracks"))
+                        .unwrap_or_default();
+                    println!("{} (by {}{})", definition.name, definition.order, limit);
+                    println!("    {}", definition.describe());
+                }
+            }
+            SmartCmds::Remove(remove_arguments) => {
+                let name = remove_arguments
+                    .get_one::<String>("smname")
+                    .expect("The name argument is required");
+                if definitions.remove(name) {
+                    definitions.save()?;
+                    println!("Removed the smart playlist definition {name}; the Spotify playlist was kept");
+                } else {
+                    return Err(format!("No smart playlist named {name}").into());
+                }
+            }
+            SmartCmds::Refresh(refresh_arguments) => {
+                let selected = match refresh_arguments.get_one::<String>("smname") {
+                    Some(name) => vec![
+                        definitions
+                            .get(name)
+                            .cloned()
+                            .ok_or_else(|| format!("No smart playlist named {name}"))?,
+                    ],
+                    None => definitions.playlists().to_vec(),
+                };
+                let mut refresher = SmartRefresher::new().await;
+                refresher.set_force(refresh_arguments.get_flag("smforce"));
+                let refreshed = refresher.refresh_all(&selected).await;
+                for refresh in &refreshed {
+                    println!("{}", refresh.summary());
+                }
+                println!("Refreshed {} of {} smart playlists", refreshed.len(), selected.len());
+            }
+            SmartCmds::Empty => {
+                event!(Level::DEBUG, "No smart subcommand given");
+            }
+        }
+        Ok(())
+    }
+
+    /// Scans and processes the config subcommand.
+    ///
+    /// This function handles the arguments for the config subcommand, performs required actions,
+    /// and executes blacklist-related functions or shell script generation when required.
+    ///
+    /// # Arguments
+    /// * `config_arguments` - The argument matches for the config subcommand.
+    async fn run_config_command(
+        &self,
+        config_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_config_command");
+        let _enter = span.enter();
+
+        match ConfigArgs::from_matches(config_arguments) {
+            ConfigArgs::Set(key, value) => {
+                println!("Setting config value: {} = {}", key, value);
+                Ok(())
+            }
+            ConfigArgs::Unset(key) => {
+                println!("Unsetting config value: {}", key);
+                Ok(())
+            }
+            ConfigArgs::Get(key) => {
+                println!("Getting config value: {}", key);
+                Ok(())
+            }
+            ConfigArgs::Shell(shell) => {
+                let mut cmd = self.command.clone();
+                generate_auto_complete!(shell, cmd);
+                Ok(())
+            }
+            ConfigArgs::Blacklist(args) => match self.run_blacklist_subcommand(&args).await {
+                Ok(_) => Ok(()),
+                Err(err) => Err(err),
+            },
+            ConfigArgs::Unfollowed(playlist_name) => {
+                let filename = format!(
+                    "playlist-artists/{playlist_name}_artists_not_followed-10-20-2025.json"
+                );
+                event!(Level::DEBUG, "Filename: {:?}", filename);
+                let file_path = ProjectDirectories::Data.path().join(filename);
+                let selected_artists = cmd_find_artists(file_path)?;
+                let scope = scopes!("user-follow-modify");
+                let client = PlaylistQuery::set_up_client(false, Some(scope)).await;
+                let artist_ids: Vec<ArtistId> = selected_artists.into_iter().filter_map(|artist| match ArtistId::from_id(artist.id().to_string()) {
+                    Ok(id) => Some(id),
+                    Err(_) => {
+                        let id_str = artist.id();
+                        event!(Level::ERROR, %id_str, "Could not instantiate an ArtistID object for {}", artist.name());
+                        None
+                    }
+                }).collect();
+                event!(
+                    Level::DEBUG,
+                    "About to add {} Artist IDs: {:?}",
+                    artist_ids.len(),
+                    artist_ids
+                );
+                client
+                    .user_follow_artists(artist_ids)
+                    .await
+                    .expect("Couldn't add all artists");
+                let user_data = UserData::new().await;
+                let artists = user_data.update_followed_artists().await;
+                let snapshots = artists.iter().map(ArtistSnapshot::from).collect::<Vec<ArtistSnapshot>>();
+                FollowedArtistsHistory::open()?
+                    .record(chrono::Utc::now(), &snapshots)
+                    .await?;
+
+                Ok(())
+            }
+            ConfigArgs::Empty => {
+                println!("No config subcommand");
+                Ok(())
+            }
+        }
+    }
+
+    /// Scans and processes the blacklist subcommand.
+    ///
+    /// This function performs operations on the blacklist, such as adding or removing artists. It supports
+    /// operations like adding artists directly, selecting from a playlist, and removing artists by name or selection.
+    ///
+    /// # Arguments
+    /// * `blacklist_arguments` - The argument matches for the blacklist subcommand.
+    async fn run_blacklist_subcommand(
+        &self,
+        blacklist_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_config_command");
+        let _enter = span.enter();
+
+        let mut blacklist = Blacklist::default();
+        match BlacklistArgs::from_matches(blacklist_arguments) {
+            BlacklistArgs::Add(artist_id) => {
+                let full_artist = FullProfiles::new().await.artist(artist_id).await;
+                let artist_name = full_artist.name;
+                let artist_id =
+                    full_artist.id.to_string().split(':').collect::<Vec<&str>>()[2].to_string();
+                let artist = BlacklistArtist::new(artist_name, artist_id);
+                println!(
+                    "Adding {}'s ID to blacklist: {}",
+                    artist.name(),
+                    artist.id()
+                );
+                blacklist.add_artist(artist);
+                Ok(())
+            }
+            BlacklistArgs::AddFromPlaylist(playlist) => {
+                let playlist_id = resolve_playlist_id(&playlist).await?;
+                let playlist = PlaylistXplr::new(playlist_id, false).await;
+                let artists = playlist.artists_by_album().await;
+                let selected = match blacklist.select_artist_to_add_by_album(artists) {
+                    Some(blacklisted_artist) => blacklisted_artist,
+                    None => {
+                        println!("No artist selected to add to the blacklist.");
+                        return Ok(());
+                    }
+                };
+                println!("Selected: {:?}", selected);
+                blacklist.add_artist(selected);
+                Ok(())
+            }
+            BlacklistArgs::RemoveByName(name) => {
+                match blacklist
+                    .artists()
+                    .iter()
+                    .find(|artist| blacklist.are_names_equal(name.as_str(), artist.name()))
+                {
+                    Some(artist) => {
+                        let artist_name = artist.name();
+                        let artist_id = artist.id();
+                        blacklist.remove_artist(artist_name.as_str(), artist_id.as_str());
+                    }
+                    None => {
+                        println!("{} not found in the blacklist.", name);
+                        return Ok(());
+                    }
+                }
+                println!("{}'s ID was removed from the blacklist", name);
+                Ok(())
+            }
+            BlacklistArgs::RemoveBySelect => {
+                blacklist.select_artist_to_remove();
+                Ok(())
+            }
+            BlacklistArgs::Empty => {
+                blacklist.print_blacklist();
+                Ok(())
+            }
+        }
+    }
+
+    /// Runs the Release Radar subcommand.
+    ///
+    /// This function handles the arguments for the Release Radar subcommand and executes the corresponding logic to
+    /// update or compare playlists.
+    ///
+    /// # Arguments
+    /// * `release_radar_arguments` - The argument matches for the Release Radar subcommand.
+    async fn run_rr_command(
+        &self,
+        release_radar_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        match ReleaseRadarCmds::from_matches(release_radar_arguments) {
+            ReleaseRadarCmds::Update(update_value) => {
+                match ReleaseRadarArgs::from_update_matches(&update_value) {
+                    ReleaseRadarArgs::UPrint(print) => {
+                        println!("Printing Release Radar Progress: {:?}", print);
+                        Ok(())
+                    }
+                    ReleaseRadarArgs::Empty => {
+                        info!(
+                            "Backing up the current Release Radar playlist into the Lagging Release Radar playlist"
+                        );
+                        Modifier::lagging_release_radar()
+                            .await
+                            .update_playlist()
+                            .await;
+                        info!("Updating Release Radar playlist");
+                        Modifier::release_radar().await.update_playlist().await;
+                        Ok(())
+                    }
+                    _ => Ok(()),
+                }
+            }
+            ReleaseRadarCmds::Compare(compare_value) => {
+                let json = compare_value
+                    .get_one::<String>("compareformat")
+                    .is_some_and(|format| format == "json");
+                let release_radar = playlist_export(PlaylistType::MyRR.get_id()).await;
+                let other = match ReleaseRadarArgs::from_compare_matches(&compare_value) {
+                    ReleaseRadarArgs::CPlaylists(playlist) => {
+                        info!("Comparing the Release Radar playlist to {:?}", playlist);
+                        load_playlist_export(&playlist).await?
+                    }
+                    _ => {
+                        info!("Comparing the Release Radar playlist to the lagging Release Radar");
+                        playlist_export(PlaylistType::MyLaggingRR.get_id()).await
+                    }
+                };
+                print_playlist_diff(&PlaylistDiff::new(&release_radar, &other), json)
+            }
+            _ => {
+                println!("No Release Radar subcommand");
+                Ok(())
+            }
+        }
+    }
+
+    /// Runs the query subcommand.
+    ///
+    /// This function handles the arguments for the query subcommand, allowing users to interact with playlists
+    /// and the blacklist to retrieve data.
+    ///
+    /// # Arguments
+    /// * `query_arguments` - The argument matches for the query subcommand.
+    async fn run_query_command(&self, query_arguments: &ArgMatches) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::TRACE, "TerminalApp.run_query_command");
+        let _enter = span.enter();
+
+        event!(
+            Level::DEBUG,
+            "Stock RR: {:?} | Custom RR: {:?} | Blacklist: {:?} | Library: {:?}",
+            query_arguments
+                .get_one::<bool>("qstock")
+                .unwrap_or(&false)
+                .to_string(),
+            query_arguments
+                .get_one::<bool>("qcustom")
+                .unwrap_or(&false)
+                .to_string(),
+            query_arguments
+                .get_one::<bool>("qblacklist")
                 .unwrap_or(&false)
                 .to_string(),
             query_arguments
@@ -585,6 +2314,14 @@ impl TerminalApp {
                 println!("Library: {:?}", liked.total_tracks());
                 Ok(())
             }
+            QueryArgs::Duplicates(duplicates_arguments) => {
+                event!(
+                    Level::TRACE,
+                    "Querying duplicates across the library: {:?}",
+                    duplicates_arguments
+                );
+                self.run_duplicates_subcommand(&duplicates_arguments).await
+            }
             QueryArgs::Empty => {
                 event!(Level::TRACE, "No Release Radar query argument");
                 Ok(())
@@ -592,6 +2329,34 @@ impl TerminalApp {
         }
     }
 
+    /// Runs the duplicates subcommand of the query command.
+    ///
+    /// This function scans every owned playlist and the liked songs, prints a summary of each
+    /// duplicate cluster, and optionally exports the full report as JSON or CSV.
+    ///
+    /// # Arguments
+    /// * `duplicates_arguments` - The argument matches for the duplicates subcommand.
+    async fn run_duplicates_subcommand(
+        &self,
+        duplicates_arguments: &ArgMatches,
+    ) -> Result<(), Box<dyn Error>> {
+        let span = span!(Level::INFO, "TerminalApp.run_duplicates_subcommand");
+        let _enter = span.enter();
+
+        let report = DuplicateReport::library().await;
+        report.print_summary();
+        if let Some(format) = duplicates_arguments.get_one::<String>("dformat") {
+            let format = format.parse::<ReportFormat>()?;
+            let path = match duplicates_arguments.get_one::<String>("doutput") {
+                Some(path) => std::path::PathBuf::from(path),
+                None => DuplicateReport::default_export_path(format),
+            };
+            report.export(&path, format)?;
+            println!("Duplicate report exported to {}", path.display());
+        }
+        Ok(())
+    }
+
     /// Defines the config subcommand.
     ///
     /// This function creates and returns the configuration subcommand with its arguments.
@@ -695,32 +2460,512 @@ impl TerminalApp {
         Command::new("playlists")
             .short_flag('p')
             .about("Manage playlists")
-            .arg(
-                Arg::new("plist")
-                    .short('l')
-                    .long("list")
-                    .value_parser(BoolValueParser::new().map(|b| b))
-                    .action(ArgAction::SetTrue)
-                    .help("List all playlists"),
+            .subcommand(
+                Command::new("dedupe")
+                    .about("Remove later occurrences of duplicated tracks from a playlist, keeping its order")
+                    .arg(
+                        Arg::new("ddplaylist")
+                            .value_name("PLAYLIST-NAME | PLAYLIST-ID")
+                            .required(true)
+                            .help("The playlist to deduplicate"),
+                    )
+                    .arg(
+                        Arg::new("ddmode")
+                            .short('m')
+                            .long("mode")
+                            .value_name("MODE")
+                            .value_parser(["exact", "fuzzy"])
+                            .default_value("exact")
+                            .help("Match duplicates by exact track ID or by recording fingerprint"),
+                    )
+                    .arg(
+                        Arg::new("dddryrun")
+                            .short('n')
+                            .long("dry-run")
+                            .action(ArgAction::SetTrue)
+                            .help("Only print the duplicates that would be removed"),
+                    ),
             )
-            .arg(
-                Arg::new("pcreate")
-                    .short('c')
-                    .long("create")
-                    .help("Create a new playlists"),
+            .subcommand(
+                Command::new("export")
+                    .about("Export a playlist to JSON, CSV, M3U8 or XSPF")
+                    .arg(
+                        Arg::new("explaylist")
+                            .value_name("PLAYLIST-NAME | PLAYLIST-ID")
+                            .required_unless_present("exall")
+                            .help("The playlist to export"),
+                    )
+                    .arg(
+                        Arg::new("exformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["json", "csv", "m3u8", "xspf"])
+                            .default_value("json")
+                            .help("The file format to export to"),
+                    )
+                    .arg(
+                        Arg::new("exoutput")
+                            .short('o')
+                            .long("output")
+                            .value_name("PATH")
+                            .help("The file to write, or the directory when used with --all. Defaults to the data directory"),
+                    )
+                    .arg(
+                        Arg::new("exall")
+                            .short('a')
+                            .long("all")
+                            .action(ArgAction::SetTrue)
+                            .conflicts_with("explaylist")
+                            .help("Export every playlist you own into a directory"),
+                    ),
+            )
+            .subcommand(
+                Command::new("import")
+                    .about("Import a CSV, M3U or \"Artist - Title\" text file into a playlist")
+                    .arg(
+                        Arg::new("imfile")
+                            .value_name("FILE")
+                            .required(true)
+                            .help("The file to import"),
+                    )
+                    .arg(
+                        Arg::new("iminto")
+                            .long("into")
+                            .value_name("PLAYLIST-NAME")
+                            .required(true)
+                            .help("The playlist to add the tracks to, created when it does not exist"),
+                    )
+                    .arg(
+                        Arg::new("imformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["csv", "m3u", "text"])
+                            .help("The file format. Guessed from the file extension by default"),
+                    )
+                    .arg(
+                        Arg::new("immincon")
+                            .short('c')
+                            .long("min-confidence")
+                            .value_name("0.0-1.0")
+                            .value_parser(value_parser!(f64))
+                            .help("Accept the best match at or above this confidence instead of asking, and skip the rest"),
+                    )
+                    .arg(
+                        Arg::new("imunmatched")
+                            .short('u')
+                            .long("unmatched")
+                            .value_name("PATH")
+                            .help("Where to write the unmatched entries. Defaults to the data directory"),
+                    ),
+            )
+            .subcommand(
+                Command::new("diff")
+                    .about("Compare two playlists, or two snapshots of a playlist, track by track")
+                    .arg(
+                        Arg::new("dfleft")
+                            .value_name("PLAYLIST | SNAPSHOT-FILE")
+                            .required(true)
+                            .help("The first playlist, or a JSON export of it"),
+                    )
+                    .arg(
+                        Arg::new("dfright")
+                            .value_name("PLAYLIST | SNAPSHOT-FILE")
+                            .required(true)
+                            .help("The second playlist, or a JSON export of it"),
+                    )
+                    .arg(
+                        Arg::new("dfformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["table", "json"])
+                            .default_value("table")
+                            .help("Print the diff as a side-by-side table or as JSON"),
+                    ),
+            )
+            .subcommand(
+                Command::new("combine")
+                    .about("Write the union, intersection or difference of playlists into a playlist")
+                    .arg(
+                        Arg::new("cbsources")
+                            .value_name("PLAYLIST | @liked")
+                            .num_args(2..)
+                            .required(true)
+                            .help("The source playlists, in order. Use @liked for your liked songs"),
+                    )
+                    .arg(
+                        Arg::new("cboperation")
+                            .long("op")
+                            .value_name("OPERATION")
+                            .value_parser(["union", "intersection", "difference", "symmetric-difference"])
+                            .required(true)
+                            .help("The set operation. Difference keeps the tracks of the first source that are in none of the others"),
+                    )
+                    .arg(
+                        Arg::new("cbinto")
+                            .long("into")
+                            .value_name("PLAYLIST-NAME")
+                            .required_unless_present("cbdryrun")
+                            .help("The playlist to write to, created when it does not exist"),
+                    )
+                    .arg(
+                        Arg::new("cbmode")
+                            .short('m')
+                            .long("mode")
+                            .value_name("MODE")
+                            .value_parser(["exact", "fuzzy"])
+                            .default_value("exact")
+                            .help("Match tracks by exact track ID or by recording fingerprint"),
+                    )
+                    .arg(
+                        Arg::new("cborder")
+                            .short('o')
+                            .long("order")
+                            .value_name("ORDER")
+                            .value_parser(["source", "added", "artist", "title"])
+                            .default_value("source")
+                            .help("The order of the written tracks"),
+                    )
+                    .arg(
+                        Arg::new("cbappend")
+                            .short('a')
+                            .long("append")
+                            .action(ArgAction::SetTrue)
+                            .help("Append to the target playlist instead of replacing its contents"),
+                    )
+                    .arg(
+                        Arg::new("cbforce")
+                            .long("force")
+                            .action(ArgAction::SetTrue)
+                            .conflicts_with("cbappend")
+                            .help("Replace the contents of the target playlist even if spotify-assistant did not create it"),
+                    )
+                    .arg(
+                        Arg::new("cbdryrun")
+                            .short('n')
+                            .long("dry-run")
+                            .action(ArgAction::SetTrue)
+                            .help("Only print the resulting tracks"),
+                    ),
+            )
+            .subcommand(
+                Command::new("split")
+                    .about("Split a playlist into one playlist per genre, decade, lead artist, added month or album type")
+                    .arg(
+                        Arg::new("spplaylist")
+                            .value_name("PLAYLIST | @liked")
+                            .required(true)
+                            .help("The playlist to split. Use @liked for your liked songs"),
+                    )
+                    .arg(
+                        Arg::new("spby")
+                            .short('b')
+                            .long("by")
+                            .value_name("KEY")
+                            .value_parser(["genre", "decade", "lead-artist", "added-month", "album-type"])
+                            .required(true)
+                            .help("How to group the tracks. Genres are those of the lead artist"),
+                    )
+                    .arg(
+                        Arg::new("spminsize")
+                            .short('m')
+                            .long("min-size")
+                            .value_name("COUNT")
+                            .value_parser(value_parser!(usize))
+                            .default_value("10")
+                            .help("Only create playlists for groups with at least this many tracks"),
+                    )
+                    .arg(
+                        Arg::new("spname")
+                            .long("name")
+                            .value_name("TEMPLATE")
+                            .default_value(DEFAULT_SPLIT_TEMPLATE)
+                            .help("The name of the child playlists. Placeholders: {playlist}, {group}, {by}"),
+                    )
+                    .arg(
+                        Arg::new("spforce")
+                            .long("force")
+                            .action(ArgAction::SetTrue)
+                            .help("Replace the contents of existing group playlists even if spotify-assistant did not create them"),
+                    )
+                    .arg(
+                        Arg::new("spdryrun")
+                            .short('n')
+                            .long("dry-run")
+                            .action(ArgAction::SetTrue)
+                            .help("Only print the group sizes"),
+                    ),
+            )
+            .subcommand(
+                Command::new("sort")
+                    .about("Sort a playlist in place, keeping the date every track was added")
+                    .arg(
+                        Arg::new("soplaylist")
+                            .value_name("PLAYLIST-NAME | PLAYLIST-ID")
+                            .required(true)
+                            .help("The playlist to sort"),
+                    )
+                    .arg(
+                        Arg::new("soby")
+                            .short('b')
+                            .long("by")
+                            .value_name("KEY")
+                            .value_parser(["release-date", "artist", "album", "popularity", "duration", "added-at"])
+                            .required(true)
+                            .help("The field to sort by"),
+                    )
+                    .arg(
+                        Arg::new("sodesc")
+                            .short('d')
+                            .long("desc")
+                            .action(ArgAction::SetTrue)
+                            .help("Sort in descending order"),
+                    )
+                    .arg(
+                        Arg::new("sodryrun")
+                            .short('n')
+                            .long("dry-run")
+                            .action(ArgAction::SetTrue)
+                            .help("Only print how many moves the sort takes"),
+                    ),
+            )
+            .subcommand(
+                Command::new("track")
+                    .about("Record a new version of every owned playlist that changed since the last run"),
+            )
+            .subcommand(
+                Command::new("history")
+                    .about("Show the recorded versions of a playlist and what changed between them")
+                    .arg(
+                        Arg::new("hiplaylist")
+                            .value_name("PLAYLIST-NAME | PLAYLIST-ID")
+                            .required(true)
+                            .help("The playlist to show the history of"),
+                    ),
+            )
+            .subcommand(
+                Command::new("show")
+                    .about("Show a playlist as it was recorded at a date")
+                    .arg(
+                        Arg::new("shplaylist")
+                            .value_name("PLAYLIST-NAME | PLAYLIST-ID")
+                            .required(true)
+                            .help("The playlist to show"),
+                    )
+                    .arg(
+                        Arg::new("shat")
+                            .long("at")
+                            .value_name("DATE")
+                            .required(true)
+                            .help("The date (YYYY-MM-DD, end of day UTC) or RFC 3339 timestamp"),
+                    ),
+            )
+            .subcommand(
+                Command::new("edit")
+                    .about("Change the name, description, visibility or collaborative flag of many owned playlists at once")
+                    .arg(
+                        Arg::new("edmatch")
+                            .long("match")
+                            .value_name("REGEX")
+                            .required(true)
+                            .help("The pattern selecting the playlists by name"),
+                    )
+                    .arg(
+                        Arg::new("edrename")
+                            .long("rename")
+                            .value_name("REPLACEMENT")
+                            .help("Replace the matched part of the name. Capture groups are referenced as $1 or ${name}"),
+                    )
+                    .arg(
+                        Arg::new("eddescription")
+                            .long("description")
+                            .value_name("TEXT")
+                            .help("The new description"),
+                    )
+                    .arg(
+                        Arg::new("edpublic")
+                            .long("public")
+                            .value_name("BOOL")
+                            .value_parser(BoolishValueParser::new())
+                            .help("Make the playlists public (true) or private (false)"),
+                    )
+                    .arg(
+                        Arg::new("edcollaborative")
+                            .long("collaborative")
+                            .value_name("BOOL")
+                            .value_parser(BoolishValueParser::new())
+                            .help("Make the playlists collaborative (true) or not (false). Collaborative playlists are made private"),
+                    )
+                    .arg(
+                        Arg::new("edyes")
+                            .short('y')
+                            .long("yes")
+                            .action(ArgAction::SetTrue)
+                            .help("Apply the changes without asking for confirmation"),
+                    ),
+            )
+            .subcommand(
+                Command::new("check")
+                    .about("Report unplayable, restricted, local, episode and relinked items and offer replacements")
+                    .arg(
+                        Arg::new("chplaylist")
+                            .value_name("PLAYLIST")
+                            .required_unless_present("chall")
+                            .conflicts_with("chall")
+                            .help("The name, ID or URI of the playlist"),
+                    )
+                    .arg(
+                        Arg::new("chall")
+                            .short('a')
+                            .long("all")
+                            .action(ArgAction::SetTrue)
+                            .help("Check every playlist you own"),
+                    )
+                    .arg(
+                        Arg::new("chreplace")
+                            .short('r')
+                            .long("replace")
+                            .action(ArgAction::SetTrue)
+                            .help("Search playable versions of the affected tracks and swap them in at the same position"),
+                    )
+                    .arg(
+                        Arg::new("chyes")
+                            .short('y')
+                            .long("yes")
+                            .requires("chreplace")
+                            .action(ArgAction::SetTrue)
+                            .help("Take clear matches without asking and skip the others"),
+                    ),
+            )
+            .subcommand(
+                Command::new("list")
+                    .about("List the playlists in your library with owner, track count, visibility and snapshot")
+                    .arg(
+                        Arg::new("lsowned")
+                            .short('o')
+                            .long("owned")
+                            .action(ArgAction::SetTrue)
+                            .help("Only list the playlists you own"),
+                    )
+                    .arg(
+                        Arg::new("lsformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["table", "json"])
+                            .default_value("table")
+                            .help("Print the playlists as a table or as JSON"),
+                    ),
+            )
+            .subcommand(
+                Command::new("create")
+                    .about("Create a playlist, optionally seeded with the tracks of a file")
+                    .arg(
+                        Arg::new("crname")
+                            .value_name("NAME")
+                            .required(true)
+                            .help("The name of the new playlist"),
+                    )
+                    .arg(
+                        Arg::new("crdescription")
+                            .short('d')
+                            .long("description")
+                            .value_name("TEXT")
+                            .help("The description of the new playlist"),
+                    )
+                    .arg(
+                        Arg::new("crpublic")
+                            .short('p')
+                            .long("public")
+                            .action(ArgAction::SetTrue)
+                            .help("Make the playlist public (playlists are private by default)"),
+                    )
+                    .arg(
+                        Arg::new("crseed")
+                            .short('s')
+                            .long("seed")
+                            .value_name("FILE")
+                            .help("Add the tracks of a CSV, M3U or text file, matched like the import subcommand does"),
+                    )
+                    .arg(
+                        Arg::new("crformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["text", "json"])
+                            .default_value("text")
+                            .help("Print the created playlist as text or as JSON"),
+                    ),
             )
-            .arg(
-                Arg::new("pmove")
-                    .short('m')
-                    .long("move")
-                    .value_name("folder-name")
-                    .help("Move a playlists"),
+            .subcommand(
+                Command::new("delete")
+                    .about("Delete a playlist you own, or unfollow one owned by someone else")
+                    .arg(
+                        Arg::new("deplaylist")
+                            .value_name("PLAYLIST")
+                            .required(true)
+                            .help("The name, ID or URI of the playlist"),
+                    )
+                    .arg(
+                        Arg::new("deyes")
+                            .short('y')
+                            .long("yes")
+                            .action(ArgAction::SetTrue)
+                            .help("Delete without asking for confirmation"),
+                    ),
             )
-            .arg(
-                Arg::new("pdelete")
-                    .short('d')
-                    .long("delete")
-                    .help("Delete a playlists"),
+            .subcommand(
+                Command::new("move")
+                    .about("Move tracks from one playlist to another")
+                    .arg(
+                        Arg::new("mvfrom")
+                            .value_name("FROM")
+                            .required(true)
+                            .help("The name, ID or URI of the playlist the tracks are moved out of"),
+                    )
+                    .arg(
+                        Arg::new("mvto")
+                            .value_name("TO")
+                            .required(true)
+                            .help("The name, ID or URI of the playlist the tracks are moved into"),
+                    )
+                    .arg(
+                        Arg::new("mvtracks")
+                            .value_name("TRACK")
+                            .num_args(1..)
+                            .help("The IDs or URIs of the tracks to move"),
+                    )
+                    .arg(
+                        Arg::new("mvfile")
+                            .long("file")
+                            .value_name("FILE")
+                            .help("A file with one track ID or URI per line"),
+                    )
+                    .arg(
+                        Arg::new("mvall")
+                            .short('a')
+                            .long("all")
+                            .action(ArgAction::SetTrue)
+                            .conflicts_with_all(["mvtracks", "mvfile"])
+                            .help("Move every track of the source playlist"),
+                    )
+                    .arg(
+                        Arg::new("mvformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["text", "json"])
+                            .default_value("text")
+                            .help("Print the result as text or as JSON"),
+                    )
+                    .group(
+                        ArgGroup::new("mvselection")
+                            .args(["mvtracks", "mvfile", "mvall"])
+                            .multiple(true)
+                            .required(true),
+                    ),
             )
             .styles(TerminalApp::styling())
     }
@@ -777,6 +3022,28 @@ impl TerminalApp {
                     .action(ArgAction::SetTrue)
                     .help("List all user playlists"),
             )
+            .subcommand(
+                Command::new("duplicates")
+                    .short_flag('D')
+                    .long_flag("duplicates")
+                    .about("Report duplicate tracks across your playlists and liked songs")
+                    .arg(
+                        Arg::new("dformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["json", "csv"])
+                            .help("Export the full report in the given format"),
+                    )
+                    .arg(
+                        Arg::new("doutput")
+                            .short('o')
+                            .long("output")
+                            .value_name("PATH")
+                            .requires("dformat")
+                            .help("Where to write the exported report. Defaults to the data directory"),
+                    ),
+            )
             .styles(TerminalApp::styling())
             .after_help("This command will list all songs in the specified Release Radar playlists")
     }
@@ -811,18 +3078,631 @@ impl TerminalApp {
                     .short_flag('C')
                     .long_flag("compare")
                     .color(ColorChoice::Always)
-                    .about("Compare the Release Radar playlists to another playlists")
+                    .about("Compare the Release Radar playlist to another playlist, track by track")
                     .arg(
                         Arg::new("playlisttocompare")
                             .short('p')
                             .long("playlists")
-                            .value_name("PLAYLIST-NAME")
-                            .help("The exact name of the playlists to use for comparison"),
+                            .value_name("PLAYLIST-NAME | PLAYLIST-ID | SNAPSHOT-FILE")
+                            .help("The playlist to use for comparison. Defaults to the lagging Release Radar"),
+                    )
+                    .arg(
+                        Arg::new("compareformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["table", "json"])
+                            .default_value("table")
+                            .help("Print the diff as a side-by-side table or as JSON"),
+                    ),
+            )
+            .styles(TerminalApp::styling())
+    }
+
+    /// Defines the smart playlists command.
+    ///
+    /// This function creates the smart command with subcommands to save, list, remove and
+    /// refresh rule-based playlist definitions.
+    ///
+    /// # Returns
+    /// A `Command` for managing smart playlists.
+    fn smart_command() -> Command {
+        Command::new("smart")
+            .short_flag('S')
+            .long_flag("smart")
+            .about("Playlists generated from rules over your liked songs or another playlist")
+            .subcommand(
+                Command::new("create")
+                    .about("Save a smart playlist definition and generate the playlist")
+                    .arg(
+                        Arg::new("smname")
+                            .value_name("NAME")
+                            .required(true)
+                            .help("The name of the generated playlist"),
+                    )
+                    .arg(
+                        Arg::new("smsource")
+                            .short('s')
+                            .long("source")
+                            .value_name("PLAYLIST | @liked")
+                            .default_value("@liked")
+                            .help("The tracks the rules are applied to"),
+                    )
+                    .arg(
+                        Arg::new("smrule")
+                            .short('r')
+                            .long("rule")
+                            .value_name("RULE")
+                            .action(ArgAction::Append)
+                            .help("A rule every track has to satisfy, e.g. \"popularity < 40\", \"released >= 2010\", \"explicit = false\" or \"duration < 5m\". Fields: popularity, explicit, duration, released, album_type, artist, added"),
+                    )
+                    .arg(
+                        Arg::new("smorder")
+                            .short('o')
+                            .long("order")
+                            .value_name("ORDER")
+                            .value_parser(["source", "added", "artist", "title"])
+                            .default_value("source")
+                            .help("The order of the written tracks"),
+                    )
+                    .arg(
+                        Arg::new("smlimit")
+                            .short('l')
+                            .long("limit")
+                            .value_name("COUNT")
+                            .value_parser(value_parser!(usize))
+                            .help("The maximum number of tracks to write"),
+                    )
+                    .arg(Self::smart_force_arg()),
+            )
+            .subcommand(Command::new("list").about("List the saved smart playlist definitions"))
+            .subcommand(
+                Command::new("remove")
+                    .about("Remove a smart playlist definition. The Spotify playlist is kept")
+                    .arg(
+                        Arg::new("smname")
+                            .value_name("NAME")
+                            .required(true)
+                            .help("The name of the smart playlist"),
+                    ),
+            )
+            .subcommand(
+                Command::new("refresh")
+                    .about("Regenerate the smart playlists and summarize what changed")
+                    .arg(
+                        Arg::new("smname")
+                            .value_name("NAME")
+                            .help("Only refresh this smart playlist"),
+                    )
+                    .arg(Self::smart_force_arg()),
+            )
+            .styles(TerminalApp::styling())
+    }
+
+    /// Defines the `--force` flag shared by the smart create and refresh subcommands.
+    fn smart_force_arg() -> Arg {
+        Arg::new("smforce")
+            .long("force")
+            .action(ArgAction::SetTrue)
+            .help("Replace the contents of existing playlists even if spotify-assistant did not create them")
+    }
+
+    /// Defines the library command.
+    ///
+    /// This function creates and returns the library command, which keeps the local copy of the
+    /// liked songs and saved albums in sync with Spotify, mirrors the liked songs into playlists
+    /// and reports statistics and album completion.
+    ///
+    /// # Returns
+    /// A `Command` for managing the liked songs library.
+    fn library_command() -> Command {
+        Command::new("library")
+            .short_flag('L')
+            .long_flag("library")
+            .about("Sync, mirror, analyze, back up, restore and prune your liked songs, and manage saved albums")
+            .subcommand(
+                Command::new("sync")
+                    .about("Fetch the songs liked since the last sync and record likes and unlikes")
+                    .arg(
+                        Arg::new("lbfull")
+                            .long("full")
+                            .action(ArgAction::SetTrue)
+                            .help("Fetch the whole library to also detect unliked songs"),
+                    ),
+            )
+            .subcommand(
+                Command::new("mirror")
+                    .about("Maintain playlists of your liked songs grouped by the year, month or recent days they were liked")
+                    .arg(
+                        Arg::new("lbby")
+                            .short('b')
+                            .long("by")
+                            .value_name("PERIOD")
+                            .default_value("year")
+                            .help("year, month, recent (last 90 days) or recent:<days>"),
+                    )
+                    .arg(
+                        Arg::new("lbsince")
+                            .short('s')
+                            .long("since")
+                            .value_name("YYYY | YYYY-MM")
+                            .help("Skip years or months before this one"),
+                    )
+                    .arg(
+                        Arg::new("lbname")
+                            .short('n')
+                            .long("name")
+                            .value_name("TEMPLATE")
+                            .help("The playlist name template. Placeholder: {period}. Defaults to \"Liked {period}\""),
+                    )
+                    .arg(
+                        Arg::new("lbforce")
+                            .long("force")
+                            .action(ArgAction::SetTrue)
+                            .help("Replace the contents of existing playlists even if spotify-assistant did not create them"),
+                    )
+                    .arg(
+                        Arg::new("lbdryrun")
+                            .long("dry-run")
+                            .action(ArgAction::SetTrue)
+                            .help("Print the playlists that would be written without changing anything"),
+                    ),
+            )
+            .subcommand(
+                Command::new("stats")
+                    .about("Statistics over your liked songs: adds per month, top artists and albums, genres, decades")
+                    .arg(
+                        Arg::new("lbtop")
+                            .short('t')
+                            .long("top")
+                            .value_name("COUNT")
+                            .value_parser(value_parser!(usize))
+                            .default_value("10")
+                            .help("The number of artists, albums and genres listed"),
+                    )
+                    .arg(
+                        Arg::new("lbnogenres")
+                            .long("no-genres")
+                            .action(ArgAction::SetTrue)
+                            .help("Skip the artist lookups needed for the genre distribution"),
+                    )
+                    .arg(
+                        Arg::new("lbformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["table", "json"])
+                            .default_value("table")
+                            .help("Print the statistics as tables or as JSON"),
+                    ),
+            )
+            .subcommand(
+                Command::new("albums")
+                    .about("List, save, remove or sync your saved albums")
+                    .subcommand(
+                        Command::new("list")
+                            .about("List the saved albums from the local cache")
+                            .arg(
+                                Arg::new("lbformat")
+                                    .short('f')
+                                    .long("format")
+                                    .value_name("FORMAT")
+                                    .value_parser(["table", "json"])
+                                    .default_value("table")
+                                    .help("Print the albums as a table or as JSON"),
+                            ),
+                    )
+                    .subcommand(
+                        Command::new("save")
+                            .about("Save albums to your library")
+                            .arg(
+                                Arg::new("lbalbum")
+                                    .value_name("ALBUM")
+                                    .num_args(1..)
+                                    .required(true)
+                                    .help("Album IDs or Spotify URIs"),
+                            ),
+                    )
+                    .subcommand(
+                        Command::new("remove")
+                            .about("Remove albums from your library")
+                            .arg(
+                                Arg::new("lbalbum")
+                                    .value_name("ALBUM")
+                                    .num_args(1..)
+                                    .required(true)
+                                    .help("Album IDs or Spotify URIs"),
+                            ),
+                    )
+                    .subcommand(Command::new("sync").about("Fetch every saved album and refresh the local cache")),
+            )
+            .subcommand(
+                Command::new("completion")
+                    .about("Find albums of which you liked many tracks, then save them or queue the rest of their tracks")
+                    .arg(
+                        Arg::new("lbmintracks")
+                            .short('m')
+                            .long("min-tracks")
+                            .value_name("COUNT")
+                            .value_parser(value_parser!(usize))
+                            .default_value("5")
+                            .help("Report albums with at least this many liked tracks"),
+                    )
+                    .arg(
+                        Arg::new("lbminfraction")
+                            .short('r')
+                            .long("min-fraction")
+                            .value_name("FRACTION")
+                            .value_parser(value_parser!(f64))
+                            .default_value("0.5")
+                            .help("Report albums of which at least this share of tracks is liked (0 to 1)"),
+                    )
+                    .arg(
+                        Arg::new("lbsave")
+                            .long("save")
+                            .action(ArgAction::SetTrue)
+                            .help("Save the reported albums that are not saved yet"),
+                    )
+                    .arg(
+                        Arg::new("lbqueue")
+                            .short('q')
+                            .long("queue")
+                            .value_name("PLAYLIST")
+                            .help("Append the tracks you have not liked yet to this playlist, creating it if needed"),
+                    )
+                    .arg(
+                        Arg::new("lbyes")
+                            .short('y')
+                            .long("yes")
+                            .action(ArgAction::SetTrue)
+                            .help("Do not ask for confirmation before saving albums"),
+                    )
+                    .arg(
+                        Arg::new("lbformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["table", "json"])
+                            .default_value("table")
+                            .help("Print the report as a table or as JSON"),
+                    ),
+            )
+            .subcommand(
+                Command::new("backup")
+                    .about("Write your liked songs, with the time they were liked and their ISRCs, to a portable file")
+                    .arg(
+                        Arg::new("lboutput")
+                            .short('o')
+                            .long("output")
+                            .value_name("FILE")
+                            .value_parser(value_parser!(PathBuf))
+                            .help("Where to write the backup. Defaults to liked_songs_backup_<date>.json in the data directory"),
+                    ),
+            )
+            .subcommand(
+                Command::new("restore")
+                    .about("Like the songs of a backup, oldest first, resuming an interrupted restore")
+                    .arg(
+                        Arg::new("lbfile")
+                            .value_name("FILE")
+                            .value_parser(value_parser!(PathBuf))
+                            .required(true)
+                            .help("A backup written by library backup"),
+                    )
+                    .arg(
+                        Arg::new("lbprofile")
+                            .short('p')
+                            .long("profile")
+                            .value_name("NAME")
+                            .help("Restore to the account of this profile; its first use asks you to log in"),
+                    )
+                    .arg(
+                        Arg::new("lbyes")
+                            .short('y')
+                            .long("yes")
+                            .action(ArgAction::SetTrue)
+                            .help("Do not ask for confirmation"),
+                    ),
+            )
+            .subcommand(
+                Command::new("prune")
+                    .about("Unlike the liked songs selected by rules, after backing them up")
+                    .arg(
+                        Arg::new("lbrule")
+                            .short('r')
+                            .long("rule")
+                            .value_name("RULE")
+                            .action(ArgAction::Append)
+                            .required(true)
+                            .help("blacklist, duplicates, genre:<text>, before:<year> or after:<year>. Repeat to combine"),
+                    )
+                    .arg(
+                        Arg::new("lbbackup")
+                            .short('o')
+                            .long("backup")
+                            .value_name("FILE")
+                            .value_parser(value_parser!(PathBuf))
+                            .help("Where to back up the removed songs. Defaults to pruned_<timestamp>.json in the data directory"),
+                    )
+                    .arg(
+                        Arg::new("lbdryrun")
+                            .long("dry-run")
+                            .action(ArgAction::SetTrue)
+                            .help("Print the songs that would be unliked without changing anything"),
+                    )
+                    .arg(
+                        Arg::new("lbyes")
+                            .short('y')
+                            .long("yes")
+                            .action(ArgAction::SetTrue)
+                            .help("Do not ask for confirmation"),
+                    ),
+            )
+            .styles(TerminalApp::styling())
+    }
+
+    /// Defines the artists command.
+    ///
+    /// This function creates and returns the artists command, which records the followed
+    /// artists over time, reports follows, unfollows and popularity, follower and genre
+    /// changes, follows or unfollows artists in bulk, suggests artists to follow, and reports
+    /// the releases of artists missing from the library.
+    ///
+    /// # Returns
+    /// A `Command` for tracking the followed artists.
+    fn artists_command() -> Command {
+        Command::new("artists")
+            .short_flag('A')
+            .long_flag("artists")
+            .about("Track and manage your followed artists")
+            .subcommand(
+                Command::new("sync")
+                    .about("Record the followed artists with their popularity, followers and genres"),
+            )
+            .subcommand(
+                Command::new("history")
+                    .about("Show every recorded state of an artist")
+                    .arg(
+                        Arg::new("arartist")
+                            .value_name("ARTIST")
+                            .required(true)
+                            .help("An artist ID or part of the artist's name"),
+                    ),
+            )
+            .subcommand(
+                Command::new("changes")
+                    .about("Show follows, unfollows and popularity, follower and genre changes since a date")
+                    .arg(
+                        Arg::new("arsince")
+                            .short('s')
+                            .long("since")
+                            .value_name("YYYY-MM-DD | RFC 3339")
+                            .required(true)
+                            .help("Compare the latest sync with the last sync recorded by then"),
+                    )
+                    .arg(
+                        Arg::new("arformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["table", "json"])
+                            .default_value("table")
+                            .help("Print the changes as a table or as JSON"),
+                    ),
+            )
+            .subcommand(Self::artist_follow_command(
+                "follow",
+                "Follow artists picked from playlists, liked songs, files or the blacklist",
+            ))
+            .subcommand(Self::artist_follow_command(
+                "unfollow",
+                "Unfollow artists picked from playlists, liked songs, files or the blacklist",
+            ))
+            .subcommand(
+                Command::new("suggest")
+                    .about("Rank artists you do not follow by how often and how recently they appear in your library")
+                    .arg(
+                        Arg::new("arlimit")
+                            .short('n')
+                            .long("limit")
+                            .value_name("NUMBER")
+                            .value_parser(value_parser!(usize))
+                            .default_value("25")
+                            .help("The number of suggestions"),
+                    )
+                    .arg(
+                        Arg::new("arnoliked")
+                            .long("no-liked")
+                            .action(ArgAction::SetTrue)
+                            .help("Do not count the liked songs"),
+                    )
+                    .arg(
+                        Arg::new("arnoplaylists")
+                            .long("no-playlists")
+                            .action(ArgAction::SetTrue)
+                            .help("Do not count the tracks of your own playlists"),
+                    )
+                    .arg(
+                        Arg::new("arnohistory")
+                            .long("no-history")
+                            .action(ArgAction::SetTrue)
+                            .help("Do not count the recently played tracks"),
+                    )
+                    .arg(
+                        Arg::new("arformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["table", "json"])
+                            .default_value("table")
+                            .help("Print the suggestions as a table and pick artists to follow, or print them as JSON"),
+                    ),
+            )
+            .subcommand(
+                Command::new("gaps")
+                    .about("Show which albums and singles of artists are fully, partially or not at all in your library")
+                    .arg(
+                        Arg::new("arartist")
+                            .value_name("ARTIST")
+                            .help("An artist ID, URI or name"),
+                    )
+                    .arg(
+                        Arg::new("arfollowed")
+                            .long("followed")
+                            .action(ArgAction::SetTrue)
+                            .help("Check every followed artist"),
+                    )
+                    .arg(
+                        Arg::new("arnoplaylists")
+                            .long("no-playlists")
+                            .action(ArgAction::SetTrue)
+                            .help("Only count liked songs and saved albums, not the tracks of your own playlists"),
+                    )
+                    .arg(
+                        Arg::new("arqueue")
+                            .short('q')
+                            .long("queue")
+                            .value_name("PLAYLIST")
+                            .help("Append the tracks missing from your library to this playlist, creating it if needed"),
+                    )
+                    .arg(
+                        Arg::new("arformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["table", "json"])
+                            .default_value("table")
+                            .help("Print the report as a table or as JSON"),
+                    )
+                    .group(
+                        ArgGroup::new("argapstarget")
+                            .args(["arartist", "arfollowed"])
+                            .required(true),
+                    ),
+            )
+            .subcommand(
+                Command::new("graph")
+                    .about("Export the graph of artists sharing tracks, for Gephi, Graphviz or scripts")
+                    .arg(
+                        Arg::new("arartist")
+                            .value_name("ARTIST")
+                            .help("Crawl from this artist ID, URI or name"),
+                    )
+                    .arg(
+                        Arg::new("arfollowed")
+                            .long("followed")
+                            .action(ArgAction::SetTrue)
+                            .help("Crawl from every followed artist"),
+                    )
+                    .arg(
+                        Arg::new("ardepth")
+                            .short('d')
+                            .long("depth")
+                            .value_name("NUMBER")
+                            .value_parser(value_parser!(u32).range(1..))
+                            .default_value("1")
+                            .help("How many collaborations away from the starting artists to crawl"),
+                    )
+                    .arg(
+                        Arg::new("arbudget")
+                            .short('b')
+                            .long("budget")
+                            .value_name("REQUESTS")
+                            .value_parser(value_parser!(u32).range(1..))
+                            .default_value("200")
+                            .help("The maximum number of API requests; the crawl stops once they are spent"),
+                    )
+                    .arg(
+                        Arg::new("aroutput")
+                            .short('o')
+                            .long("output")
+                            .value_name("FILE")
+                            .value_parser(value_parser!(PathBuf))
+                            .help("Where to write the graph. Defaults to collaborations_<date> in the data directory"),
+                    )
+                    .arg(
+                        Arg::new("arformat")
+                            .short('f')
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(["graphml", "dot", "json"])
+                            .help("The graph format. Guessed from the output file extension, GraphML otherwise"),
+                    )
+                    .group(
+                        ArgGroup::new("argraphtarget")
+                            .args(["arartist", "arfollowed"])
+                            .required(true),
                     ),
             )
             .styles(TerminalApp::styling())
     }
 
+    /// Defines the follow and unfollow subcommands of the artists command.
+    ///
+    /// Both take the same artist sources; any number of them can be combined.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the subcommand.
+    /// * `about` - The description of the subcommand.
+    ///
+    /// # Returns
+    /// A `Command` collecting artists to follow or unfollow.
+    fn artist_follow_command(name: &'static str, about: &'static str) -> Command {
+        Command::new(name)
+            .about(about)
+            .arg(
+                Arg::new("arplaylist")
+                    .short('p')
+                    .long("playlist")
+                    .value_name("PLAYLIST")
+                    .action(ArgAction::Append)
+                    .help("The artists of a playlist, by name or ID; can be repeated"),
+            )
+            .arg(
+                Arg::new("arliked")
+                    .short('l')
+                    .long("liked")
+                    .action(ArgAction::SetTrue)
+                    .help("The artists of your liked songs"),
+            )
+            .arg(
+                Arg::new("arjson")
+                    .short('j')
+                    .long("json")
+                    .value_name("PATH")
+                    .value_parser(value_parser!(PathBuf))
+                    .help("A JSON file of artists, such as a followed artists snapshot"),
+            )
+            .arg(
+                Arg::new("arblacklist")
+                    .short('b')
+                    .long("blacklist")
+                    .action(ArgAction::SetTrue)
+                    .help("The blacklisted artists"),
+            )
+            .arg(
+                Arg::new("arnames")
+                    .short('n')
+                    .long("names")
+                    .value_name("PATH")
+                    .value_parser(value_parser!(PathBuf))
+                    .help("A text file with one artist name per line, resolved by search"),
+            )
+            .arg(
+                Arg::new("arall")
+                    .short('a')
+                    .long("all")
+                    .action(ArgAction::SetTrue)
+                    .help("Apply to every collected artist instead of picking them"),
+            )
+            .group(
+                ArgGroup::new("arsource")
+                    .args(["arplaylist", "arliked", "arjson", "arblacklist", "arnames"])
+                    .multiple(true)
+                    .required(true),
+            )
+    }
+
     /// Defines the listening history command.
     ///
     /// This function creates and defines the listening history command, including arguments for filtering,
@@ -921,6 +3801,15 @@ impl TerminalApp {
         if matches.subcommand_matches("playlists").is_some() {
             subcommand_count += 1;
         }
+        if matches.subcommand_matches("smart").is_some() {
+            subcommand_count += 1;
+        }
+        if matches.subcommand_matches("library").is_some() {
+            subcommand_count += 1;
+        }
+        if matches.subcommand_matches("artists").is_some() {
+            subcommand_count += 1;
+        }
         if subcommand_count > 1 {
             eprintln!("Error: Only one subcommand can be used at a time");
             std::process::exit(1);
@@ -1,4 +1,5 @@
 use dialoguer::{FuzzySelect, MultiSelect, theme::ColorfulTheme};
+use spotify_assistant_core::actions::playlists::import::{ImportEntry, MatchCandidate};
 use spotify_assistant_core::models::filtering::ArtistLite;
 
 #[allow(dead_code)]
@@ -49,3 +50,38 @@ pub fn pick_multiple_artists(candidates: &[ArtistLite]) -> Vec<&ArtistLite> {
         .filter_map(|idx| candidates.get(idx))
         .collect()
 }
+
+/// Asks the user which candidate matches an imported entry.
+///
+/// The last item skips the entry, in which case `None` is returned.
+pub fn pick_track_match(entry: &ImportEntry, candidates: &[MatchCandidate]) -> Option<usize> {
+    let mut items: Vec<String> = candidates
+        .iter()
+        .map(|c| {
+            let artists = c
+                .track
+                .artists
+                .iter()
+                .map(|artist| artist.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ");
+            format!(
+                "{} - {}  ({}, {:.0}% match)",
+                artists,
+                c.track.name,
+                c.track.album.name,
+                c.confidence * 100.0
+            )
+        })
+        .collect();
+    items.push("Skip (leave unmatched)".to_string());
+
+    let idx = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Line {}: {}", entry.line, entry.describe()))
+        .items(&items)
+        .default(0)
+        .interact_opt()
+        .ok()??;
+
+    (idx < candidates.len()).then_some(idx)
+}
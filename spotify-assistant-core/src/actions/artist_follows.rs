@@ -0,0 +1,301 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use rspotify::model::{ArtistId, FullArtist, PlaylistId, SearchResult, SearchType};
+use rspotify::prelude::{BaseClient, Id, OAuthClient};
+use rspotify::{AuthCodeSpotify, ClientError, scopes};
+use tracing::{Level, event};
+
+use crate::actions::exploration::playlist::PlaylistXplr;
+use crate::actions::liked_songs::UserLibrary;
+use crate::enums::validation::BatchLimits;
+use crate::errors::configuration::ConfigurationError;
+use crate::models::blacklist::Blacklist;
+use crate::models::filtering::{ArtistLite, load_artists_from_json};
+use crate::traits::apis::Api;
+
+/// The number of search results considered when resolving an artist name.
+const NAME_SEARCH_LIMIT: u32 = 5;
+
+/// The outcome of a bulk follow or unfollow.
+///
+/// # Fields
+///
+/// * `changed` - The artists followed or unfollowed.
+/// * `failed` - The artists of the batches that failed.
+#[derive(Clone, Debug, Default)]
+pub struct FollowSummary {
+    pub changed: Vec<ArtistLite>,
+    pub failed: Vec<ArtistLite>,
+}
+
+/// Collects artists from several sources and follows or unfollows them in bulk.
+///
+/// Every source yields [`ArtistLite`]s with popularity and follower counts, ready for
+/// selection. [`ArtistFollower::partition_followed`] tells which of them would change, and
+/// only those should be passed to [`ArtistFollower::follow`] or [`ArtistFollower::unfollow`],
+/// which apply the changes 50 artists per request.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::artist_follows::ArtistFollower;
+///
+/// async fn main() {
+///     let follower = ArtistFollower::new().await;
+///     let artists = follower.from_liked_songs().await;
+///     let (_, not_followed) = follower.partition_followed(artists).await.unwrap();
+///     let summary = follower.follow(&not_followed).await;
+///     println!("Followed {} artists", summary.changed.len());
+/// }
+/// ```
+pub struct ArtistFollower {
+    client: AuthCodeSpotify,
+}
+
+impl Api for ArtistFollower {
+    fn select_scopes() -> HashSet<String> {
+        scopes!(
+            "user-follow-read",
+            "user-follow-modify",
+            "playlist-read-private",
+            "playlist-read-collaborative",
+            "user-library-read"
+        )
+    }
+}
+
+impl ArtistFollower {
+    pub async fn new() -> Self {
+        let span = tracing::span!(Level::INFO, "ArtistFollower.new");
+        let _enter = span.enter();
+
+        ArtistFollower {
+            client: Self::set_up_client(false, Some(Self::select_scopes())).await,
+        }
+    }
+
+    /// The artists of the tracks of a playlist.
+    pub async fn from_playlist(&self, playlist_id: PlaylistId<'static>) -> Vec<ArtistLite> {
+        let xplr = PlaylistXplr::new(playlist_id, false).await;
+        let artist_ids = xplr
+            .artists()
+            .into_iter()
+            .filter_map(|artist| artist.id)
+            .collect::<Vec<ArtistId<'static>>>();
+        self.lookup(artist_ids).await
+    }
+
+    /// The artists of the liked songs.
+    pub async fn from_liked_songs(&self) -> Vec<ArtistLite> {
+        let artist_ids = UserLibrary::new()
+            .await
+            .tracks()
+            .into_iter()
+            .flat_map(|saved_track| saved_track.track.artists)
+            .filter_map(|artist| artist.id)
+            .collect::<Vec<ArtistId<'static>>>();
+        self.lookup(artist_ids).await
+    }
+
+    /// The artists of a JSON file of [`ArtistLite`]s, such as a followed artists snapshot.
+    pub fn from_json(path: &Path) -> anyhow::Result<Vec<ArtistLite>> {
+        load_artists_from_json(path.to_path_buf())
+    }
+
+    /// The blacklisted artists.
+    ///
+    /// # Errors
+    /// Returns the error of reading the blacklist file.
+    pub async fn from_blacklist(&self) -> Result<Vec<ArtistLite>, ConfigurationError> {
+        let artist_ids = Blacklist::load()?
+            .artists()
+            .iter()
+            .filter_map(|artist| ArtistId::from_id(artist.id()).ok())
+            .collect::<Vec<ArtistId<'static>>>();
+        Ok(self.lookup(artist_ids).await)
+    }
+
+    /// Resolves a text list of artist names, one per line, by searching for each name.
+    ///
+    /// # Returns
+    /// The artists found and the names that matched no artist.
+    pub async fn from_names(&self, contents: &str) -> (Vec<ArtistLite>, Vec<String>) {
+        let span = tracing::span!(Level::INFO, "ArtistFollower.from_names");
+        let _enter = span.enter();
+
+        let mut artists: Vec<ArtistLite> = Vec::new();
+        let mut unresolved = Vec::new();
+        for name in Self::parse_names(contents) {
+            let found = match self
+                .client
+                .search(&name, SearchType::Artist, None, None, Some(NAME_SEARCH_LIMIT), None)
+                .await
+            {
+                Ok(SearchResult::Artists(page)) => page.items,
+                Ok(other) => {
+                    event!(Level::WARN, "Unexpected search result: {:?}", other);
+                    Vec::new()
+                }
+                Err(err) => {
+                    event!(Level::ERROR, "Error searching for {name}: {:?}", err);
+                    Vec::new()
+                }
+            };
+            match Self::best_name_match(&name, &found) {
+                Some(artist) if !artists.iter().any(|known| known.id() == artist.id.id()) => {
+                    artists.push(ArtistLite::from(artist));
+                }
+                Some(_) => {}
+                None => unresolved.push(name),
+            }
+        }
+        (artists, unresolved)
+    }
+
+    /// Reads one artist name per line, skipping blank lines and `#` comments.
+    pub fn parse_names(contents: &str) -> Vec<String> {
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Picks the search result for a name: the first artist with exactly that name, ignoring
+    /// case, or else the first result.
+    pub fn best_name_match<'a>(name: &str, found: &'a [FullArtist]) -> Option<&'a FullArtist> {
+        found
+            .iter()
+            .find(|artist| artist.name.eq_ignore_ascii_case(name.trim()))
+            .or_else(|| found.first())
+    }
+
+    /// Loads the artists with the given IDs, 50 per request, dropping duplicates. Failed
+    /// batches are logged and skipped.
+    pub async fn lookup(&self, artist_ids: Vec<ArtistId<'static>>) -> Vec<ArtistLite> {
+        let artist_ids = Self::clean_duplicate_id_vector(artist_ids);
+        let mut artists = Vec::with_capacity(artist_ids.len());
+        for chunk in artist_ids.chunks(BatchLimits::Artists.get_limit()) {
+            match self.client.artists(chunk.iter().cloned()).await {
+                Ok(found) => artists.extend(found.iter().map(ArtistLite::from)),
+                Err(err) => event!(Level::ERROR, "Could not load a batch of artists: {:?}", err),
+            }
+        }
+        artists.sort_by_key(|artist| artist.name().to_lowercase());
+        artists
+    }
+
+    /// Splits the artists into those the user follows and those they do not. Artists with an
+    /// invalid ID are left out.
+    ///
+    /// # Errors
+    /// Returns the error of the first failed request.
+    pub async fn partition_followed(
+        &self,
+        artists: Vec<ArtistLite>,
+    ) -> Result<(Vec<ArtistLite>, Vec<ArtistLite>), ClientError> {
+        let mut followed = Vec::new();
+        let mut not_followed = Vec::new();
+        let artists = artists
+            .into_iter()
+            .filter(|artist| {
+                let valid = ArtistId::from_id(artist.id()).is_ok();
+                if !valid {
+                    event!(Level::WARN, "Skipping {}: invalid artist ID {}", artist.name(), artist.id());
+                }
+                valid
+            })
+            .collect::<Vec<ArtistLite>>();
+        for chunk in artists.chunks(BatchLimits::ModifyWhoCurrentUserFollows.get_limit()) {
+            let following = self.client.user_artist_check_follow(Self::ids(chunk)).await?;
+            for (artist, follows) in chunk.iter().zip(following) {
+                if follows {
+                    followed.push(artist.clone());
+                } else {
+                    not_followed.push(artist.clone());
+                }
+            }
+        }
+        Ok((followed, not_followed))
+    }
+
+    /// Follows the artists. Callers pass the artists
+    /// [`ArtistFollower::partition_followed`] found not followed.
+    pub async fn follow(&self, artists: &[ArtistLite]) -> FollowSummary {
+        let span = tracing::span!(Level::INFO, "ArtistFollower.follow");
+        let _enter = span.enter();
+
+        self.apply(artists, true).await
+    }
+
+    /// Unfollows the artists. Callers pass the artists
+    /// [`ArtistFollower::partition_followed`] found followed.
+    pub async fn unfollow(&self, artists: &[ArtistLite]) -> FollowSummary {
+        let span = tracing::span!(Level::INFO, "ArtistFollower.unfollow");
+        let _enter = span.enter();
+
+        self.apply(artists, false).await
+    }
+
+    async fn apply(&self, artists: &[ArtistLite], follow: bool) -> FollowSummary {
+        let mut summary = FollowSummary::default();
+        for chunk in artists.chunks(BatchLimits::ModifyWhoCurrentUserFollows.get_limit()) {
+            let artist_ids = Self::ids(chunk);
+            let result = if follow {
+                self.client.user_follow_artists(artist_ids).await
+            } else {
+                self.client.user_unfollow_artists(artist_ids).await
+            };
+            match result {
+                Ok(()) => summary.changed.extend(chunk.iter().cloned()),
+                Err(err) => {
+                    event!(Level::ERROR, "Could not change a batch of artists: {:?}", err);
+                    summary.failed.extend(chunk.iter().cloned());
+                }
+            }
+        }
+        event!(
+            Level::INFO,
+            "{} {} artists, {} failed",
+            if follow { "Followed" } else { "Unfollowed" },
+            summary.changed.len(),
+            summary.failed.len()
+        );
+        summary
+    }
+
+    fn ids(artists: &[ArtistLite]) -> Vec<ArtistId<'static>> {
+        artists
+            .iter()
+            .filter_map(|artist| ArtistId::from_id(artist.id().to_string()).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    #[test]
+    fn resolves_names_from_a_text_list() {
+        let names = ArtistFollower::parse_names("# Favourites\nRadiohead\n\n  Björk  \n");
+        assert_eq!(names, vec!["Radiohead", "Björk"]);
+
+        let found = vec![
+            OfflineObjects::artist_full(&"A".repeat(22), "Radiohead Tribute"),
+            OfflineObjects::artist_full(&"B".repeat(22), "radiohead"),
+        ];
+        assert_eq!(
+            ArtistFollower::best_name_match("Radiohead", &found).map(|artist| artist.name.as_str()),
+            Some("radiohead")
+        );
+        assert_eq!(
+            ArtistFollower::best_name_match("Portishead", &found).map(|artist| artist.name.as_str()),
+            Some("Radiohead Tribute")
+        );
+        assert!(ArtistFollower::best_name_match("Portishead", &[]).is_none());
+        assert_eq!(ArtistLite::from(&found[1]).id(), "B".repeat(22));
+    }
+}
@@ -0,0 +1,395 @@
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+use rspotify::model::{AlbumType, ArtistId, SimplifiedArtist, SimplifiedTrack};
+use rspotify::prelude::{BaseClient, Id, OAuthClient};
+use rspotify::{AuthCodeSpotify, ClientError, scopes};
+use serde::Serialize;
+use tracing::{Level, event};
+
+use crate::enums::graph_format::GraphFormat;
+use crate::enums::validation::BatchLimits;
+use crate::traits::apis::Api;
+use crate::utilities::general::xml_escape;
+
+/// The number of releases requested per page of an artist's discography.
+const ALBUM_PAGE: u32 = 50;
+
+/// An artist in the collaboration graph.
+///
+/// # Fields
+///
+/// * `id` - The artist ID.
+/// * `name` - The artist name, exported as the node label.
+/// * `depth` - The number of collaborations between the artist and the nearest seed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct GraphNode {
+    pub id: String,
+    #[serde(rename = "label")]
+    pub name: String,
+    pub depth: usize,
+}
+
+/// Two artists sharing `weight` tracks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    pub weight: usize,
+}
+
+/// An undirected graph of artists, connected by the tracks they share.
+///
+/// Every track counts once, however often it is seen during a crawl, and adds one to the
+/// weight of every pair of its artists. Nodes and edges are kept sorted by ID, so exports of
+/// the same crawl are identical.
+#[derive(Clone, Debug, Default)]
+pub struct CollaborationGraph {
+    nodes: BTreeMap<String, GraphNode>,
+    edges: BTreeMap<(String, String), usize>,
+    tracks: HashSet<String>,
+    /// The number of API requests made while crawling.
+    pub requests: usize,
+    /// Whether the crawl stopped because the request budget ran out.
+    pub truncated: bool,
+}
+
+#[derive(Serialize)]
+struct GraphDocument<'a> {
+    nodes: Vec<&'a GraphNode>,
+    edges: Vec<GraphEdge>,
+}
+
+impl CollaborationGraph {
+    /// Adds an artist, keeping the smallest depth it was reached at. An artist added without
+    /// a name, such as a seed given by ID, takes the name it is seen with later.
+    pub fn add_artist(&mut self, id: &str, name: &str, depth: usize) {
+        self.nodes
+            .entry(id.to_string())
+            .and_modify(|node| {
+                node.depth = node.depth.min(depth);
+                if node.name.is_empty() {
+                    node.name = name.to_string();
+                }
+            })
+            .or_insert_with(|| GraphNode { id: id.to_string(), name: name.to_string(), depth });
+    }
+
+    /// Adds the artists of a track at `depth` and connects every pair of them. Returns `false`
+    /// when the track was already counted. Local artists without an ID are ignored.
+    pub fn add_track(&mut self, track_id: &str, artists: &[SimplifiedArtist], depth: usize) -> bool {
+        if !self.tracks.insert(track_id.to_string()) {
+            return false;
+        }
+        let mut artist_ids = Vec::with_capacity(artists.len());
+        for artist in artists {
+            let Some(artist_id) = &artist.id else {
+                continue;
+            };
+            let artist_id = artist_id.id().to_string();
+            if artist_ids.contains(&artist_id) {
+                continue;
+            }
+            self.add_artist(&artist_id, &artist.name, depth);
+            artist_ids.push(artist_id);
+        }
+        for (position, first) in artist_ids.iter().enumerate() {
+            for second in &artist_ids[position + 1..] {
+                let pair = if first < second {
+                    (first.clone(), second.clone())
+                } else {
+                    (second.clone(), first.clone())
+                };
+                *self.edges.entry(pair).or_insert(0) += 1;
+            }
+        }
+        true
+    }
+
+    pub fn nodes(&self) -> Vec<&GraphNode> {
+        self.nodes.values().collect()
+    }
+
+    pub fn edges(&self) -> Vec<GraphEdge> {
+        self.edges
+            .iter()
+            .map(|((source, target), weight)| GraphEdge {
+                source: source.clone(),
+                target: target.clone(),
+                weight: *weight,
+            })
+            .collect()
+    }
+
+    /// Renders the graph in the given format.
+    pub fn export(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::GraphMl => self.to_graphml(),
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Json => self.to_json(),
+        }
+    }
+
+    /// Renders the graph as a GraphML document with `label`, `depth` and `weight` attributes.
+    pub fn to_graphml(&self) -> String {
+        let mut document = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        document.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        document.push_str("  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n");
+        document.push_str("  <key id=\"depth\" for=\"node\" attr.name=\"depth\" attr.type=\"int\"/>\n");
+        document.push_str("  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n");
+        document.push_str("  <graph id=\"collaborations\" edgedefault=\"undirected\">\n");
+        for node in self.nodes.values() {
+            document.push_str(&format!(
+                "    <node id=\"{}\">\n      <data key=\"label\">{}</data>\n      <data key=\"depth\">{}</data>\n    </node>\n",
+                xml_escape(&node.id),
+                xml_escape(&node.name),
+                node.depth
+            ));
+        }
+        for (index, edge) in self.edges().iter().enumerate() {
+            document.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n      <data key=\"weight\">{}</data>\n    </edge>\n",
+                index,
+                xml_escape(&edge.source),
+                xml_escape(&edge.target),
+                edge.weight
+            ));
+        }
+        document.push_str("  </graph>\n</graphml>\n");
+        document
+    }
+
+    /// Renders the graph as an undirected Graphviz DOT graph.
+    pub fn to_dot(&self) -> String {
+        let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut document = String::from("graph collaborations {\n");
+        for node in self.nodes.values() {
+            document.push_str(&format!(
+                "  {} [label={}, depth={}];\n",
+                quote(&node.id),
+                quote(&node.name),
+                node.depth
+            ));
+        }
+        for edge in self.edges() {
+            document.push_str(&format!(
+                "  {} -- {} [weight={}];\n",
+                quote(&edge.source),
+                quote(&edge.target),
+                edge.weight
+            ));
+        }
+        document.push_str("}\n");
+        document
+    }
+
+    /// Renders the graph as JSON with `nodes` (`id`, `label`, `depth`) and `edges` (`source`,
+    /// `target`, `weight`), the layout Gephi's JSON importer reads.
+    pub fn to_json(&self) -> String {
+        let document = GraphDocument { nodes: self.nodes(), edges: self.edges() };
+        serde_json::to_string_pretty(&document).expect("graph serializes to JSON")
+    }
+}
+
+/// Crawls the discographies of artists, breadth first, into a [`CollaborationGraph`].
+///
+/// Seeds are at depth 0. The releases of every artist closer to a seed than `max_depth` are
+/// crawled, including releases the artist only appears on; the collaborators found on them
+/// join the graph one level deeper. Only the first page of tracks of each release is read.
+/// Every request counts against `budget`, and the crawl stops once it is spent.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::collaboration_graph::CollaborationCrawler;
+/// use spotify_assistant_core::enums::graph_format::GraphFormat;
+///
+/// async fn main() {
+///     let crawler = CollaborationCrawler::new(1, 200).await;
+///     let graph = crawler.crawl(crawler.followed_seeds().await).await;
+///     std::fs::write("collaborations.graphml", graph.export(GraphFormat::GraphMl)).unwrap();
+/// }
+/// ```
+pub struct CollaborationCrawler {
+    client: AuthCodeSpotify,
+    max_depth: usize,
+    budget: usize,
+}
+
+impl Api for CollaborationCrawler {
+    fn select_scopes() -> HashSet<String> {
+        scopes!("user-follow-read")
+    }
+}
+
+impl CollaborationCrawler {
+    pub async fn new(max_depth: usize, budget: usize) -> Self {
+        CollaborationCrawler {
+            client: Self::set_up_client(false, Some(Self::select_scopes())).await,
+            max_depth,
+            budget,
+        }
+    }
+
+    /// The followed artists as seeds.
+    ///
+    /// # Errors
+    /// Returns the error of listing the followed artists.
+    pub async fn followed_seeds(&self) -> Result<Vec<(ArtistId<'static>, String)>, ClientError> {
+        let mut seeds = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let page = self
+                .client
+                .current_user_followed_artists(after.as_deref(), Some(BatchLimits::Artists.get_limit() as u32))
+                .await?;
+            seeds.extend(page.items.iter().map(|artist| (artist.id.clone(), artist.name.clone())));
+            match (page.next, page.cursors.and_then(|cursors| cursors.after)) {
+                (Some(_), Some(cursor)) => after = Some(cursor),
+                _ => break,
+            }
+        }
+        Ok(seeds)
+    }
+
+    /// Crawls from the seeds until `max_depth` is reached or the budget is spent.
+    pub async fn crawl(&self, seeds: Vec<(ArtistId<'static>, String)>) -> CollaborationGraph {
+        let span = tracing::span!(Level::INFO, "CollaborationCrawler.crawl");
+        let _enter = span.enter();
+
+        let mut graph = CollaborationGraph::default();
+        let mut queued = HashSet::new();
+        let mut queue = VecDeque::new();
+        for (artist_id, name) in seeds {
+            if queued.insert(artist_id.id().to_string()) {
+                graph.add_artist(artist_id.id(), &name, 0);
+                queue.push_back((artist_id, 0));
+            }
+        }
+
+        while let Some((artist_id, depth)) = queue.pop_front() {
+            if depth >= self.max_depth {
+                continue;
+            }
+            let Some(tracks) = self.artist_tracks(&artist_id, &mut graph).await else {
+                event!(Level::WARN, "Request budget of {} spent, stopping the crawl", self.budget);
+                graph.truncated = true;
+                break;
+            };
+            for track in tracks {
+                let Some(track_id) = &track.id else {
+                    continue;
+                };
+                if !graph.add_track(track_id.id(), &track.artists, depth + 1) {
+                    continue;
+                }
+                for collaborator in track.artists.iter().filter_map(|artist| artist.id.clone()) {
+                    if queued.insert(collaborator.id().to_string()) {
+                        queue.push_back((collaborator, depth + 1));
+                    }
+                }
+            }
+        }
+        event!(
+            Level::INFO,
+            "{} artists and {} collaborations found with {} requests",
+            graph.nodes.len(),
+            graph.edges.len(),
+            graph.requests
+        );
+        graph
+    }
+
+    /// The tracks featuring the artist on their releases, or `None` once the budget is spent.
+    /// Failed requests are logged and skipped.
+    async fn artist_tracks(&self, artist_id: &ArtistId<'static>, graph: &mut CollaborationGraph) -> Option<Vec<SimplifiedTrack>> {
+        let mut album_ids = Vec::new();
+        let mut offset = 0;
+        loop {
+            if graph.requests >= self.budget {
+                return None;
+            }
+            graph.requests += 1;
+            let groups = [AlbumType::Album, AlbumType::Single, AlbumType::AppearsOn];
+            let page = match self
+                .client
+                .artist_albums_manual(artist_id.clone(), groups, Some(Self::market()), Some(ALBUM_PAGE), Some(offset))
+                .await
+            {
+                Ok(page) => page,
+                Err(err) => {
+                    event!(Level::ERROR, "Could not list the releases of {}: {:?}", artist_id.id(), err);
+                    break;
+                }
+            };
+            album_ids.extend(page.items.into_iter().filter_map(|album| album.id));
+            if page.next.is_none() {
+                break;
+            }
+            offset += ALBUM_PAGE;
+        }
+
+        let album_ids = Self::clean_duplicate_id_vector(album_ids);
+        let mut tracks = Vec::new();
+        for chunk in album_ids.chunks(BatchLimits::Albums.get_limit()) {
+            if graph.requests >= self.budget {
+                return None;
+            }
+            graph.requests += 1;
+            let albums = match self
+                .client
+                .albums(chunk.iter().cloned(), Some(Self::market()))
+                .await
+            {
+                Ok(albums) => albums,
+                Err(err) => {
+                    event!(Level::ERROR, "Could not load a batch of releases: {:?}", err);
+                    continue;
+                }
+            };
+            tracks.extend(albums.into_iter().flat_map(|album| album.tracks.items).filter(|track| {
+                track
+                    .artists
+                    .iter()
+                    .any(|artist| artist.id.as_ref().is_some_and(|id| id.id() == artist_id.id()))
+            }));
+        }
+        Some(tracks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    #[test]
+    fn weights_edges_by_shared_tracks_and_exports_them() {
+        let (a, b, c) = ("A".repeat(22), "B".repeat(22), "C".repeat(22));
+        let artist = |id: &str, name: &str| OfflineObjects::artist_simple(id, name);
+
+        let mut graph = CollaborationGraph::default();
+        graph.add_artist(&a, "", 0);
+        assert!(graph.add_track("t1", &[artist(&a, "Alpha"), artist(&b, "Beta & Co")], 1));
+        assert!(graph.add_track("t2", &[artist(&b, "Beta & Co"), artist(&a, "Alpha"), artist(&c, "Gamma")], 1));
+        assert!(!graph.add_track("t1", &[artist(&a, "Alpha"), artist(&b, "Beta & Co")], 1));
+
+        assert_eq!(graph.nodes().iter().map(|node| node.depth).collect::<Vec<usize>>(), vec![0, 1, 1]);
+        assert_eq!(graph.nodes()[0].name, "Alpha");
+        assert_eq!(
+            graph.edges(),
+            vec![
+                GraphEdge { source: a.clone(), target: b.clone(), weight: 2 },
+                GraphEdge { source: a.clone(), target: c.clone(), weight: 1 },
+                GraphEdge { source: b.clone(), target: c.clone(), weight: 1 },
+            ]
+        );
+
+        let graphml = graph.export(GraphFormat::GraphMl);
+        assert!(graphml.contains("<data key=\"label\">Beta &amp; Co</data>"));
+        assert!(graphml.contains(&format!("<edge id=\"e0\" source=\"{a}\" target=\"{b}\">")));
+        let dot = graph.export(GraphFormat::Dot);
+        assert!(dot.contains(&format!("\"{a}\" -- \"{b}\" [weight=2];")));
+        let json = serde_json::from_str::<serde_json::Value>(&graph.export(GraphFormat::Json)).unwrap();
+        assert_eq!(json["nodes"][1]["label"], "Beta & Co");
+        assert_eq!(json["edges"][0]["weight"], 2);
+    }
+}
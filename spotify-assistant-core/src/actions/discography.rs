@@ -0,0 +1,334 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use rspotify::model::{AlbumId, ArtistId, FullAlbum, FullTrack, PlayableItem, SimplifiedAlbum, SimplifiedTrack, TrackId};
+use rspotify::prelude::{BaseClient, Id};
+use rspotify::{AuthCodeSpotify, ClientError, scopes};
+use serde::Serialize;
+use tracing::{Level, event};
+
+use crate::actions::exploration::artist::ArtistXplorer;
+use crate::actions::exploration::playlist::PlaylistXplr;
+use crate::actions::liked_songs::UserLibrary;
+use crate::actions::playlists::user::UserPlaylists;
+use crate::actions::saved_albums::SavedAlbums;
+use crate::enums::validation::BatchLimits;
+use crate::models::full_track_fingerprint::FullTrackFingerprint;
+use crate::paginator::PaginatorRunner;
+use crate::traits::apis::Api;
+use crate::utilities::general::normalize_for_matching;
+
+/// How much of a release is in the library.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseCoverage {
+    Full,
+    Partial,
+    Missing,
+}
+
+impl fmt::Display for ReleaseCoverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReleaseCoverage::Full => write!(f, "full"),
+            ReleaseCoverage::Partial => write!(f, "partial"),
+            ReleaseCoverage::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// The tracks the user engaged with: liked songs, tracks of their own playlists and saved
+/// albums.
+///
+/// A release track counts as in the library when its ID is known, when its album is saved,
+/// or when a track with the same base title (see [`FullTrackFingerprint::base_title`]) by the
+/// same artist is known, so a single also counts when only its album version was liked.
+#[derive(Clone, Debug, Default)]
+pub struct LibraryIndex {
+    track_ids: HashSet<String>,
+    album_ids: HashSet<String>,
+    titles: HashSet<(String, String)>,
+}
+
+impl LibraryIndex {
+    /// Adds a liked or playlisted track.
+    pub fn add_track(&mut self, track: &FullTrack) {
+        if let Some(track_id) = &track.id {
+            self.track_ids.insert(track_id.id().to_string());
+        }
+        let title = Self::title_key(&track.name);
+        for artist in &track.artists {
+            if let Some(artist_id) = &artist.id {
+                self.titles.insert((artist_id.id().to_string(), title.clone()));
+            }
+        }
+    }
+
+    /// Adds a saved album, whose tracks all count as in the library.
+    pub fn add_saved_album(&mut self, album_id: &str) {
+        self.album_ids.insert(album_id.to_string());
+    }
+
+    /// Whether the track of the artist's release is in the library.
+    pub fn contains(&self, artist_id: &str, album_id: &str, track: &SimplifiedTrack) -> bool {
+        self.album_ids.contains(album_id)
+            || track
+                .id
+                .as_ref()
+                .is_some_and(|track_id| self.track_ids.contains(track_id.id()))
+            || self
+                .titles
+                .contains(&(artist_id.to_string(), Self::title_key(&track.name)))
+    }
+
+    fn title_key(name: &str) -> String {
+        normalize_for_matching(&FullTrackFingerprint::base_title(name))
+    }
+}
+
+/// How much of one release of an artist is in the library.
+///
+/// # Fields
+///
+/// * `album_id` - The ID of the release.
+/// * `name` - The name of the release.
+/// * `album_type` - `album`, `single` or `compilation`.
+/// * `release_date` - The release date as reported by Spotify.
+/// * `in_library` - The number of the release's tracks in the library.
+/// * `total` - The number of tracks on the release.
+/// * `coverage` - Whether all, some or none of the tracks are in the library.
+/// * `missing_track_ids` - The IDs of the tracks not in the library, in release order.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ReleaseGap {
+    pub album_id: String,
+    pub name: String,
+    pub album_type: String,
+    pub release_date: String,
+    pub in_library: usize,
+    pub total: usize,
+    pub coverage: ReleaseCoverage,
+    pub missing_track_ids: Vec<String>,
+}
+
+impl ReleaseGap {
+    /// Evaluates a release of the artist against the library.
+    pub fn evaluate(artist_id: &str, album: &FullAlbum, tracks: &[SimplifiedTrack], index: &LibraryIndex) -> Self {
+        let album_id = album.id.id();
+        let missing_track_ids = tracks
+            .iter()
+            .filter(|track| !index.contains(artist_id, album_id, track))
+            .filter_map(|track| track.id.as_ref().map(|track_id| track_id.id().to_string()))
+            .collect::<Vec<String>>();
+        let in_library = tracks.len().saturating_sub(missing_track_ids.len());
+        let coverage = if in_library == 0 {
+            ReleaseCoverage::Missing
+        } else if missing_track_ids.is_empty() {
+            ReleaseCoverage::Full
+        } else {
+            ReleaseCoverage::Partial
+        };
+        ReleaseGap {
+            album_id: album_id.to_string(),
+            name: album.name.clone(),
+            album_type: format!("{:?}", album.album_type).to_lowercase(),
+            release_date: album.release_date.clone(),
+            in_library,
+            total: tracks.len(),
+            coverage,
+            missing_track_ids,
+        }
+    }
+}
+
+/// The releases of an artist with how much of each is in the library, newest first.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ArtistGaps {
+    pub artist_id: String,
+    pub artist: String,
+    pub releases: Vec<ReleaseGap>,
+}
+
+impl ArtistGaps {
+    /// The number of releases with the given coverage.
+    pub fn count(&self, coverage: ReleaseCoverage) -> usize {
+        self.releases
+            .iter()
+            .filter(|release| release.coverage == coverage)
+            .count()
+    }
+}
+
+/// Checks which albums and singles of artists are fully, partially or not at all in the
+/// library.
+///
+/// Releases are listed with [`ArtistXplorer`]; releases the artist only appears on are left
+/// out.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::discography::DiscographyChecker;
+///
+/// async fn main() {
+///     let checker = DiscographyChecker::new(true).await;
+///     let gaps = checker.gaps(artist_id).await.unwrap();
+///     for release in &gaps.releases {
+///         println!("{}: {}/{}", release.name, release.in_library, release.total);
+///     }
+/// }
+/// ```
+pub struct DiscographyChecker {
+    client: AuthCodeSpotify,
+    index: LibraryIndex,
+}
+
+impl Api for DiscographyChecker {
+    fn select_scopes() -> HashSet<String> {
+        scopes!(
+            "user-library-read",
+            "playlist-read-private",
+            "playlist-read-collaborative"
+        )
+    }
+}
+
+impl DiscographyChecker {
+    /// Builds the library index from the liked songs and the saved albums and, when
+    /// `include_playlists` is set, from the tracks of the user's own playlists.
+    pub async fn new(include_playlists: bool) -> Self {
+        let span = tracing::span!(Level::INFO, "DiscographyChecker.new");
+        let _enter = span.enter();
+
+        let mut index = LibraryIndex::default();
+        for saved_track in UserLibrary::new().await.tracks() {
+            index.add_track(&saved_track.track);
+        }
+        for saved_album in SavedAlbums::new().await.albums() {
+            index.add_saved_album(saved_album.album.id.id());
+        }
+        if include_playlists {
+            for playlist in UserPlaylists::new().await.get_owned_playlists().await {
+                let (_, items) = PlaylistXplr::with_items(playlist.id, false).await;
+                for item in items {
+                    if let Some(PlayableItem::Track(track)) = item.track {
+                        index.add_track(&track);
+                    }
+                }
+            }
+        }
+        DiscographyChecker {
+            client: Self::set_up_client(false, Some(Self::select_scopes())).await,
+            index,
+        }
+    }
+
+    /// Whether a release of the artist is one of their own albums, singles or compilations.
+    pub fn is_own_release(album: &SimplifiedAlbum) -> bool {
+        album.album_group.as_deref() != Some("appears_on")
+    }
+
+    /// Reports the releases of the artist.
+    ///
+    /// # Errors
+    /// Returns the error of loading the artist.
+    pub async fn gaps(&self, artist_id: ArtistId<'static>) -> Result<ArtistGaps, ClientError> {
+        let span = tracing::span!(Level::INFO, "DiscographyChecker.gaps");
+        let _enter = span.enter();
+
+        let xplorer = ArtistXplorer::new(artist_id.clone()).await?;
+        let album_ids = xplorer
+            .albums
+            .iter()
+            .filter(|album| Self::is_own_release(album))
+            .filter_map(|album| album.id.clone())
+            .collect::<Vec<AlbumId<'static>>>();
+        let album_ids = Self::clean_duplicate_id_vector(album_ids);
+        let mut releases = Vec::with_capacity(album_ids.len());
+        for chunk in album_ids.chunks(BatchLimits::Albums.get_limit()) {
+            let albums = match self.client.albums(chunk.iter().cloned(), Some(Self::market())).await {
+                Ok(albums) => albums,
+                Err(err) => {
+                    event!(Level::ERROR, "Could not load a batch of releases: {:?}", err);
+                    continue;
+                }
+            };
+            for album in &albums {
+                let tracks = self.album_tracks(album).await;
+                releases.push(ReleaseGap::evaluate(artist_id.id(), album, &tracks, &self.index));
+            }
+        }
+        releases.sort_by(|a, b| b.release_date.cmp(&a.release_date).then_with(|| a.name.cmp(&b.name)));
+        event!(Level::INFO, "{} releases checked for {}", releases.len(), xplorer.artist.name);
+        Ok(ArtistGaps {
+            artist_id: artist_id.id().to_string(),
+            artist: xplorer.artist.name,
+            releases,
+        })
+    }
+
+    /// Loads the tracks missing from the library, in report order, for a catch-up playlist.
+    pub async fn missing_tracks(&self, gaps: &[ArtistGaps]) -> Vec<FullTrack> {
+        let span = tracing::span!(Level::INFO, "DiscographyChecker.missing_tracks");
+        let _enter = span.enter();
+
+        let track_ids = gaps
+            .iter()
+            .flat_map(|artist| artist.releases.iter())
+            .flat_map(|release| release.missing_track_ids.iter())
+            .filter_map(|id| TrackId::from_id(id.clone()).ok())
+            .collect::<Vec<TrackId<'static>>>();
+        let track_ids = Self::clean_duplicate_id_vector(track_ids);
+        let mut tracks = Vec::with_capacity(track_ids.len());
+        for chunk in track_ids.chunks(BatchLimits::Tracks.get_limit()) {
+            match self.client.tracks(chunk.iter().cloned(), Some(Self::market())).await {
+                Ok(fetched) => tracks.extend(fetched),
+                Err(err) => event!(Level::ERROR, "Could not load a batch of tracks: {:?}", err),
+            }
+        }
+        tracks
+    }
+
+    /// The tracks of the release, loading the tracks beyond the first page.
+    async fn album_tracks(&self, album: &FullAlbum) -> Vec<SimplifiedTrack> {
+        if album.tracks.next.is_none() {
+            return album.tracks.items.clone();
+        }
+        let paginator = PaginatorRunner::new(self.client.album_track(album.id.clone(), Some(Self::market())), ());
+        paginator.run().await.unwrap_or_else(|err| {
+            event!(Level::ERROR, "Could not load the tracks of {}: {:?}", album.name, err);
+            album.tracks.items.clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    #[test]
+    fn classifies_releases_by_library_coverage() {
+        let artist_id = "A".repeat(22);
+        let (album_id, album) = OfflineObjects::sample_full_album();
+        let tracks = vec![
+            OfflineObjects::track_simplified(&"T".repeat(22), "Opening", &artist_id, "Artist"),
+            OfflineObjects::track_simplified(&"U".repeat(22), "Closing (Album Version)", &artist_id, "Artist"),
+        ];
+
+        let index = LibraryIndex::default();
+        let gap = ReleaseGap::evaluate(&artist_id, &album, &tracks, &index);
+        assert_eq!((gap.coverage, gap.in_library, gap.total), (ReleaseCoverage::Missing, 0, 2));
+
+        let mut index = LibraryIndex::default();
+        let mut liked = OfflineObjects::full_track(&"X".repeat(22), "Closing - Single", "Artist", "Single", None, 1);
+        liked.artists[0].id = ArtistId::from_id(artist_id.clone()).ok();
+        index.add_track(&liked);
+        let gap = ReleaseGap::evaluate(&artist_id, &album, &tracks, &index);
+        assert_eq!(gap.coverage, ReleaseCoverage::Partial);
+        assert_eq!(gap.missing_track_ids, vec!["T".repeat(22)]);
+
+        index.add_saved_album(album_id.id());
+        let gap = ReleaseGap::evaluate(&artist_id, &album, &tracks, &index);
+        assert_eq!((gap.coverage, gap.in_library), (ReleaseCoverage::Full, 2));
+        assert_eq!(gap.coverage.to_string(), "full");
+    }
+}
@@ -8,7 +8,7 @@ use crate::traits::vector_manipulation::VecManipulation;
 use rspotify::clients::{BaseClient, OAuthClient};
 use rspotify::model::{
     AlbumId, ArtistId, FullAlbum, FullPlaylist, FullTrack, PlayableId, PlayableItem, PlaylistId,
-    SimplifiedAlbum, SimplifiedArtist, TrackId,
+    PlaylistItem, SimplifiedAlbum, SimplifiedArtist, TrackId,
 };
 use rspotify::{AuthCodeSpotify, scopes};
 use std::collections::{HashMap, HashSet};
@@ -105,6 +105,37 @@ impl PlaylistXplr {
         }
     }
 
+    /// Creates a `PlaylistXplr` from a single fetch of the playlist items and returns the items
+    /// alongside it.
+    ///
+    /// `tracks` is derived from the items, so callers that also need positions (see
+    /// [`PlaylistXplr::positions_from_items`]) do not page through the playlist twice.
+    ///
+    /// # Example
+    /// ```no_run,ignore
+    /// let (xplr, items) = PlaylistXplr::with_items(playlist_id, false).await;
+    /// let positioned = PlaylistXplr::positions_from_items(items);
+    /// ```
+    pub async fn with_items(playlist_id: PlaylistId<'static>, drop_duplicates: bool) -> (Self, Vec<PlaylistItem>) {
+        let _pl_xplr_span = debug_span!("pl-xplr-items").entered();
+
+        let client = Self::set_up_client(false, Some(Self::select_scopes())).await;
+        let full_playlist = Self::instantiate_playlist(&client, playlist_id.clone()).await;
+        let mut xplr = PlaylistXplr {
+            client,
+            playlist_id,
+            full_playlist,
+            tracks: Vec::new(),
+            drop_duplicates,
+        };
+        let items = xplr.playlist_items().await;
+        xplr.tracks = Self::positions_from_items(items.clone())
+            .into_iter()
+            .map(|(_, track)| track)
+            .collect();
+        (xplr, items)
+    }
+
     /// Fetches the tracks of a specified Spotify playlist and returns them as a vector of `FullTrack`.
     ///
     /// This asynchronous function leverages the Spotify API client to retrieve items from a playlist, filters out
@@ -176,6 +207,59 @@ impl PlaylistXplr {
         }
     }
 
+    /// Fetches every item of the playlist, including episodes and unavailable entries.
+    ///
+    /// `tracks` only keeps playable tracks, which shifts the index of everything after a skipped
+    /// item. The items returned here keep their original order, so the index of each element is
+    /// its position in the playlist as understood by Spotify's position-based endpoints.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<PlaylistItem>` in playlist order. An empty vector is returned (and the error is
+    /// logged) if the items could not be retrieved.
+    pub async fn playlist_items(&self) -> Vec<PlaylistItem> {
+        let _pl_items_span = debug_span!("pl-items").entered();
+        let playlist_items =
+            self.client
+                .playlist_items(self.playlist_id.clone(), None, Some(Self::market()));
+        PaginatorRunner::new(playlist_items, ())
+            .run()
+            .await
+            .unwrap_or_else(|err| {
+                error!("Could not retrieve playlist items: {:?}", err);
+                Vec::new()
+            })
+    }
+
+    /// Pairs each track of the playlist with its zero-based position in the playlist.
+    ///
+    /// Positions are counted over every playlist item (see [`PlaylistXplr::playlist_items`]),
+    /// so they remain valid for position-based removals and reorders even when the playlist
+    /// contains episodes or unavailable items.
+    ///
+    /// # Example
+    /// ```no_run,ignore
+    /// let xplr = PlaylistXplr::new(playlist_id, false).await;
+    /// for (position, track) in xplr.positioned_tracks().await {
+    ///     println!("{position}: {}", track.name);
+    /// }
+    /// ```
+    pub async fn positioned_tracks(&self) -> Vec<(usize, FullTrack)> {
+        Self::positions_from_items(self.playlist_items().await)
+    }
+
+    /// Pairs each track of already fetched playlist items with its zero-based position.
+    pub fn positions_from_items(items: Vec<PlaylistItem>) -> Vec<(usize, FullTrack)> {
+        items
+            .into_iter()
+            .enumerate()
+            .filter_map(|(position, item)| match item.track {
+                Some(PlayableItem::Track(track)) => Some((position, track)),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Retrieves and instantiates a Spotify playlist using the provided credentials and playlist ID.
     ///
     /// This asynchronous function fetches the playlist data from the Spotify API using the
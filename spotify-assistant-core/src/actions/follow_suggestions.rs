@@ -0,0 +1,273 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use rspotify::model::{ArtistId, PlayableItem, SimplifiedArtist};
+use rspotify::prelude::Id;
+use serde::Serialize;
+use tracing::{Level, event};
+
+use crate::actions::artist_follows::ArtistFollower;
+use crate::actions::exploration::playlist::PlaylistXplr;
+use crate::actions::liked_songs::UserLibrary;
+use crate::actions::playlists::user::UserPlaylists;
+use crate::actions::recently_played::UserListeningHistory;
+use crate::actions::user::UserData;
+use crate::errors::configuration::ConfigurationError;
+use crate::models::blacklist::Blacklist;
+use crate::models::filtering::ArtistLite;
+
+/// The number of days after which an appearance counts half as much.
+pub const RECENCY_HALF_LIFE_DAYS: f64 = 180.0;
+
+/// Where an artist appeared, with the weight of a single appearance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AppearanceSource {
+    LikedSong,
+    Playlist,
+    Play,
+}
+
+impl AppearanceSource {
+    /// The weight of one appearance before the recency decay. A play counts most, as it is
+    /// the strongest sign of current interest.
+    pub fn weight(&self) -> f64 {
+        match self {
+            AppearanceSource::LikedSong => 1.0,
+            AppearanceSource::Playlist => 0.5,
+            AppearanceSource::Play => 2.0,
+        }
+    }
+}
+
+/// An artist the user does not follow yet, with how often and how recently they appeared.
+///
+/// # Fields
+///
+/// * `id` - The artist ID.
+/// * `name` - The artist name.
+/// * `liked_songs` - The number of liked songs featuring the artist.
+/// * `playlist_tracks` - The number of tracks of owned playlists featuring the artist.
+/// * `plays` - The number of recent plays featuring the artist.
+/// * `last_seen` - The most recent time a track of the artist was liked, added or played.
+/// * `score` - The recency-weighted sum of the appearances.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct FollowSuggestion {
+    pub id: String,
+    pub name: String,
+    pub liked_songs: usize,
+    pub playlist_tracks: usize,
+    pub plays: usize,
+    pub last_seen: Option<DateTime<Utc>>,
+    pub score: f64,
+}
+
+impl FollowSuggestion {
+    /// A one-line description of where the artist appeared.
+    pub fn describe(&self) -> String {
+        format!(
+            "{} ({:.1}: {} liked, {} in playlists, {} plays)",
+            self.name, self.score, self.liked_songs, self.playlist_tracks, self.plays
+        )
+    }
+}
+
+/// Counts artist appearances and ranks the artists worth following.
+///
+/// Every appearance adds its [`AppearanceSource::weight`], halved for every
+/// [`RECENCY_HALF_LIFE_DAYS`] since it happened. Appearances without a date count in full.
+#[derive(Clone, Debug)]
+pub struct ArtistTally {
+    now: DateTime<Utc>,
+    artists: HashMap<String, FollowSuggestion>,
+}
+
+impl ArtistTally {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        ArtistTally { now, artists: HashMap::new() }
+    }
+
+    /// Records one appearance of each of the artists. Local artists without an ID are ignored.
+    pub fn record(&mut self, artists: &[SimplifiedArtist], source: AppearanceSource, at: Option<DateTime<Utc>>) {
+        let age_days = at
+            .map(|at| (self.now - at).num_seconds().max(0) as f64 / 86_400.0)
+            .unwrap_or(0.0);
+        let weight = source.weight() * 0.5_f64.powf(age_days / RECENCY_HALF_LIFE_DAYS);
+        for artist in artists {
+            let Some(artist_id) = &artist.id else {
+                continue;
+            };
+            let entry = self
+                .artists
+                .entry(artist_id.id().to_string())
+                .or_insert_with(|| FollowSuggestion {
+                    id: artist_id.id().to_string(),
+                    name: artist.name.clone(),
+                    ..FollowSuggestion::default()
+                });
+            match source {
+                AppearanceSource::LikedSong => entry.liked_songs += 1,
+                AppearanceSource::Playlist => entry.playlist_tracks += 1,
+                AppearanceSource::Play => entry.plays += 1,
+            }
+            entry.score += weight;
+            if at > entry.last_seen {
+                entry.last_seen = at;
+            }
+        }
+    }
+
+    /// The artists that are neither followed nor excluded, highest score first, ties by name.
+    ///
+    /// # Arguments
+    /// * `followed` - The IDs of the followed artists.
+    /// * `excluded` - The IDs of other artists to leave out, such as the blacklist.
+    /// * `limit` - The maximum number of suggestions.
+    pub fn rank(self, followed: &HashSet<String>, excluded: &HashSet<String>, limit: usize) -> Vec<FollowSuggestion> {
+        let mut ranked = self
+            .artists
+            .into_values()
+            .filter(|artist| !followed.contains(&artist.id) && !excluded.contains(&artist.id))
+            .collect::<Vec<FollowSuggestion>>();
+        ranked.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        ranked.truncate(limit);
+        ranked
+    }
+}
+
+/// Suggests artists to follow from the liked songs, the owned playlists and the recently
+/// played tracks.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::follow_suggestions::FollowSuggester;
+///
+/// async fn main() {
+///     for suggestion in FollowSuggester::new().suggest(25).await.unwrap() {
+///         println!("{}", suggestion.describe());
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FollowSuggester {
+    pub liked_songs: bool,
+    pub playlists: bool,
+    pub plays: bool,
+}
+
+impl Default for FollowSuggester {
+    fn default() -> Self {
+        FollowSuggester { liked_songs: true, playlists: true, plays: true }
+    }
+}
+
+impl FollowSuggester {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the appearances from the enabled sources and ranks the artists that are
+    /// neither followed nor blacklisted.
+    ///
+    /// # Errors
+    /// Returns the error of reading a blacklist file that exists but is invalid.
+    pub async fn suggest(&self, limit: usize) -> Result<Vec<FollowSuggestion>, ConfigurationError> {
+        let span = tracing::span!(Level::INFO, "FollowSuggester.suggest");
+        let _enter = span.enter();
+
+        let mut tally = ArtistTally::new(Utc::now());
+        if self.liked_songs {
+            for saved_track in UserLibrary::new().await.tracks() {
+                tally.record(&saved_track.track.artists, AppearanceSource::LikedSong, Some(saved_track.added_at));
+            }
+        }
+        if self.playlists {
+            for playlist in UserPlaylists::new().await.get_owned_playlists().await {
+                let (_, items) = PlaylistXplr::with_items(playlist.id, false).await;
+                event!(Level::DEBUG, "{}: {} items", playlist.name, items.len());
+                for item in items {
+                    if let Some(PlayableItem::Track(track)) = item.track {
+                        tally.record(&track.artists, AppearanceSource::Playlist, item.added_at);
+                    }
+                }
+            }
+        }
+        if self.plays {
+            for play in UserListeningHistory::new().await.tracks() {
+                tally.record(&play.track.artists, AppearanceSource::Play, Some(play.played_at));
+            }
+        }
+
+        let followed = UserData::new()
+            .await
+            .artists()
+            .await
+            .into_iter()
+            .map(|artist| artist.id.id().to_string())
+            .collect::<HashSet<String>>();
+        let blacklisted = match Blacklist::load() {
+            Ok(blacklist) => blacklist.artists().iter().map(|artist| artist.id()).collect(),
+            Err(ConfigurationError::FileNotFound(path)) => {
+                event!(Level::DEBUG, "No blacklist to exclude: {}", path);
+                HashSet::new()
+            }
+            Err(err) => return Err(err),
+        };
+        Ok(tally.rank(&followed, &blacklisted, limit))
+    }
+
+    /// Loads the suggested artists with their popularity and follower counts, keeping the
+    /// ranking order.
+    pub async fn artists(follower: &ArtistFollower, suggestions: &[FollowSuggestion]) -> Vec<ArtistLite> {
+        let artist_ids = suggestions
+            .iter()
+            .filter_map(|suggestion| ArtistId::from_id(suggestion.id.clone()).ok())
+            .collect::<Vec<ArtistId<'static>>>();
+        let mut artists = follower.lookup(artist_ids).await;
+        let positions = suggestions
+            .iter()
+            .enumerate()
+            .map(|(position, suggestion)| (suggestion.id.as_str(), position))
+            .collect::<HashMap<&str, usize>>();
+        artists.sort_by_key(|artist| positions.get(artist.id()).copied().unwrap_or(usize::MAX));
+        artists
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+    use chrono::Duration;
+
+    #[test]
+    fn ranks_unfollowed_artists_by_recency_weighted_appearances() {
+        let now = Utc::now();
+        let artists_of = |artist_name: &str| OfflineObjects::full_track("t", "Song", artist_name, "Album", None, 1).artists;
+        let id_of = |artist_name: &str| artists_of(artist_name)[0].id.as_ref().unwrap().id().to_string();
+
+        let mut tally = ArtistTally::new(now);
+        tally.record(&artists_of("Old Favourite"), AppearanceSource::LikedSong, Some(now - Duration::days(720)));
+        tally.record(&artists_of("Old Favourite"), AppearanceSource::LikedSong, Some(now - Duration::days(720)));
+        tally.record(&artists_of("New Find"), AppearanceSource::Play, Some(now));
+        tally.record(&artists_of("New Find"), AppearanceSource::Playlist, None);
+        tally.record(&artists_of("Followed"), AppearanceSource::Play, Some(now));
+        tally.record(&artists_of("Blacklisted"), AppearanceSource::Play, Some(now));
+
+        let followed = HashSet::from([id_of("Followed")]);
+        let excluded = HashSet::from([id_of("Blacklisted")]);
+        let ranked = tally.rank(&followed, &excluded, 10);
+        assert_eq!(
+            ranked.iter().map(|artist| artist.name.as_str()).collect::<Vec<&str>>(),
+            vec!["New Find", "Old Favourite"]
+        );
+        assert_eq!((ranked[0].plays, ranked[0].playlist_tracks), (1, 1));
+        assert!((ranked[0].score - 2.5).abs() < 1e-6);
+        assert_eq!(ranked[1].liked_songs, 2);
+        assert!((ranked[1].score - 2.0 * 0.0625).abs() < 1e-6);
+        assert_eq!(ranked[1].last_seen, Some(now - Duration::days(720)));
+    }
+}
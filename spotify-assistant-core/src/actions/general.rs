@@ -32,6 +32,11 @@ pub trait FullProfilesClient {
         artist_id: ArtistId<'a>,
     ) -> Pin<Box<dyn Future<Output = Result<FullArtist, ClientError>> + Send + 'a>>;
 
+    fn artists<'a>(
+        &'a self,
+        artist_ids: Vec<ArtistId<'a>>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<FullArtist>, ClientError>> + Send + 'a>>;
+
     fn album<'a>(
         &'a self,
         album_id: AlbumId<'a>,
@@ -60,6 +65,13 @@ impl FullProfilesClient for AuthCodeSpotify {
         Box::pin(BaseClient::artist(self, artist_id))
     }
 
+    fn artists<'a>(
+        &'a self,
+        artist_ids: Vec<ArtistId<'a>>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<FullArtist>, ClientError>> + Send + 'a>> {
+        Box::pin(BaseClient::artists(self, artist_ids))
+    }
+
     fn album<'a>(
         &'a self,
         album_id: AlbumId<'a>,
@@ -143,6 +155,19 @@ impl<C: FullProfilesClient> FullProfiles<C> {
         })
     }
 
+    /// Fetches the full details of several artists in a single request.
+    ///
+    /// # Arguments
+    ///
+    /// - `artist_ids`: The artists to fetch, at most `BatchLimits::Artists` of them.
+    ///
+    /// # Returns
+    ///
+    /// - The artists in the order of `artist_ids`, or the `ClientError` of the request.
+    pub async fn artists(&self, artist_ids: Vec<ArtistId<'static>>) -> Result<Vec<FullArtist>, ClientError> {
+        self.client.artists(artist_ids).await
+    }
+
     /// Retrieves detailed information about a specific album by its unique identifier.
     ///
     /// This asynchronous function queries the Spotify Web API to fetch all available details
@@ -300,6 +325,7 @@ mod tests {
 
     struct MockFullProfilesClient {
         artist: Mutex<Option<Result<FullArtist, ClientError>>>,
+        artists: Mutex<Option<Result<Vec<FullArtist>, ClientError>>>,
         album: Mutex<Option<Result<FullAlbum, ClientError>>>,
         track: Mutex<Option<Result<FullTrack, ClientError>>>,
         playlist: Mutex<Option<Result<FullPlaylist, ClientError>>>,
@@ -314,6 +340,7 @@ mod tests {
         ) -> Self {
             Self {
                 artist: Mutex::new(artist),
+                artists: Mutex::new(None),
                 album: Mutex::new(album),
                 track: Mutex::new(track),
                 playlist: Mutex::new(playlist),
@@ -335,6 +362,19 @@ mod tests {
             Box::pin(async move { response })
         }
 
+        fn artists<'a>(
+            &'a self,
+            _artist_ids: Vec<ArtistId<'a>>,
+        ) -> Pin<Box<dyn Future<Output = Result<Vec<FullArtist>, ClientError>> + Send + 'a>> {
+            let response = self
+                .artists
+                .lock()
+                .unwrap()
+                .take()
+                .expect("artists response missing");
+            Box::pin(async move { response })
+        }
+
         fn album<'a>(
             &'a self,
             _album_id: AlbumId<'a>,
@@ -570,6 +610,21 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[tokio::test]
+    async fn artists_returns_mock_data() {
+        let expected = vec![sample_full_artist()];
+        let client = MockFullProfilesClient::new(None, None, None, None);
+        *client.artists.lock().unwrap() = Some(Ok(expected.clone()));
+        let profiles = FullProfiles::with_client(client);
+
+        let actual = profiles
+            .artists(vec![ArtistId::from_id("0123456789abcdef012345").unwrap()])
+            .await
+            .unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
     #[tokio::test]
     async fn album_returns_mock_data() {
         let expected = sample_full_album();
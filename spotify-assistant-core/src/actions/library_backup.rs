@@ -0,0 +1,404 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use chrono::{DateTime, Utc};
+use rspotify::model::{FullTrack, SavedTrack, TrackId};
+use rspotify::prelude::{BaseClient, Id, OAuthClient};
+use rspotify::{AuthCodeSpotify, ClientError, scopes};
+use serde::{Deserialize, Serialize};
+use tracing::{Level, event};
+
+use crate::actions::playlists::import::{ImportEntry, MatchCandidate, MatchOutcome, PlaylistImporter};
+use crate::enums::validation::BatchLimits;
+use crate::traits::apis::Api;
+
+/// The version of the backup format written by [`LibraryBackup::write`].
+pub const BACKUP_VERSION: u32 = 1;
+
+/// One liked song in a backup.
+///
+/// # Fields
+///
+/// * `track_id` - The Spotify ID of the track when it was backed up.
+/// * `name` - The name of the track.
+/// * `artists` - The names of the track's artists.
+/// * `album` - The name of the track's album.
+/// * `isrc` - The ISRC of the recording, used to find the track again when its ID is no
+///   longer available.
+/// * `added_at` - When the song was liked.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BackupEntry {
+    pub track_id: String,
+    pub name: String,
+    pub artists: Vec<String>,
+    pub album: String,
+    pub isrc: Option<String>,
+    pub added_at: DateTime<Utc>,
+}
+
+impl BackupEntry {
+    /// Describes the entry as `Artist - Title`.
+    pub fn describe(&self) -> String {
+        format!("{} - {}", self.artists.join(", "), self.name)
+    }
+
+    fn import_entry(&self) -> ImportEntry {
+        ImportEntry {
+            raw: self.describe(),
+            title: self.name.clone(),
+            artist: self.artists.join(", "),
+            album: Some(self.album.clone()),
+            isrc: self.isrc.as_ref().map(|isrc| isrc.to_uppercase()),
+            ..ImportEntry::default()
+        }
+    }
+
+    /// Builds the entry of a liked song. Local files, which have no ID, yield `None`.
+    pub fn from_saved(saved_track: &SavedTrack) -> Option<Self> {
+        let track = &saved_track.track;
+        Some(BackupEntry {
+            track_id: track.id.as_ref()?.id().to_string(),
+            name: track.name.clone(),
+            artists: track.artists.iter().map(|artist| artist.name.clone()).collect(),
+            album: track.album.name.clone(),
+            isrc: track.external_ids.get("isrc").cloned(),
+            added_at: saved_track.added_at,
+        })
+    }
+}
+
+/// A portable copy of the liked songs, written as JSON.
+///
+/// # Fields
+///
+/// * `version` - The version of the backup format.
+/// * `created_at` - When the backup was taken.
+/// * `tracks` - The liked songs, in the order of the library (newest first).
+///
+/// # Example
+/// ```no_run,ignore
+/// use std::path::Path;
+/// use spotify_assistant_core::actions::library_backup::LibraryBackup;
+/// use spotify_assistant_core::actions::liked_songs::UserLibrary;
+///
+/// async fn main() {
+///     let backup = LibraryBackup::from_saved(&UserLibrary::new().await.tracks());
+///     backup.write(Path::new("liked_songs.backup.json")).unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LibraryBackup {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub tracks: Vec<BackupEntry>,
+}
+
+impl LibraryBackup {
+    /// Builds a backup of the liked songs. Local files are left out.
+    pub fn from_saved(saved_tracks: &[SavedTrack]) -> Self {
+        LibraryBackup {
+            version: BACKUP_VERSION,
+            created_at: Utc::now(),
+            tracks: saved_tracks.iter().filter_map(BackupEntry::from_saved).collect(),
+        }
+    }
+
+    /// Writes the backup to `path`, creating the parent directories.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Reads a backup written by [`LibraryBackup::write`].
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// The songs in the order they were liked, oldest first. Restoring them in this order
+    /// recreates the order of the library.
+    pub fn chronological(&self) -> Vec<BackupEntry> {
+        let mut tracks = self.tracks.clone();
+        tracks.sort_by_key(|entry| entry.added_at);
+        tracks
+    }
+}
+
+/// How far a restore got, saved after every batch so an interrupted restore resumes where it
+/// stopped.
+///
+/// # Fields
+///
+/// * `done` - The number of chronologically ordered entries handled.
+/// * `unresolved` - The IDs of the entries that could not be restored so far.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RestoreProgress {
+    pub done: usize,
+    pub unresolved: Vec<String>,
+}
+
+impl RestoreProgress {
+    /// The progress file of a backup: `<backup>.progress.json` next to it.
+    pub fn path(backup_path: &Path) -> PathBuf {
+        let mut file_name = backup_path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".progress.json");
+        backup_path.with_file_name(file_name)
+    }
+
+    /// Loads the progress of a backup, or starts from the beginning when there is none.
+    pub fn load(backup_path: &Path) -> Self {
+        fs::read_to_string(Self::path(backup_path))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, backup_path: &Path) -> io::Result<()> {
+        fs::write(Self::path(backup_path), serde_json::to_string(self)?)
+    }
+}
+
+/// The outcome of a restore.
+///
+/// # Fields
+///
+/// * `restored` - The number of songs liked by the restore.
+/// * `already_liked` - The number of songs that were liked on the account already.
+/// * `resumed_from` - The number of entries handled by an earlier, interrupted run.
+/// * `replaced` - The entries whose ID was unavailable, with the track found by their ISRC.
+/// * `unresolved` - The entries that could not be restored.
+#[derive(Clone, Debug, Default)]
+pub struct RestoreReport {
+    pub restored: usize,
+    pub already_liked: usize,
+    pub resumed_from: usize,
+    pub replaced: Vec<(BackupEntry, FullTrack)>,
+    pub unresolved: Vec<BackupEntry>,
+}
+
+/// Likes the songs of a backup on the account of a profile.
+///
+/// Songs are liked oldest first in batches, skipping those that are liked already. Unavailable
+/// IDs are looked up by their ISRC. The progress is saved next to the backup after every batch
+/// and removed once the restore completes, so running the restore again after an interruption
+/// continues where it stopped.
+///
+/// # Example
+/// ```no_run,ignore
+/// use std::path::Path;
+/// use spotify_assistant_core::actions::library_backup::{LibraryBackup, LibraryRestore};
+///
+/// async fn main() {
+///     let path = Path::new("liked_songs.backup.json");
+///     let backup = LibraryBackup::read(path).unwrap();
+///     let report = LibraryRestore::new(Some("work")).await.restore(&backup, path).await.unwrap();
+///     println!("{} songs restored", report.restored);
+/// }
+/// ```
+pub struct LibraryRestore {
+    client: AuthCodeSpotify,
+    importer: PlaylistImporter,
+}
+
+impl Api for LibraryRestore {
+    fn select_scopes() -> HashSet<String> {
+        scopes!("user-library-read", "user-library-modify")
+    }
+}
+
+impl LibraryRestore {
+    /// Sets up the restore for a profile; `None` restores to the default account.
+    pub async fn new(profile: Option<&str>) -> Self {
+        let span = tracing::span!(Level::INFO, "LibraryRestore.new");
+        let _enter = span.enter();
+
+        LibraryRestore {
+            client: Self::set_up_profile_client(false, Some(Self::select_scopes()), profile).await,
+            importer: PlaylistImporter::new().await,
+        }
+    }
+
+    /// Restores the backup read from `backup_path`.
+    ///
+    /// # Errors
+    /// Returns the error of a failed request. The progress up to the last completed batch is
+    /// kept, so the restore can be run again.
+    pub async fn restore(&self, backup: &LibraryBackup, backup_path: &Path) -> Result<RestoreReport, ClientError> {
+        let span = tracing::span!(Level::INFO, "LibraryRestore.restore");
+        let _enter = span.enter();
+
+        let entries = backup.chronological();
+        let mut progress = RestoreProgress::load(backup_path);
+        let mut report = RestoreReport {
+            resumed_from: progress.done.min(entries.len()),
+            unresolved: entries
+                .iter()
+                .filter(|entry| progress.unresolved.contains(&entry.track_id))
+                .cloned()
+                .collect(),
+            ..RestoreReport::default()
+        };
+        if report.resumed_from > 0 {
+            event!(Level::INFO, "Resuming the restore after {} entries", report.resumed_from);
+        }
+
+        for batch in entries[report.resumed_from..].chunks(BatchLimits::ModifyCurrentUserTracks.get_limit()) {
+            let mut track_ids = Vec::with_capacity(batch.len());
+            for (entry, track) in batch.iter().zip(self.available(batch).await?) {
+                let track = match track {
+                    Some(track) => Some(track),
+                    None => self.find_by_isrc(entry).await?.inspect(|track| {
+                        report.replaced.push((entry.clone(), track.clone()));
+                    }),
+                };
+                match track.and_then(|track| track.id) {
+                    Some(track_id) if !track_ids.contains(&track_id) => track_ids.push(track_id),
+                    Some(_) => {}
+                    None => {
+                        progress.unresolved.push(entry.track_id.clone());
+                        report.unresolved.push(entry.clone());
+                    }
+                }
+            }
+
+            let liked = if track_ids.is_empty() {
+                Vec::new()
+            } else {
+                self.client
+                    .current_user_saved_tracks_contains(track_ids.iter().cloned())
+                    .await?
+            };
+            let to_like = track_ids
+                .iter()
+                .zip(liked.iter().chain(std::iter::repeat(&false)))
+                .filter(|(_, liked)| !**liked)
+                .map(|(track_id, _)| track_id.clone())
+                .collect::<Vec<TrackId<'static>>>();
+            if !to_like.is_empty() {
+                self.client.current_user_saved_tracks_add(to_like.iter().cloned()).await?;
+            }
+            report.restored += to_like.len();
+            report.already_liked += track_ids.len() - to_like.len();
+
+            progress.done += batch.len();
+            if let Err(err) = progress.save(backup_path) {
+                event!(Level::ERROR, "Could not save the restore progress: {:?}", err);
+            }
+            event!(Level::DEBUG, "Restored {}/{} entries", progress.done, entries.len());
+        }
+
+        match fs::remove_file(RestoreProgress::path(backup_path)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                event!(Level::WARN, "Could not remove the restore progress: {:?}", err);
+            }
+            _ => {}
+        }
+        event!(
+            Level::INFO,
+            "Restored {} songs, {} were liked already, {} unresolved",
+            report.restored,
+            report.already_liked,
+            report.unresolved.len()
+        );
+        Ok(report)
+    }
+
+    /// Looks up the tracks of a batch. Tracks that no longer exist or cannot be played in the
+    /// market are `None`.
+    ///
+    /// # Errors
+    /// Returns the error of the lookup, so a failed request never marks the batch unresolved.
+    async fn available(&self, batch: &[BackupEntry]) -> Result<Vec<Option<FullTrack>>, ClientError> {
+        let track_ids = batch
+            .iter()
+            .map(|entry| TrackId::from_id(entry.track_id.clone()).ok())
+            .collect::<Vec<Option<TrackId<'static>>>>();
+        let fetched = self
+            .client
+            .tracks(track_ids.iter().flatten().cloned(), Some(Self::market()))
+            .await?;
+        Ok(track_ids
+            .iter()
+            .map(|track_id| {
+                let track_id = track_id.as_ref()?;
+                fetched
+                    .iter()
+                    .find(|track| {
+                        track.id.as_ref() == Some(track_id)
+                            || track.linked_from.as_ref().is_some_and(|linked| linked.id.as_ref() == Some(track_id))
+                    })
+                    .filter(|track| track.is_playable != Some(false))
+                    .cloned()
+            })
+            .collect())
+    }
+
+    /// Finds the track of an unavailable entry by its ISRC. Only certain matches are used.
+    ///
+    /// # Errors
+    /// Returns the error of the search, so the entry is retried rather than left unresolved.
+    async fn find_by_isrc(&self, entry: &BackupEntry) -> Result<Option<FullTrack>, ClientError> {
+        let import_entry = entry.import_entry();
+        let Some(isrc) = &import_entry.isrc else {
+            return Ok(None);
+        };
+        let candidates = self
+            .importer
+            .search_isrc(isrc)
+            .await?
+            .into_iter()
+            .map(|track| MatchCandidate {
+                confidence: PlaylistImporter::score(&import_entry, &track),
+                track,
+            })
+            .collect();
+        Ok(match PlaylistImporter::decide(candidates, Some(1.0)) {
+            MatchOutcome::Matched(candidate) => Some(candidate.track),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    #[test]
+    fn backups_round_trip_and_restore_oldest_first() {
+        let backup = LibraryBackup::from_saved(&[
+            OfflineObjects::saved_track('C', "2025-03-01T00:00:00Z"),
+            OfflineObjects::saved_track('A', "2024-01-01T00:00:00Z"),
+            OfflineObjects::saved_track('B', "2024-06-01T00:00:00Z"),
+        ]);
+        assert_eq!(backup.tracks[0].isrc.as_deref(), Some("USRC1700000C"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("liked.json");
+        backup.write(&path).unwrap();
+        let read = LibraryBackup::read(&path).unwrap();
+        assert_eq!(read, backup);
+        assert_eq!(
+            read.chronological().iter().map(|entry| entry.name.as_str()).collect::<Vec<&str>>(),
+            vec!["A", "B", "C"]
+        );
+    }
+
+    #[test]
+    fn progress_is_kept_next_to_the_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("liked.json");
+        assert_eq!(RestoreProgress::path(&path), dir.path().join("liked.json.progress.json"));
+        assert_eq!(RestoreProgress::load(&path), RestoreProgress::default());
+
+        let progress = RestoreProgress {
+            done: 50,
+            unresolved: vec!["A".repeat(22)],
+        };
+        progress.save(&path).unwrap();
+        assert_eq!(RestoreProgress::load(&path), progress);
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use rspotify::model::{SavedTrack, TrackId};
+use rspotify::prelude::{Id, OAuthClient};
+use rspotify::{AuthCodeSpotify, scopes};
+use tracing::{Level, event};
+
+use crate::actions::library_backup::LibraryBackup;
+use crate::actions::library_stats::LibraryAnalyzer;
+use crate::actions::liked_songs::UserLibrary;
+use crate::enums::fs::ProjectDirectories;
+use crate::enums::prune::PruneRule;
+use crate::enums::validation::BatchLimits;
+use crate::errors::configuration::ConfigurationError;
+use crate::models::blacklist::Blacklist;
+use crate::models::full_track_fingerprint::FullTrackFingerprint;
+use crate::traits::apis::Api;
+
+/// A liked song selected by at least one prune rule.
+///
+/// # Fields
+///
+/// * `saved_track` - The liked song.
+/// * `reasons` - The rules that selected it.
+#[derive(Clone, Debug)]
+pub struct PruneCandidate {
+    pub saved_track: SavedTrack,
+    pub reasons: Vec<PruneRule>,
+}
+
+impl PruneCandidate {
+    /// Describes the candidate as `Artist - Title (reasons)`.
+    pub fn describe(&self) -> String {
+        let track = &self.saved_track.track;
+        format!(
+            "{} - {} ({})",
+            track
+                .artists
+                .iter()
+                .map(|artist| artist.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", "),
+            track.name,
+            self.reasons
+                .iter()
+                .map(PruneRule::to_string)
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+
+/// Unlikes the liked songs selected by rules: blacklisted artists, genres, release years and
+/// duplicate recordings.
+///
+/// Before anything is removed, the removed songs are written as a [`LibraryBackup`], so a
+/// prune can be undone with [`LibraryRestore`].
+///
+/// [`LibraryRestore`]: crate::actions::library_backup::LibraryRestore
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::library_prune::LibraryPruner;
+/// use spotify_assistant_core::enums::prune::PruneRule;
+///
+/// async fn main() {
+///     let pruner = LibraryPruner::new().await;
+///     let candidates = pruner.candidates(&[PruneRule::Blacklist, PruneRule::Duplicates]).await.unwrap();
+///     let backup = LibraryPruner::default_backup_path();
+///     pruner.prune(&candidates, &backup).await.unwrap();
+/// }
+/// ```
+pub struct LibraryPruner {
+    client: AuthCodeSpotify,
+}
+
+impl Api for LibraryPruner {
+    fn select_scopes() -> HashSet<String> {
+        scopes!("user-library-read", "user-library-modify")
+    }
+}
+
+impl LibraryPruner {
+    pub async fn new() -> Self {
+        let span = tracing::span!(Level::INFO, "LibraryPruner.new");
+        let _enter = span.enter();
+
+        LibraryPruner {
+            client: Self::set_up_client(false, Some(Self::select_scopes())).await,
+        }
+    }
+
+    /// Where the removed songs are backed up when no path is given:
+    /// `pruned_<timestamp>.json` in the data directory.
+    pub fn default_backup_path() -> PathBuf {
+        ProjectDirectories::Data
+            .path()
+            .join(format!("pruned_{}.json", Utc::now().format("%Y%m%d_%H%M%S")))
+    }
+
+    /// Applies the rules to the liked songs.
+    ///
+    /// # Arguments
+    /// * `saved_tracks` - The liked songs, newest first.
+    /// * `rules` - The rules to apply.
+    /// * `blacklisted` - The IDs of the blacklisted artists.
+    /// * `genres` - The genres of the lead artists keyed by artist ID.
+    ///
+    /// # Returns
+    /// The songs selected by at least one rule, in library order. Of the copies of a recording,
+    /// the one liked first is kept.
+    pub fn evaluate(
+        saved_tracks: &[SavedTrack],
+        rules: &[PruneRule],
+        blacklisted: &HashSet<String>,
+        genres: &HashMap<String, Vec<String>>,
+    ) -> Vec<PruneCandidate> {
+        let mut duplicates = HashSet::new();
+        if rules.contains(&PruneRule::Duplicates) {
+            let mut seen = HashSet::new();
+            for (index, saved_track) in saved_tracks.iter().enumerate().rev() {
+                if let Some(fingerprint) = FullTrackFingerprint::try_new(&saved_track.track)
+                    && !seen.insert(fingerprint)
+                {
+                    duplicates.insert(index);
+                }
+            }
+        }
+
+        let mut candidates = Vec::new();
+        for (index, saved_track) in saved_tracks.iter().enumerate() {
+            let track = &saved_track.track;
+            if track.id.is_none() {
+                continue;
+            }
+            let release_year = track
+                .album
+                .release_date
+                .as_deref()
+                .and_then(|date| date.get(..4))
+                .and_then(|year| year.parse::<i32>().ok());
+            let lead_genres = track
+                .artists
+                .first()
+                .and_then(|artist| artist.id.as_ref())
+                .and_then(|artist_id| genres.get(artist_id.id()));
+            let reasons = rules
+                .iter()
+                .filter(|rule| match rule {
+                    PruneRule::Blacklist => track
+                        .artists
+                        .iter()
+                        .filter_map(|artist| artist.id.as_ref())
+                        .any(|artist_id| blacklisted.contains(artist_id.id())),
+                    PruneRule::Genre(genre) => lead_genres
+                        .is_some_and(|lead_genres| {
+                            lead_genres
+                                .iter()
+                                .any(|lead_genre| lead_genre.to_lowercase().contains(genre.as_str()))
+                        }),
+                    PruneRule::Before(year) => release_year.is_some_and(|release| release < *year),
+                    PruneRule::After(year) => release_year.is_some_and(|release| release > *year),
+                    PruneRule::Duplicates => duplicates.contains(&index),
+                })
+                .cloned()
+                .collect::<Vec<PruneRule>>();
+            if !reasons.is_empty() {
+                candidates.push(PruneCandidate {
+                    saved_track: saved_track.clone(),
+                    reasons,
+                });
+            }
+        }
+        candidates
+    }
+
+    /// Synchronizes the liked songs and applies the rules to them. The blacklist is only read,
+    /// and the genres only looked up, when a rule needs them.
+    ///
+    /// # Errors
+    /// Returns the error of reading the blacklist when the `Blacklist` rule is given and the
+    /// blacklist file is missing or cannot be parsed.
+    pub async fn candidates(&self, rules: &[PruneRule]) -> Result<Vec<PruneCandidate>, ConfigurationError> {
+        let span = tracing::span!(Level::INFO, "LibraryPruner.candidates");
+        let _enter = span.enter();
+
+        let saved_tracks = UserLibrary::new().await.tracks();
+        let blacklisted = if rules.contains(&PruneRule::Blacklist) {
+            Blacklist::load()?
+                .artists()
+                .iter()
+                .map(|artist| artist.id())
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        let genres = if rules.iter().any(PruneRule::needs_genres) {
+            LibraryAnalyzer::new().await.lead_artist_genres(&saved_tracks).await
+        } else {
+            HashMap::new()
+        };
+        let candidates = Self::evaluate(&saved_tracks, rules, &blacklisted, &genres);
+        event!(Level::INFO, "{} of {} liked songs selected", candidates.len(), saved_tracks.len());
+        Ok(candidates)
+    }
+
+    /// Backs the candidates up to `backup_path`, then unlikes them in batches and synchronizes
+    /// the cached library.
+    ///
+    /// # Returns
+    /// The number of songs unliked.
+    ///
+    /// # Errors
+    /// Returns the error of the failed request. Nothing is removed when the backup cannot be
+    /// written.
+    pub async fn prune(&self, candidates: &[PruneCandidate], backup_path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+        let span = tracing::span!(Level::INFO, "LibraryPruner.prune");
+        let _enter = span.enter();
+
+        let removed = candidates
+            .iter()
+            .map(|candidate| candidate.saved_track.clone())
+            .collect::<Vec<SavedTrack>>();
+        LibraryBackup::from_saved(&removed).write(backup_path)?;
+        event!(Level::INFO, "Backed up {} songs to {:?}", removed.len(), backup_path);
+
+        let track_ids = removed
+            .iter()
+            .filter_map(|saved_track| saved_track.track.id.clone())
+            .collect::<Vec<TrackId<'static>>>();
+        for chunk in track_ids.chunks(BatchLimits::ModifyCurrentUserTracks.get_limit()) {
+            self.client
+                .current_user_saved_tracks_delete(chunk.iter().cloned())
+                .await?;
+        }
+        if let Err(err) = UserLibrary::from_cache().await.sync(false).await {
+            event!(Level::WARN, "Could not synchronize the liked songs after pruning: {:?}", err);
+        }
+        event!(Level::INFO, "Unliked {} songs", track_ids.len());
+        Ok(track_ids.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn saved(id: char, artist: &str, isrc: &str, release: &str, added_at: &str) -> SavedTrack {
+        let mut saved = OfflineObjects::saved_track(id, added_at);
+        saved.track = OfflineObjects::full_track(&id.to_string().repeat(22), "Song", artist, "Album", Some(isrc), 200_000);
+        saved.track.album.release_date = Some(release.to_string());
+        saved
+    }
+
+    fn reasons(candidates: &[PruneCandidate]) -> Vec<(String, Vec<PruneRule>)> {
+        candidates
+            .iter()
+            .map(|candidate| {
+                (
+                    candidate.saved_track.track.id.as_ref().unwrap().id()[..1].to_string(),
+                    candidate.reasons.clone(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn applies_every_rule_and_keeps_the_first_liked_copy() {
+        let library = vec![
+            saved('D', "Other", "USRC10000001", "2020-01-01", "2025-04-01T00:00:00Z"),
+            saved('C', "Banned", "USRC10000002", "1985", "2025-03-01T00:00:00Z"),
+            saved('B', "Other", "USRC10000001", "2018-05-05", "2025-02-01T00:00:00Z"),
+            saved('A', "Other", "USRC10000003", "2001", "2025-01-01T00:00:00Z"),
+        ];
+        let banned_id = library[1].track.artists[0].id.as_ref().unwrap().id().to_string();
+        let other_id = library[0].track.artists[0].id.as_ref().unwrap().id().to_string();
+        let blacklisted = HashSet::from([banned_id]);
+        let genres = HashMap::from([(other_id, vec!["Christmas Pop".to_string()])]);
+
+        let candidates = LibraryPruner::evaluate(
+            &library,
+            &[PruneRule::Blacklist, PruneRule::Before(1990), PruneRule::Duplicates],
+            &blacklisted,
+            &genres,
+        );
+        assert_eq!(
+            reasons(&candidates),
+            vec![
+                ("D".to_string(), vec![PruneRule::Duplicates]),
+                ("C".to_string(), vec![PruneRule::Blacklist, PruneRule::Before(1990)]),
+            ]
+        );
+
+        let by_genre = LibraryPruner::evaluate(&library, &[PruneRule::Genre("christmas".to_string())], &blacklisted, &genres);
+        assert_eq!(by_genre.len(), 3);
+        assert!(by_genre[0].describe().ends_with("(genre christmas)"));
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::{HashMap, HashSet};
+
+use rspotify::model::{ArtistId, SavedTrack};
+use rspotify::prelude::{BaseClient, Id};
+use rspotify::{AuthCodeSpotify, scopes};
+use serde::Serialize;
+use tracing::{Level, event};
+
+use crate::actions::liked_songs::UserLibrary;
+use crate::enums::validation::BatchLimits;
+use crate::traits::apis::Api;
+use crate::utilities::general::print_separator;
+
+/// A label with the number of liked songs it applies to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CountEntry {
+    pub label: String,
+    pub count: usize,
+}
+
+/// An album with the number of its tracks among the liked songs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AlbumCount {
+    pub album: String,
+    pub artist: String,
+    pub count: usize,
+}
+
+/// Statistics over the liked songs.
+///
+/// # Fields
+///
+/// * `total_tracks` - The number of liked songs.
+/// * `total_duration_ms` - The summed duration of the liked songs.
+/// * `adds_per_month` - The number of songs liked per month (`YYYY-MM`), oldest first.
+/// * `top_artists` - The lead artists with the most liked songs.
+/// * `top_albums` - The albums with the most liked songs.
+/// * `genres` - The genres of the lead artists, counted once per song, most common first.
+///   Empty when genres were not looked up.
+/// * `decades` - The release decades (e.g. `1990s`), oldest first.
+/// * `explicit_share` - The share of explicit songs, between 0 and 1.
+/// * `average_popularity` - The average Spotify popularity (0 to 100).
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct LibraryStats {
+    pub total_tracks: usize,
+    pub total_duration_ms: i64,
+    pub adds_per_month: Vec<CountEntry>,
+    pub top_artists: Vec<CountEntry>,
+    pub top_albums: Vec<AlbumCount>,
+    pub genres: Vec<CountEntry>,
+    pub decades: Vec<CountEntry>,
+    pub explicit_share: f64,
+    pub average_popularity: f64,
+}
+
+impl LibraryStats {
+    /// Computes the statistics without any network access.
+    ///
+    /// # Arguments
+    /// * `saved_tracks` - The liked songs.
+    /// * `genres` - The genres of the lead artists keyed by artist ID.
+    /// * `top` - The number of entries kept in the artist, album and genre rankings.
+    pub fn compute(
+        saved_tracks: &[SavedTrack],
+        genres: &HashMap<String, Vec<String>>,
+        top: usize,
+    ) -> Self {
+        let total_tracks = saved_tracks.len();
+        if total_tracks == 0 {
+            return LibraryStats::default();
+        }
+        let mut months: HashMap<String, usize> = HashMap::new();
+        let mut artists: HashMap<String, usize> = HashMap::new();
+        let mut albums: HashMap<(String, String), usize> = HashMap::new();
+        let mut genre_counts: HashMap<String, usize> = HashMap::new();
+        let mut decades: HashMap<String, usize> = HashMap::new();
+        let mut total_duration_ms = 0;
+        let mut explicit = 0;
+        let mut popularity = 0;
+        for saved_track in saved_tracks {
+            let track = &saved_track.track;
+            total_duration_ms += track.duration.num_milliseconds();
+            explicit += usize::from(track.explicit);
+            popularity += track.popularity as usize;
+            *months
+                .entry(saved_track.added_at.format("%Y-%m").to_string())
+                .or_default() += 1;
+            let lead = track.artists.first();
+            if let Some(artist) = lead {
+                *artists.entry(artist.name.clone()).or_default() += 1;
+            }
+            let album_artist = track
+                .album
+                .artists
+                .first()
+                .or(lead)
+                .map(|artist| artist.name.clone())
+                .unwrap_or_default();
+            *albums.entry((track.album.name.clone(), album_artist)).or_default() += 1;
+            let lead_genres = lead
+                .and_then(|artist| artist.id.as_ref())
+                .and_then(|artist_id| genres.get(artist_id.id()));
+            for genre in lead_genres.into_iter().flatten().collect::<HashSet<&String>>() {
+                *genre_counts.entry(genre.clone()).or_default() += 1;
+            }
+            let year = track
+                .album
+                .release_date
+                .as_deref()
+                .and_then(|date| date.get(..4))
+                .and_then(|year| year.parse::<u32>().ok());
+            if let Some(year) = year {
+                *decades.entry(format!("{}s", year / 10 * 10)).or_default() += 1;
+            }
+        }
+
+        LibraryStats {
+            total_tracks,
+            total_duration_ms,
+            adds_per_month: Self::chronological(months),
+            top_artists: Self::ranked(artists, top),
+            top_albums: {
+                let mut ranked = albums
+                    .into_iter()
+                    .map(|((album, artist), count)| AlbumCount { album, artist, count })
+                    .collect::<Vec<AlbumCount>>();
+                ranked.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.album.cmp(&b.album)));
+                ranked.truncate(top);
+                ranked
+            },
+            genres: Self::ranked(genre_counts, top),
+            decades: Self::chronological(decades),
+            explicit_share: explicit as f64 / total_tracks as f64,
+            average_popularity: popularity as f64 / total_tracks as f64,
+        }
+    }
+
+    /// The entries sorted by count, most common first, ties by label.
+    fn ranked(counts: HashMap<String, usize>, top: usize) -> Vec<CountEntry> {
+        let mut ranked = counts
+            .into_iter()
+            .map(|(label, count)| CountEntry { label, count })
+            .collect::<Vec<CountEntry>>();
+        ranked.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.label.cmp(&b.label)));
+        ranked.truncate(top);
+        ranked
+    }
+
+    /// The entries sorted by label, which orders months and decades chronologically.
+    fn chronological(counts: HashMap<String, usize>) -> Vec<CountEntry> {
+        let mut entries = counts
+            .into_iter()
+            .map(|(label, count)| CountEntry { label, count })
+            .collect::<Vec<CountEntry>>();
+        entries.sort_by(|a, b| a.label.cmp(&b.label));
+        entries
+    }
+
+    /// Serializes the statistics as pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Prints the statistics as terminal tables.
+    pub fn print_tables(&self) {
+        let total_minutes = self.total_duration_ms / 60_000;
+        println!(
+            "{} liked songs, {} h {} min",
+            self.total_tracks,
+            total_minutes / 60,
+            total_minutes % 60
+        );
+        println!(
+            "Explicit: {:.1}% | Average popularity: {:.1}",
+            self.explicit_share * 100.0,
+            self.average_popularity
+        );
+        Self::print_counts("Songs liked per month", &self.adds_per_month);
+        Self::print_counts("Top lead artists", &self.top_artists);
+        print_separator();
+        println!("Most liked albums");
+        for album in &self.top_albums {
+            println!("    {:>5}  {} - {}", album.count, album.artist, album.album);
+        }
+        if !self.genres.is_empty() {
+            Self::print_counts("Genres of the lead artists", &self.genres);
+        }
+        Self::print_counts("Release decades", &self.decades);
+    }
+
+    fn print_counts(title: &str, entries: &[CountEntry]) {
+        print_separator();
+        println!("{title}");
+        let largest = entries.iter().map(|entry| entry.count).max().unwrap_or(1).max(1);
+        for entry in entries {
+            let bar = "#".repeat((entry.count * 30).div_ceil(largest));
+            println!("    {:<24} {:>5}  {}", entry.label, entry.count, bar);
+        }
+    }
+}
+
+/// Loads the liked songs and the genres of their lead artists to build [`LibraryStats`].
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::library_stats::LibraryAnalyzer;
+///
+/// async fn main() {
+///     let stats = LibraryAnalyzer::new().await.stats(10, true).await;
+///     stats.print_tables();
+/// }
+/// ```
+pub struct LibraryAnalyzer {
+    client: AuthCodeSpotify,
+}
+
+impl Api for LibraryAnalyzer {
+    fn select_scopes() -> HashSet<String> {
+        scopes!("user-library-read")
+    }
+}
+
+impl LibraryAnalyzer {
+    pub async fn new() -> Self {
+        let span = tracing::span!(Level::INFO, "LibraryAnalyzer.new");
+        let _enter = span.enter();
+
+        LibraryAnalyzer {
+            client: Self::set_up_client(false, Some(Self::select_scopes())).await,
+        }
+    }
+
+    /// Computes the statistics of the liked songs, synchronizing the library first.
+    ///
+    /// # Arguments
+    /// * `top` - The number of entries kept in the rankings.
+    /// * `with_genres` - Whether the genres of the lead artists are looked up, at one request
+    ///   per 50 artists.
+    pub async fn stats(&self, top: usize, with_genres: bool) -> LibraryStats {
+        let span = tracing::span!(Level::INFO, "LibraryAnalyzer.stats");
+        let _enter = span.enter();
+
+        let saved_tracks = UserLibrary::new().await.tracks();
+        let genres = if with_genres {
+            self.lead_artist_genres(&saved_tracks).await
+        } else {
+            HashMap::new()
+        };
+        LibraryStats::compute(&saved_tracks, &genres, top)
+    }
+
+    /// Looks up the genres of every lead artist, 50 artists per request. Failed batches are
+    /// logged and skipped.
+    pub async fn lead_artist_genres(&self, saved_tracks: &[SavedTrack]) -> HashMap<String, Vec<String>> {
+        let mut artist_ids: Vec<ArtistId<'static>> = Vec::new();
+        let mut seen = HashSet::new();
+        for saved_track in saved_tracks {
+            let Some(artist_id) = saved_track
+                .track
+                .artists
+                .first()
+                .and_then(|artist| artist.id.clone())
+            else {
+                continue;
+            };
+            if seen.insert(artist_id.id().to_string()) {
+                artist_ids.push(artist_id);
+            }
+        }
+        let mut genres = HashMap::with_capacity(artist_ids.len());
+        for chunk in artist_ids.chunks(BatchLimits::Artists.get_limit()) {
+            match self.client.artists(chunk.iter().cloned()).await {
+                Ok(artists) => {
+                    for artist in artists {
+                        genres.insert(artist.id.id().to_string(), artist.genres);
+                    }
+                }
+                Err(err) => event!(Level::ERROR, "Could not load a batch of artists: {:?}", err),
+            }
+        }
+        event!(Level::DEBUG, "Looked up the genres of {} artists", genres.len());
+        genres
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn saved(id: char, artist: &str, album: &str, added_at: &str, release: &str, explicit: bool) -> SavedTrack {
+        let mut saved = OfflineObjects::saved_track(id, added_at);
+        saved.track = OfflineObjects::full_track(&id.to_string().repeat(22), &id.to_string(), artist, album, None, 240_000);
+        saved.track.album.release_date = Some(release.to_string());
+        saved.track.explicit = explicit;
+        saved.track.popularity = 50;
+        saved
+    }
+
+    #[test]
+    fn computes_rankings_and_distributions() {
+        let library = vec![
+            saved('A', "Artist One", "First", "2025-01-05T00:00:00Z", "1994-05-01", true),
+            saved('B', "Artist One", "First", "2025-01-20T00:00:00Z", "1994-05-01", false),
+            saved('C', "Artist Two", "Second", "2024-12-01T00:00:00Z", "2011", false),
+            saved('D', "Artist One", "Third", "2025-02-01T00:00:00Z", "1999-01-01", false),
+        ];
+        let lead_id = library[0].track.artists[0].id.as_ref().unwrap().id().to_string();
+        let genres = HashMap::from([(lead_id, vec!["trip hop".to_string(), "electronica".to_string()])]);
+        let stats = LibraryStats::compute(&library, &genres, 1);
+
+        assert_eq!(stats.total_tracks, 4);
+        assert_eq!(stats.total_duration_ms, 960_000);
+        assert_eq!(
+            stats.adds_per_month.iter().map(|entry| (entry.label.as_str(), entry.count)).collect::<Vec<_>>(),
+            vec![("2024-12", 1), ("2025-01", 2), ("2025-02", 1)]
+        );
+        assert_eq!(stats.top_artists, vec![CountEntry { label: "Artist One".to_string(), count: 3 }]);
+        assert_eq!(stats.top_albums[0].album, "First");
+        assert_eq!(stats.top_albums[0].count, 2);
+        assert_eq!(stats.genres, vec![CountEntry { label: "electronica".to_string(), count: 3 }]);
+        assert_eq!(
+            stats.decades.iter().map(|entry| (entry.label.as_str(), entry.count)).collect::<Vec<_>>(),
+            vec![("1990s", 3), ("2010s", 1)]
+        );
+        assert_eq!(stats.explicit_share, 0.25);
+        assert_eq!(stats.average_popularity, 50.0);
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["top_albums"][0]["artist"], "Artist One");
+        assert_eq!(LibraryStats::compute(&[], &genres, 5), LibraryStats::default());
+    }
+}
@@ -1,14 +1,153 @@
 use crate::enums::fs::ProjectDirectories;
 use crate::paginator::PaginatorRunner;
 use crate::traits::apis::Api;
+use chrono::{DateTime, Duration, Utc};
 use rspotify::AuthCodeSpotify;
 use rspotify::model::{FullTrack, SavedTrack};
 use rspotify::prelude::{Id, OAuthClient};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{fs, io};
 use tracing::{Level, event, span};
 
+/// Number of saved tracks requested per page during an incremental sync.
+const SYNC_PAGE_SIZE: u32 = 50;
+
+/// How long an incremental sync is trusted before the whole library is fetched again to
+/// detect removed tracks.
+const RECONCILE_INTERVAL_DAYS: i64 = 7;
+
+/// Whether a [`LibraryEvent`] records a track being liked or unliked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LibraryEventKind {
+    Added,
+    Removed,
+}
+
+/// A change to the liked songs, as recorded by [`UserLibrary::sync`].
+///
+/// # Fields
+///
+/// * `kind` - Whether the track was liked or unliked.
+/// * `track_id` - The ID of the track.
+/// * `name` - The name of the track.
+/// * `artists` - The names of the track's artists.
+/// * `at` - RFC 3339 timestamp of the change. Likes use the time Spotify reports the track was
+///   saved; removals use the time the sync noticed them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibraryEvent {
+    pub kind: LibraryEventKind,
+    pub track_id: String,
+    pub name: String,
+    pub artists: Vec<String>,
+    pub at: String,
+}
+
+impl LibraryEvent {
+    fn new(kind: LibraryEventKind, saved_track: &SavedTrack, at: DateTime<Utc>) -> Self {
+        LibraryEvent {
+            kind,
+            track_id: UserLibrary::track_key(saved_track),
+            name: saved_track.track.name.clone(),
+            artists: saved_track
+                .track
+                .artists
+                .iter()
+                .map(|artist| artist.name.clone())
+                .collect(),
+            at: at.to_rfc3339(),
+        }
+    }
+
+    /// Appends events to a JSON lines file, creating it when needed.
+    pub fn append(path: &Path, events: &[LibraryEvent]) -> io::Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+        for library_event in events {
+            writeln!(file, "{}", serde_json::to_string(library_event)?)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the events of a JSON lines file, oldest first. Lines that cannot be parsed are
+    /// logged and skipped; a missing file has no events.
+    pub fn load(path: &Path) -> Vec<LibraryEvent> {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return Vec::new();
+        };
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| match serde_json::from_str::<LibraryEvent>(line) {
+                Ok(library_event) => Some(library_event),
+                Err(err) => {
+                    event!(Level::ERROR, "Skipping unreadable library event {:?}: {:?}", line, err);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// When the liked songs were last synchronized, stored next to `liked_songs.json`.
+///
+/// # Fields
+///
+/// * `last_sync` - RFC 3339 timestamp of the last sync of any kind.
+/// * `last_full_sync` - RFC 3339 timestamp of the last sync that fetched the whole library.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibrarySyncState {
+    pub last_sync: Option<String>,
+    pub last_full_sync: Option<String>,
+}
+
+impl LibrarySyncState {
+    fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Whether the last full sync is older than the reconciliation interval, or never happened.
+    pub fn needs_reconciliation(&self, now: DateTime<Utc>) -> bool {
+        self.last_full_sync
+            .as_deref()
+            .and_then(|last_full_sync| DateTime::parse_from_rfc3339(last_full_sync).ok())
+            .is_none_or(|last_full_sync| {
+                now - last_full_sync.with_timezone(&Utc) >= Duration::days(RECONCILE_INTERVAL_DAYS)
+            })
+    }
+}
+
+/// The outcome of [`UserLibrary::sync`].
+///
+/// # Fields
+///
+/// * `added` - The tracks liked since the previous sync.
+/// * `removed` - The tracks unliked since the previous sync. Only found by full syncs.
+/// * `total` - The number of liked songs after the sync.
+/// * `full` - Whether the whole library was fetched.
+#[derive(Clone, Debug, Default)]
+pub struct SyncReport {
+    pub added: Vec<LibraryEvent>,
+    pub removed: Vec<LibraryEvent>,
+    pub total: usize,
+    pub full: bool,
+}
+
 /// Represents the structure that handles a user's liked songs.
 ///
 /// This structure is used to manage and interact with a user's liked songs
@@ -46,10 +185,12 @@ impl UserLibrary {
     /// 1. Creates a client by calling `set_up_client`, with predefined settings.
     /// 2. Determines the directory for storing cached data.
     /// 3. Checks for a previously saved file (`liked_songs.json`) to load tracks.
-    ///    - If the file is found and successfully read, the function loads the cached liked tracks and uses them to initialize the struct.
+    ///    - If the file is found and successfully read, the cached liked tracks are brought up to
+    ///      date with an incremental [`UserLibrary::sync`].
     ///    - Otherwise, it fetches all liked tracks using the client.
     ///      - If the fetch operation succeeds, the tracks are saved to a local cache file.
-    ///      - If the fetch operation fails, it initializes the struct with an empty track list.
+    ///      - If the fetch operation fails, the cached tracks are used as they are, or an empty
+    ///        track list when there is no cache.
     ///
     /// Debugging and diagnostic information is logged at various stages, depending on success or failure of operations.
     ///
@@ -68,44 +209,225 @@ impl UserLibrary {
     /// }
     /// ```
     pub async fn new() -> Self {
+        let mut library = Self::from_cache().await;
+        match library.sync(false).await {
+            Ok(report) => event!(
+                Level::INFO,
+                "Synchronized liked songs: {} added, {} removed",
+                report.added.len(),
+                report.removed.len()
+            ),
+            Err(err) => event!(
+                Level::ERROR,
+                "Failed to synchronize liked songs, using the cached library: {:?}",
+                err
+            ),
+        }
+        library
+    }
+
+    /// Loads the cached liked songs without contacting Spotify, for callers that run
+    /// [`UserLibrary::sync`] themselves. The library is empty when there is no cache.
+    pub async fn from_cache() -> Self {
         let client = Self::set_up_client(false, Some(Self::select_scopes())).await;
         let data_dir = ProjectDirectories::Data;
         let saved_tracks_path = data_dir.path().join("liked_songs.json");
-        if let Ok(saved_tracks) = Self::load_from_file() {
-            println!("Loaded liked songs from cache.");
-            Self {
-                client,
-                saved_tracks,
-                saved_tracks_path,
+        let cached = Self::load_from_file().ok();
+        if cached.is_some() {
+            event!(Level::DEBUG, "Loaded liked songs from cache.");
+        }
+        Self {
+            client,
+            saved_tracks: cached.unwrap_or_default(),
+            saved_tracks_path,
+        }
+    }
+
+    /// Brings the cached liked songs up to date with Spotify.
+    ///
+    /// An incremental sync pages through the saved tracks newest-first and stops at the first
+    /// track already in the cache with the same `added_at`, so it usually costs one request.
+    /// Removals cannot be seen that way, so the whole library is fetched instead when:
+    /// - `full` is set,
+    /// - the cache is empty,
+    /// - the last full sync is older than a week, or
+    /// - the number of liked songs reported by Spotify differs from the cache after the
+    ///   incremental sync.
+    ///
+    /// Every like and unlike found is appended to `liked_songs_events.jsonl` in the data
+    /// directory, except for the tracks loaded by the very first sync.
+    ///
+    /// # Errors
+    /// Returns the error of the first failed request. The cache is left untouched then.
+    ///
+    /// # Example
+    /// ```no_run,ignore
+    /// use spotify_assistant_core::actions::liked_songs::UserLibrary;
+    /// async fn main() {
+    ///     let mut library = UserLibrary::new().await;
+    ///     let report = library.sync(true).await.unwrap();
+    ///     println!("{} added, {} removed", report.added.len(), report.removed.len());
+    /// }
+    /// ```
+    pub async fn sync(&mut self, full: bool) -> Result<SyncReport, rspotify::ClientError> {
+        let span = span!(Level::INFO, "UserLibrary.sync");
+        let _enter = span.enter();
+
+        let data_dir = ProjectDirectories::Data.path();
+        let state_path = data_dir.join("liked_songs_sync.json");
+        let mut state = LibrarySyncState::load(&state_path);
+        let now = Utc::now();
+        let initial = self.saved_tracks.is_empty() && state.last_sync.is_none();
+        let mut report = SyncReport::default();
+
+        let mut reconcile = full || self.saved_tracks.is_empty() || state.needs_reconciliation(now);
+        if !reconcile {
+            let (newest, total) = self.fetch_newest().await?;
+            let mut merged = self.saved_tracks.clone();
+            let added = Self::merge_newest(&mut merged, newest);
+            if merged.len() == total as usize {
+                report.added = added;
+                self.saved_tracks = merged;
+            } else {
+                event!(
+                    Level::INFO,
+                    "Spotify reports {} liked songs but the cache has {}; fetching the whole library",
+                    total,
+                    merged.len()
+                );
+                reconcile = true;
             }
-        } else {
-            let saved_tracks = match Self::update_library(&client).await {
-                Ok(tracks) => {
-                    event!(Level::INFO, "Successfully fetched liked songs.");
-                    tracks
-                }
-                Err(err) => {
-                    event!(Level::ERROR, "Failed to fetch liked songs: {:?}", err);
-                    Vec::new()
+        }
+        if reconcile {
+            let library = Self::update_library(&self.client).await?;
+            let (added, removed) = Self::reconcile(&self.saved_tracks, &library, now);
+            report.added = added;
+            report.removed = removed;
+            report.full = true;
+            self.saved_tracks = library;
+            state.last_full_sync = Some(now.to_rfc3339());
+        }
+        report.total = self.saved_tracks.len();
+        state.last_sync = Some(now.to_rfc3339());
+
+        if let Err(err) = fs::create_dir_all(&data_dir)
+            .and_then(|_| self.save_to_file())
+            .and_then(|_| state.save(&state_path))
+        {
+            event!(Level::ERROR, "Failed to save liked songs to cache: {:?}", err);
+        }
+        if !initial {
+            let mut events = report.added.clone();
+            events.extend(report.removed.iter().cloned());
+            if let Err(err) = LibraryEvent::append(&Self::events_path(), &events) {
+                event!(Level::ERROR, "Failed to record library events: {:?}", err);
+            }
+        }
+        Ok(report)
+    }
+
+    /// The file library events are recorded in: `<data dir>/liked_songs_events.jsonl`.
+    pub fn events_path() -> PathBuf {
+        ProjectDirectories::Data.path().join("liked_songs_events.jsonl")
+    }
+
+    /// Every recorded like and unlike, oldest first.
+    pub fn events() -> Vec<LibraryEvent> {
+        LibraryEvent::load(&Self::events_path())
+    }
+
+    /// Pages through the saved tracks newest-first until reaching a track already cached with
+    /// the same `added_at`.
+    ///
+    /// # Returns
+    /// The saved tracks newer than the cache, newest first, and the total number of liked
+    /// songs reported by Spotify.
+    async fn fetch_newest(&self) -> Result<(Vec<SavedTrack>, u32), rspotify::ClientError> {
+        let known = self
+            .saved_tracks
+            .iter()
+            .map(Self::saved_key)
+            .collect::<HashSet<(String, String)>>();
+        let mut newest = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .client
+                .current_user_saved_tracks_manual(
+                    Some(Self::market()),
+                    Some(SYNC_PAGE_SIZE),
+                    Some(offset),
+                )
+                .await?;
+            let item_count = page.items.len() as u32;
+            for saved_track in page.items {
+                if known.contains(&Self::saved_key(&saved_track)) {
+                    return Ok((newest, page.total));
                 }
-            };
-            let self_obj = Self {
-                client,
-                saved_tracks,
-                saved_tracks_path,
-            };
-            match self_obj.save_to_file() {
-                Ok(_) => event!(Level::INFO, "Liked songs saved to cache."),
-                Err(err) => event!(
-                    Level::ERROR,
-                    "Failed to save liked songs to cache: {:?}",
-                    err
-                ),
-            };
-            self_obj
+                newest.push(saved_track);
+            }
+            if page.next.is_none() || item_count == 0 {
+                return Ok((newest, page.total));
+            }
+            offset += item_count;
         }
     }
 
+    /// Puts the newly saved tracks in front of the cached ones. A cached entry of a track that
+    /// was saved again is dropped, since Spotify lists every track once.
+    ///
+    /// # Returns
+    /// One `Added` event per newly saved track.
+    pub fn merge_newest(cached: &mut Vec<SavedTrack>, newest: Vec<SavedTrack>) -> Vec<LibraryEvent> {
+        let newest_keys = newest.iter().map(Self::track_key).collect::<HashSet<String>>();
+        cached.retain(|saved_track| !newest_keys.contains(&Self::track_key(saved_track)));
+        let added = newest
+            .iter()
+            .map(|saved_track| LibraryEvent::new(LibraryEventKind::Added, saved_track, saved_track.added_at))
+            .collect();
+        cached.splice(0..0, newest);
+        added
+    }
+
+    /// Compares the cached library with the full library fetched from Spotify.
+    ///
+    /// # Returns
+    /// The `Added` events for tracks missing from the cache (or saved again since), and the
+    /// `Removed` events, stamped with `now`, for cached tracks Spotify no longer lists.
+    pub fn reconcile(
+        cached: &[SavedTrack],
+        library: &[SavedTrack],
+        now: DateTime<Utc>,
+    ) -> (Vec<LibraryEvent>, Vec<LibraryEvent>) {
+        let cached_keys = cached.iter().map(Self::saved_key).collect::<HashSet<(String, String)>>();
+        let library_ids = library.iter().map(Self::track_key).collect::<HashSet<String>>();
+        let added = library
+            .iter()
+            .filter(|saved_track| !cached_keys.contains(&Self::saved_key(saved_track)))
+            .map(|saved_track| LibraryEvent::new(LibraryEventKind::Added, saved_track, saved_track.added_at))
+            .collect();
+        let removed = cached
+            .iter()
+            .filter(|saved_track| !library_ids.contains(&Self::track_key(saved_track)))
+            .map(|saved_track| LibraryEvent::new(LibraryEventKind::Removed, saved_track, now))
+            .collect();
+        (added, removed)
+    }
+
+    /// The track ID, or the track name for the rare saved track without one.
+    fn track_key(saved_track: &SavedTrack) -> String {
+        saved_track
+            .track
+            .id
+            .as_ref()
+            .map(|id| id.id().to_string())
+            .unwrap_or_else(|| saved_track.track.name.clone())
+    }
+
+    fn saved_key(saved_track: &SavedTrack) -> (String, String) {
+        (Self::track_key(saved_track), saved_track.added_at.to_rfc3339())
+    }
+
     fn load_from_file() -> io::Result<Vec<SavedTrack>> {
         let data_dir = ProjectDirectories::Data;
         let liked_songs_path = data_dir.path().join("liked_songs.json");
@@ -129,15 +451,7 @@ impl UserLibrary {
 
         let liked_songs = client.current_user_saved_tracks(Some(Self::market()));
         let paginator = PaginatorRunner::new(liked_songs, ());
-        let library = paginator.run().await.unwrap_or_else(|err| {
-            event!(
-                Level::ERROR,
-                "Could not retrieve your saved tracks: {:?}",
-                err
-            );
-            Vec::new()
-        });
-        Ok(library)
+        paginator.run().await
     }
 
     pub fn total_tracks(&self) -> usize {
@@ -431,4 +745,74 @@ mod tests {
 
         drop(env_guard);
     }
+
+    fn names(tracks: &[SavedTrack]) -> Vec<String> {
+        tracks.iter().map(|saved_track| saved_track.track.name.clone()).collect()
+    }
+
+    #[test]
+    fn merge_newest_prepends_and_moves_saved_again_tracks() {
+        let mut cached = vec![
+            OfflineObjects::saved_track('B', "2024-02-01T00:00:00Z"),
+            OfflineObjects::saved_track('A', "2024-01-01T00:00:00Z"),
+        ];
+        let newest = vec![
+            OfflineObjects::saved_track('A', "2024-04-01T00:00:00Z"),
+            OfflineObjects::saved_track('C', "2024-03-01T00:00:00Z"),
+        ];
+        let added = UserLibrary::merge_newest(&mut cached, newest);
+        assert_eq!(names(&cached), vec!["A", "C", "B"]);
+        assert_eq!(added.len(), 2);
+        assert_eq!(added[0].kind, LibraryEventKind::Added);
+        assert_eq!(added[0].at, "2024-04-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn reconcile_finds_additions_and_removals() {
+        let cached = vec![
+            OfflineObjects::saved_track('B', "2024-02-01T00:00:00Z"),
+            OfflineObjects::saved_track('A', "2024-01-01T00:00:00Z"),
+        ];
+        let library = vec![
+            OfflineObjects::saved_track('C', "2024-03-01T00:00:00Z"),
+            OfflineObjects::saved_track('A', "2024-01-01T00:00:00Z"),
+        ];
+        let now = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        let (added, removed) = UserLibrary::reconcile(&cached, &library, now);
+        assert_eq!(added.iter().map(|e| e.name.as_str()).collect::<Vec<&str>>(), vec!["C"]);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].name, "B");
+        assert_eq!(removed[0].kind, LibraryEventKind::Removed);
+        assert_eq!(removed[0].at, now.to_rfc3339());
+    }
+
+    #[test]
+    fn reconciliation_is_due_weekly() {
+        let now = DateTime::parse_from_rfc3339("2024-05-08T00:00:00Z").unwrap().with_timezone(&Utc);
+        assert!(LibrarySyncState::default().needs_reconciliation(now));
+        let recent = LibrarySyncState {
+            last_sync: None,
+            last_full_sync: Some("2024-05-02T00:00:00+00:00".to_string()),
+        };
+        assert!(!recent.needs_reconciliation(now));
+        let stale = LibrarySyncState {
+            last_sync: None,
+            last_full_sync: Some("2024-05-01T00:00:00+00:00".to_string()),
+        };
+        assert!(stale.needs_reconciliation(now));
+    }
+
+    #[test]
+    fn library_events_round_trip_through_json_lines() {
+        let temp_dir = tempdir().expect("temporary directory");
+        let path = temp_dir.path().join("nested").join("events.jsonl");
+        let now = Utc::now();
+        let first = LibraryEvent::new(LibraryEventKind::Added, &OfflineObjects::saved_track('A', "2024-01-01T00:00:00Z"), now);
+        let second = LibraryEvent::new(LibraryEventKind::Removed, &OfflineObjects::saved_track('B', "2024-01-01T00:00:00Z"), now);
+        LibraryEvent::append(&path, std::slice::from_ref(&first)).unwrap();
+        LibraryEvent::append(&path, std::slice::from_ref(&second)).unwrap();
+        fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"not json\n").unwrap();
+        assert_eq!(LibraryEvent::load(&path), vec![first, second]);
+        assert!(LibraryEvent::load(&temp_dir.path().join("missing.jsonl")).is_empty());
+    }
 }
@@ -1,8 +1,16 @@
+pub mod artist_follows;
+pub mod collaboration_graph;
+pub mod discography;
 pub mod exploration;
+pub mod follow_suggestions;
 pub mod general;
+pub mod library_backup;
+pub mod library_prune;
+pub mod library_stats;
 pub mod liked_songs;
 pub mod playlist_editor;
 pub mod playlists;
 pub mod recently_played;
+pub mod saved_albums;
 pub mod update;
 pub mod user;
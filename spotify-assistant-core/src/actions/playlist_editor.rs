@@ -1,8 +1,9 @@
 use crate::actions::exploration::playlist::PlaylistXplr;
 use crate::enums::pl::PlaylistType;
+use crate::models::configuration::Duplicates;
 use crate::traits::apis::Api;
 use rspotify::clients::OAuthClient;
-use rspotify::model::{Id, PlayableId, PlaylistId};
+use rspotify::model::{Id, PlayableId, PlaylistId, TrackId};
 use rspotify::{AuthCodeSpotify, scopes};
 use std::collections::HashSet;
 use tracing::{debug, debug_span, error, info};
@@ -65,7 +66,15 @@ impl Modifier {
     }
     pub async fn update_playlist(&self) {
         let _update_pl_span = debug_span!("update-playlist").entered();
-        let track_ids = self.ref_pl_xplorer.track_ids_expanded().await;
+        let rules = Duplicates::load().rules_for(
+            self.target_pl_xplorer.playlist_id.id(),
+            &self.target_pl_xplorer.full_playlist.name,
+        );
+        let tracks = rules.resolve(self.ref_pl_xplorer.full_tracks_expanded().await);
+        let track_ids = tracks
+            .into_iter()
+            .filter_map(|track| track.id)
+            .collect::<Vec<TrackId>>();
         let ids_len = track_ids.len();
         self.check_if_stock_release_radar_id_was_used(ids_len);
         let mut first_chunk = true;
@@ -0,0 +1,269 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use rspotify::model::{FullTrack, PlayableItem, PlaylistId};
+use tracing::{Level, event};
+
+use crate::actions::exploration::playlist::PlaylistXplr;
+use crate::actions::liked_songs::UserLibrary;
+use crate::actions::playlists::dedupe::OccurrenceKey;
+use crate::enums::combine::{CombineOrder, SetOperation};
+use crate::enums::duplication::DedupeMode;
+
+/// The input that stands for the user's liked songs wherever a combine source is expected.
+pub const LIKED_SONGS: &str = "@liked";
+
+/// A track of a [`CombineSource`] with the time it was added to the source, if known.
+#[derive(Clone, Debug)]
+pub struct SourceTrack {
+    pub track: FullTrack,
+    pub added_at: Option<DateTime<Utc>>,
+}
+
+/// A playlist, or the liked songs, used as an operand of a [`SetOperation`].
+#[derive(Clone, Debug)]
+pub struct CombineSource {
+    pub name: String,
+    pub tracks: Vec<SourceTrack>,
+}
+
+impl CombineSource {
+    pub fn new(name: String, tracks: Vec<SourceTrack>) -> Self {
+        CombineSource { name, tracks }
+    }
+
+    /// Whether `input` refers to the liked songs rather than to a playlist.
+    pub fn is_liked_songs(input: &str) -> bool {
+        input.trim().eq_ignore_ascii_case(LIKED_SONGS)
+    }
+
+    /// Loads the user's liked songs (from the local cache when available).
+    pub async fn liked_songs() -> Self {
+        let span = tracing::span!(Level::INFO, "CombineSource.liked_songs");
+        let _enter = span.enter();
+
+        let tracks = UserLibrary::new()
+            .await
+            .tracks()
+            .into_iter()
+            .map(|saved| SourceTrack {
+                track: saved.track,
+                added_at: Some(saved.added_at),
+            })
+            .collect();
+        CombineSource::new("Liked Songs".to_string(), tracks)
+    }
+
+    /// Loads every track of a playlist. Episodes and unavailable items are skipped.
+    pub async fn playlist(playlist_id: PlaylistId<'static>) -> Self {
+        let span = tracing::span!(Level::INFO, "CombineSource.playlist");
+        let _enter = span.enter();
+
+        let (xplr, items) = PlaylistXplr::with_items(playlist_id, false).await;
+        let tracks = items
+            .into_iter()
+            .filter_map(|item| match item.track {
+                Some(PlayableItem::Track(track)) => Some(SourceTrack {
+                    track,
+                    added_at: item.added_at,
+                }),
+                _ => None,
+            })
+            .collect();
+        CombineSource::new(xplr.full_playlist.name.clone(), tracks)
+    }
+}
+
+/// Builds playlists out of set operations over other playlists.
+///
+/// Tracks are compared with the same keys as [`DedupeMode`]: by track ID, or by recording
+/// fingerprint so that the single and album versions of a song count as the same track. Every
+/// track appears at most once in the result, as the copy from the first source containing it.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::playlists::combine::{CombineSource, PlaylistCombiner};
+/// use spotify_assistant_core::enums::combine::{CombineOrder, SetOperation};
+/// use spotify_assistant_core::enums::duplication::DedupeMode;
+///
+/// async fn main() {
+///     let sources = vec![
+///         CombineSource::playlist(workout_id).await,
+///         CombineSource::playlist(running_id).await,
+///     ];
+///     let tracks = PlaylistCombiner::combine(&sources, SetOperation::Difference, DedupeMode::Fingerprint);
+///     let tracks = PlaylistCombiner::order(tracks, CombineOrder::Artist);
+/// }
+/// ```
+pub struct PlaylistCombiner;
+
+impl PlaylistCombiner {
+    /// Applies `operation` to the sources.
+    ///
+    /// `Difference` keeps the tracks of the first source that are in none of the others, and
+    /// `SymmetricDifference` keeps the tracks that are in exactly one source. Local files are
+    /// skipped since they cannot be written to a playlist.
+    ///
+    /// # Returns
+    /// The resulting tracks in source order: first by source, then by position in the source.
+    pub fn combine(
+        sources: &[CombineSource],
+        operation: SetOperation,
+        mode: DedupeMode,
+    ) -> Vec<SourceTrack> {
+        let keyed = sources
+            .iter()
+            .map(|source| Self::unique_tracks(source, mode))
+            .collect::<Vec<Vec<(OccurrenceKey, &SourceTrack)>>>();
+        let mut source_counts: HashMap<&OccurrenceKey, usize> = HashMap::new();
+        for tracks in &keyed {
+            for (key, _) in tracks {
+                *source_counts.entry(key).or_default() += 1;
+            }
+        }
+
+        let count = |key: &OccurrenceKey| source_counts.get(key).copied().unwrap_or_default();
+        let candidates = match operation {
+            SetOperation::Union | SetOperation::SymmetricDifference => {
+                keyed.iter().flatten().collect::<Vec<&(OccurrenceKey, &SourceTrack)>>()
+            }
+            SetOperation::Intersection | SetOperation::Difference => {
+                keyed.iter().take(1).flatten().collect()
+            }
+        };
+        let mut emitted = HashSet::new();
+        let result = candidates
+            .into_iter()
+            .filter(|(key, _)| match operation {
+                SetOperation::Union => true,
+                SetOperation::Intersection => count(key) == sources.len(),
+                SetOperation::Difference | SetOperation::SymmetricDifference => count(key) == 1,
+            })
+            .filter(|(key, _)| emitted.insert(key))
+            .map(|(_, track)| (*track).clone())
+            .collect::<Vec<SourceTrack>>();
+        event!(
+            Level::DEBUG,
+            "{} of {} sources: {} tracks",
+            operation,
+            sources.len(),
+            result.len()
+        );
+        result
+    }
+
+    fn unique_tracks(source: &CombineSource, mode: DedupeMode) -> Vec<(OccurrenceKey, &SourceTrack)> {
+        let mut seen = HashSet::new();
+        source
+            .tracks
+            .iter()
+            .filter_map(|track| OccurrenceKey::of(&track.track, mode).map(|key| (key, track)))
+            .filter(|(key, _)| seen.insert(key.clone()))
+            .collect()
+    }
+
+    /// Sorts the tracks in the requested order. Sorting is stable, so ties keep source order.
+    pub fn order(mut tracks: Vec<SourceTrack>, order: CombineOrder) -> Vec<FullTrack> {
+        let lower = |text: &str| text.to_lowercase();
+        match order {
+            CombineOrder::Source => {}
+            CombineOrder::AddedAt => {
+                tracks.sort_by_key(|track| (track.added_at.is_none(), track.added_at));
+            }
+            CombineOrder::Artist => tracks.sort_by_key(|track| {
+                (
+                    track
+                        .track
+                        .artists
+                        .first()
+                        .map(|artist| lower(&artist.name))
+                        .unwrap_or_default(),
+                    lower(&track.track.name),
+                )
+            }),
+            CombineOrder::Title => tracks.sort_by_key(|track| lower(&track.track.name)),
+        }
+        tracks.into_iter().map(|track| track.track).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn track(id: char, name: &str, album: &str, isrc: &str) -> SourceTrack {
+        let id = id.to_string().repeat(22);
+        SourceTrack {
+            track: OfflineObjects::full_track(&id, name, "Artist", album, Some(isrc), 200_000),
+            added_at: None,
+        }
+    }
+
+    fn sources() -> Vec<CombineSource> {
+        vec![
+            CombineSource::new(
+                "Workout".to_string(),
+                vec![
+                    track('A', "One", "Album", "ISRC1"),
+                    track('B', "Two", "Album", "ISRC2"),
+                    track('C', "Three", "Album", "ISRC3"),
+                    track('A', "One", "Album", "ISRC1"),
+                ],
+            ),
+            CombineSource::new(
+                "Running".to_string(),
+                vec![
+                    track('B', "Two", "Album", "ISRC2"),
+                    track('D', "Four", "Album", "ISRC4"),
+                    track('E', "Three", "Hits", "ISRC3"),
+                ],
+            ),
+        ]
+    }
+
+    fn names(tracks: Vec<SourceTrack>) -> Vec<String> {
+        tracks.into_iter().map(|track| track.track.name).collect()
+    }
+
+    #[test]
+    fn exact_operations_compare_track_ids() {
+        let sources = sources();
+        let combine = |operation| names(PlaylistCombiner::combine(&sources, operation, DedupeMode::ExactId));
+        assert_eq!(combine(SetOperation::Union), vec!["One", "Two", "Three", "Four", "Three"]);
+        assert_eq!(combine(SetOperation::Intersection), vec!["Two"]);
+        assert_eq!(combine(SetOperation::Difference), vec!["One", "Three"]);
+        assert_eq!(
+            combine(SetOperation::SymmetricDifference),
+            vec!["One", "Three", "Four", "Three"]
+        );
+    }
+
+    #[test]
+    fn fingerprint_operations_match_other_releases() {
+        let sources = sources();
+        let combine =
+            |operation| names(PlaylistCombiner::combine(&sources, operation, DedupeMode::Fingerprint));
+        assert_eq!(combine(SetOperation::Union), vec!["One", "Two", "Three", "Four"]);
+        assert_eq!(combine(SetOperation::Intersection), vec!["Two", "Three"]);
+        assert_eq!(combine(SetOperation::Difference), vec!["One"]);
+        assert_eq!(combine(SetOperation::SymmetricDifference), vec!["One", "Four"]);
+    }
+
+    #[test]
+    fn order_sorts_stably() {
+        let mut tracks = sources().remove(0).tracks;
+        tracks[1].added_at = DateTime::from_timestamp(100, 0);
+        tracks[2].added_at = DateTime::from_timestamp(50, 0);
+        let by_added = PlaylistCombiner::order(tracks.clone(), CombineOrder::AddedAt)
+            .into_iter()
+            .map(|track| track.name)
+            .collect::<Vec<String>>();
+        assert_eq!(by_added, vec!["Three", "Two", "One", "One"]);
+        let by_title = PlaylistCombiner::order(tracks, CombineOrder::Title)
+            .into_iter()
+            .map(|track| track.name)
+            .collect::<Vec<String>>();
+        assert_eq!(by_title, vec!["One", "One", "Three", "Two"]);
+    }
+}
@@ -1,10 +1,14 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use dotenv::dotenv;
 use rspotify::model::{FullPlaylist, FullTrack, PlayableItem};
 use rspotify::{scopes, AuthCodeSpotify};
+use serde::Serialize;
 
+use crate::actions::playlists::export::{ExportedTrack, PlaylistExport};
+use crate::models::full_track_fingerprint::FullTrackFingerprint;
 use crate::traits::apis::Api;
+use crate::utilities::general::normalize_for_matching;
 
 /// A struct that represents a comparison of playlists.
 ///
@@ -240,3 +244,401 @@ impl ComparePlaylists {
         combined
     }
 }
+
+/// A track present in both playlists of a [`PlaylistDiff`].
+///
+/// # Fields
+///
+/// * `track` - The track as it appears in the left playlist.
+/// * `left_position` - The zero-based position of the track in the left playlist.
+/// * `right_position` - The zero-based position of the track in the right playlist.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SharedTrack {
+    pub track: ExportedTrack,
+    pub left_position: usize,
+    pub right_position: usize,
+}
+
+/// Two different Spotify tracks that are versions of the same song, e.g. the single and the
+/// album release, or the explicit and the clean edit.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AlternateVersion {
+    pub left: ExportedTrack,
+    pub right: ExportedTrack,
+}
+
+/// A track-level diff between two playlists, or two snapshots of the same playlist.
+///
+/// Items are matched by URI first, occurrence by occurrence, so a track that appears twice in
+/// one playlist and once in the other is reported once in both and once on its own side.
+/// Unmatched items whose ISRC, or base title and artists, agree are reported as alternate
+/// versions instead of being listed on either side.
+///
+/// # Fields
+///
+/// * `left` - The name of the left playlist.
+/// * `right` - The name of the right playlist.
+/// * `only_left` - Tracks only in the left playlist.
+/// * `only_right` - Tracks only in the right playlist.
+/// * `in_both` - Tracks in both playlists, in left playlist order.
+/// * `moved` - The smallest set of shared tracks whose relative order differs between the
+///   playlists. Tracks that only shifted because of insertions or removals are not included.
+/// * `alternate_versions` - Tracks replaced by another version of the same song.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::playlists::compare::PlaylistDiff;
+/// use spotify_assistant_core::actions::playlists::export::PlaylistExport;
+///
+/// async fn main() {
+///     let before = PlaylistExport::load(Path::new("before.json")).unwrap();
+///     let (xplr, items) = PlaylistXplr::with_items(playlist_id, false).await;
+///     let after = PlaylistExport::from_playlist(&xplr, items);
+///     let diff = PlaylistDiff::new(&before, &after);
+///     println!("{}", diff.table(120));
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PlaylistDiff {
+    pub left: String,
+    pub right: String,
+    pub only_left: Vec<ExportedTrack>,
+    pub only_right: Vec<ExportedTrack>,
+    pub in_both: Vec<SharedTrack>,
+    pub moved: Vec<SharedTrack>,
+    pub alternate_versions: Vec<AlternateVersion>,
+}
+
+impl PlaylistDiff {
+    pub fn new(left: &PlaylistExport, right: &PlaylistExport) -> Self {
+        let mut right_by_uri: HashMap<&str, Vec<&ExportedTrack>> = HashMap::new();
+        for track in right.tracks.iter().rev() {
+            right_by_uri.entry(track.uri.as_str()).or_default().push(track);
+        }
+
+        let mut in_both = Vec::new();
+        let mut unmatched_left = Vec::new();
+        for track in &left.tracks {
+            match right_by_uri.get_mut(track.uri.as_str()).and_then(Vec::pop) {
+                Some(right_track) => in_both.push(SharedTrack {
+                    track: track.clone(),
+                    left_position: track.position,
+                    right_position: right_track.position,
+                }),
+                None => unmatched_left.push(track),
+            }
+        }
+        let matched_right = in_both
+            .iter()
+            .map(|shared| shared.right_position)
+            .collect::<HashSet<usize>>();
+        let mut unmatched_right = right
+            .tracks
+            .iter()
+            .filter(|track| !matched_right.contains(&track.position))
+            .map(Some)
+            .collect::<Vec<Option<&ExportedTrack>>>();
+
+        let mut only_left = Vec::new();
+        let mut alternate_versions = Vec::new();
+        for track in unmatched_left {
+            let version = unmatched_right.iter_mut().find(|candidate| {
+                candidate.is_some_and(|candidate| Self::same_song(track, candidate))
+            });
+            match version.and_then(Option::take) {
+                Some(right_track) => alternate_versions.push(AlternateVersion {
+                    left: track.clone(),
+                    right: right_track.clone(),
+                }),
+                None => only_left.push(track.clone()),
+            }
+        }
+
+        PlaylistDiff {
+            left: left.name.clone(),
+            right: right.name.clone(),
+            only_left,
+            only_right: unmatched_right.into_iter().flatten().cloned().collect(),
+            moved: Self::moved(&in_both),
+            in_both,
+            alternate_versions,
+        }
+    }
+
+    fn same_song(left: &ExportedTrack, right: &ExportedTrack) -> bool {
+        if left.isrc.is_some() && left.isrc == right.isrc {
+            return true;
+        }
+        Self::song_key(left) == Self::song_key(right)
+    }
+
+    fn song_key(track: &ExportedTrack) -> (String, Vec<String>) {
+        let mut artists = track
+            .artists
+            .iter()
+            .map(|artist| normalize_for_matching(artist))
+            .collect::<Vec<String>>();
+        artists.sort();
+        (
+            normalize_for_matching(&FullTrackFingerprint::base_title(&track.name)),
+            artists,
+        )
+    }
+
+    /// Returns the shared tracks outside the longest run that kept its relative order, which is
+    /// the smallest set of tracks that has to move to turn one order into the other.
+    fn moved(in_both: &[SharedTrack]) -> Vec<SharedTrack> {
+        // Patience sorting over the right positions, in left order.
+        let mut tails: Vec<usize> = Vec::new();
+        let mut previous: Vec<Option<usize>> = vec![None; in_both.len()];
+        for (index, shared) in in_both.iter().enumerate() {
+            let slot = tails.partition_point(|&tail| {
+                in_both[tail].right_position < shared.right_position
+            });
+            previous[index] = slot.checked_sub(1).map(|slot| tails[slot]);
+            if slot == tails.len() {
+                tails.push(index);
+            } else {
+                tails[slot] = index;
+            }
+        }
+        let mut kept = HashSet::new();
+        let mut cursor = tails.last().copied();
+        while let Some(index) = cursor {
+            kept.insert(index);
+            cursor = previous[index];
+        }
+        in_both
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !kept.contains(index))
+            .map(|(_, shared)| shared.clone())
+            .collect()
+    }
+
+    /// Whether both playlists contain exactly the same tracks in the same order.
+    pub fn is_identical(&self) -> bool {
+        self.only_left.is_empty()
+            && self.only_right.is_empty()
+            && self.moved.is_empty()
+            && self.alternate_versions.is_empty()
+    }
+
+    /// A one-line summary of the diff.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} only in {}, {} only in {}, {} in both ({} moved), {} alternate versions",
+            self.only_left.len(),
+            self.left,
+            self.only_right.len(),
+            self.right,
+            self.in_both.len(),
+            self.moved.len(),
+            self.alternate_versions.len()
+        )
+    }
+
+    /// Serializes the diff as pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Renders the diff as a side-by-side table `width` columns wide, followed by the moved
+    /// tracks and alternate versions.
+    pub fn table(&self, width: usize) -> String {
+        let column = width.saturating_sub(6).max(30) / 3;
+        let rows = ComparePlaylists::combine_vectors(
+            self.only_left.iter().map(Self::describe).collect(),
+            self.in_both
+                .iter()
+                .map(|shared| Self::describe(&shared.track))
+                .collect(),
+            self.only_right.iter().map(Self::describe).collect(),
+            (
+                format!("Only in {}", self.left),
+                "In both".to_string(),
+                format!("Only in {}", self.right),
+            ),
+            String::new(),
+        );
+
+        let mut lines = Vec::with_capacity(rows.len() + 1);
+        for (index, (left, both, right)) in rows.iter().enumerate() {
+            lines.push(format!(
+                "{} | {} | {}",
+                Self::cell(left, column),
+                Self::cell(both, column),
+                Self::cell(right, column)
+            ));
+            if index == 0 {
+                lines.push(format!("{0}-+-{0}-+-{0}", "-".repeat(column)));
+            }
+        }
+        if !self.moved.is_empty() {
+            lines.push(String::new());
+            lines.push("Moved:".to_string());
+            for shared in &self.moved {
+                lines.push(format!(
+                    "    #{} -> #{}  {}",
+                    shared.left_position + 1,
+                    shared.right_position + 1,
+                    Self::describe(&shared.track)
+                ));
+            }
+        }
+        if !self.alternate_versions.is_empty() {
+            lines.push(String::new());
+            lines.push("Alternate versions:".to_string());
+            for version in &self.alternate_versions {
+                lines.push(format!(
+                    "    {} ({}) <-> {} ({})",
+                    Self::describe(&version.left),
+                    version.left.album,
+                    Self::describe(&version.right),
+                    version.right.album
+                ));
+            }
+        }
+        lines.push(String::new());
+        lines.push(self.summary());
+        lines.join("\n")
+    }
+
+    fn describe(track: &ExportedTrack) -> String {
+        format!("{} - {}", track.artists.join(", "), track.name)
+    }
+
+    fn cell(text: &str, width: usize) -> String {
+        let length = text.chars().count();
+        if length > width {
+            let truncated = text.chars().take(width.saturating_sub(1)).collect::<String>();
+            format!("{truncated}…")
+        } else {
+            format!("{text}{}", " ".repeat(width - length))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(position: usize, id: char, name: &str, isrc: &str) -> ExportedTrack {
+        ExportedTrack {
+            position,
+            name: name.to_string(),
+            artists: vec!["Artist".to_string()],
+            album: "Album".to_string(),
+            isrc: Some(isrc.to_string()),
+            duration_ms: 200_000,
+            added_at: None,
+            added_by: None,
+            uri: format!("spotify:track:{}", id.to_string().repeat(22)),
+            is_local: false,
+        }
+    }
+
+    fn export(name: &str, tracks: Vec<ExportedTrack>) -> PlaylistExport {
+        PlaylistExport {
+            id: name.to_string(),
+            name: name.to_string(),
+            description: None,
+            owner: None,
+            snapshot_id: None,
+            exported_at: String::new(),
+            tracks,
+        }
+    }
+
+    #[test]
+    fn combine_vectors_pads_shorter_columns() {
+        let rows = ComparePlaylists::combine_vectors(
+            vec![1, 2],
+            vec![3],
+            vec![],
+            (10, 20, 30),
+            0,
+        );
+        assert_eq!(rows, vec![(10, 20, 30), (1, 3, 0), (2, 0, 0)]);
+    }
+
+    #[test]
+    fn diff_separates_sides_and_alternate_versions() {
+        let left = export(
+            "A",
+            vec![
+                track(0, 'A', "Shared", "ISRC1"),
+                track(1, 'B', "Gone", "ISRC2"),
+                track(2, 'C', "Song", "ISRC3"),
+            ],
+        );
+        let right = export(
+            "B",
+            vec![
+                track(0, 'A', "Shared", "ISRC1"),
+                track(1, 'D', "Song (Radio Edit)", "ISRC4"),
+                track(2, 'E', "New", "ISRC5"),
+            ],
+        );
+        let diff = PlaylistDiff::new(&left, &right);
+        assert_eq!(diff.in_both.len(), 1);
+        assert_eq!(diff.only_left[0].name, "Gone");
+        assert_eq!(diff.only_right[0].name, "New");
+        assert_eq!(diff.alternate_versions.len(), 1);
+        assert_eq!(diff.alternate_versions[0].right.name, "Song (Radio Edit)");
+        assert!(diff.moved.is_empty());
+        assert!(!diff.is_identical());
+    }
+
+    #[test]
+    fn only_reordered_tracks_are_reported_as_moved() {
+        let left = export(
+            "A",
+            vec![
+                track(0, 'A', "One", "ISRC1"),
+                track(1, 'B', "Two", "ISRC2"),
+                track(2, 'C', "Three", "ISRC3"),
+                track(3, 'D', "Four", "ISRC4"),
+            ],
+        );
+        let right = export(
+            "B",
+            vec![
+                track(0, 'E', "Inserted", "ISRC5"),
+                track(1, 'B', "Two", "ISRC2"),
+                track(2, 'C', "Three", "ISRC3"),
+                track(3, 'D', "Four", "ISRC4"),
+                track(4, 'A', "One", "ISRC1"),
+            ],
+        );
+        let diff = PlaylistDiff::new(&left, &right);
+        assert_eq!(diff.moved.len(), 1);
+        assert_eq!(diff.moved[0].track.name, "One");
+        assert_eq!((diff.moved[0].left_position, diff.moved[0].right_position), (0, 4));
+    }
+
+    #[test]
+    fn repeated_tracks_are_matched_occurrence_by_occurrence() {
+        let left = export(
+            "A",
+            vec![track(0, 'A', "One", "ISRC1"), track(1, 'A', "One", "ISRC1")],
+        );
+        let right = export("B", vec![track(0, 'A', "One", "ISRC1")]);
+        let diff = PlaylistDiff::new(&left, &right);
+        assert_eq!(diff.in_both.len(), 1);
+        assert_eq!(diff.only_left.len(), 1);
+        assert_eq!(diff.only_left[0].position, 1);
+        assert!(PlaylistDiff::new(&right, &right).is_identical());
+    }
+
+    #[test]
+    fn table_lines_fit_the_requested_width() {
+        let left = export("A", vec![track(0, 'A', &"Long title ".repeat(10), "ISRC1")]);
+        let right = export("B", Vec::new());
+        let table = PlaylistDiff::new(&left, &right).table(60);
+        let lines = table.lines().collect::<Vec<&str>>();
+        assert!(lines[0].starts_with("Only in A"));
+        assert!(lines[..3].iter().all(|line| line.chars().count() <= 60));
+        assert!(lines.last().unwrap().starts_with("1 only in A"));
+    }
+}
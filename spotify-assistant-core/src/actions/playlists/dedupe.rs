@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+
+use rspotify::clients::OAuthClient;
+use rspotify::model::{FullTrack, ItemPositions, PlayableId, PlaylistId, TrackId};
+use rspotify::prelude::Id;
+use serde::Serialize;
+use tracing::{Level, event};
+
+use crate::actions::exploration::playlist::PlaylistXplr;
+use crate::enums::duplication::DedupeMode;
+use crate::enums::validation::BatchLimits;
+use crate::errors::playlists::PlaylistError;
+use crate::models::full_track_fingerprint::FullTrackFingerprint;
+use crate::utilities::general::print_separator;
+
+/// A playlist item that duplicates an earlier item and is removed by [`PlaylistDeduper`].
+///
+/// # Fields
+///
+/// * `position` - The zero-based position of the removed item.
+/// * `kept_position` - The zero-based position of the earlier item that is kept.
+/// * `track_id` - The Spotify ID of the removed item.
+/// * `name` - The track name of the removed item.
+/// * `artists` - The artist names of the removed item.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RemovedOccurrence {
+    pub position: usize,
+    pub kept_position: usize,
+    pub track_id: String,
+    pub name: String,
+    pub artists: Vec<String>,
+}
+
+/// The identity used to decide whether two tracks are the same under a [`DedupeMode`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum OccurrenceKey {
+    Id(String),
+    Fingerprint(FullTrackFingerprint),
+}
+
+impl OccurrenceKey {
+    /// Builds the key of `track`. In `Fingerprint` mode, tracks that cannot be fingerprinted
+    /// fall back to their ID. Tracks without an ID (local files) have no key.
+    pub(crate) fn of(track: &FullTrack, mode: DedupeMode) -> Option<Self> {
+        let track_id = track.id.as_ref()?.id().to_string();
+        Some(match mode {
+            DedupeMode::ExactId => OccurrenceKey::Id(track_id),
+            DedupeMode::Fingerprint => match FullTrackFingerprint::try_new(track) {
+                Some(fingerprint) => OccurrenceKey::Fingerprint(fingerprint),
+                None => OccurrenceKey::Id(track_id),
+            },
+        })
+    }
+}
+
+/// Removes duplicate items from an existing playlist in place.
+///
+/// Only the later occurrences of a duplicated track are removed. Removal is done by position
+/// against the playlist's snapshot, so the remaining items keep their order and any other copy
+/// of the same track (e.g. the kept one) is left untouched.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::playlists::dedupe::PlaylistDeduper;
+/// use spotify_assistant_core::enums::duplication::DedupeMode;
+///
+/// async fn main() {
+///     let deduper = PlaylistDeduper::new(playlist_id, DedupeMode::Fingerprint).await;
+///     let removed = deduper.later_occurrences().await;
+///     deduper.remove(&removed).await.unwrap();
+///     PlaylistDeduper::print_removed(&deduper.name(), &removed, false);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PlaylistDeduper {
+    xplr: PlaylistXplr,
+    positioned: Vec<(usize, FullTrack)>,
+    mode: DedupeMode,
+}
+
+impl PlaylistDeduper {
+    pub async fn new(playlist_id: PlaylistId<'static>, mode: DedupeMode) -> Self {
+        let span = tracing::span!(Level::INFO, "PlaylistDeduper.new");
+        let _enter = span.enter();
+
+        let (xplr, items) = PlaylistXplr::with_items(playlist_id, false).await;
+        PlaylistDeduper {
+            xplr,
+            positioned: PlaylistXplr::positions_from_items(items),
+            mode,
+        }
+    }
+    pub fn name(&self) -> String {
+        self.xplr.full_playlist.name.clone()
+    }
+    pub fn mode(&self) -> DedupeMode {
+        self.mode
+    }
+
+    /// Returns every item of the playlist, as loaded by [`PlaylistDeduper::new`], that
+    /// duplicates an earlier one.
+    pub async fn later_occurrences(&self) -> Vec<RemovedOccurrence> {
+        let span = tracing::span!(Level::INFO, "PlaylistDeduper.later_occurrences");
+        let _enter = span.enter();
+
+        Self::find_later_occurrences(&self.positioned, self.mode)
+    }
+
+    /// Finds the items of a positioned track list that duplicate an earlier item.
+    ///
+    /// In `Fingerprint` mode, tracks that cannot be fingerprinted fall back to matching by
+    /// ID. Items without an ID (local files) are never reported since they cannot be removed
+    /// through the API.
+    ///
+    /// # Returns
+    /// The later occurrences in playlist order. The first occurrence of every track is never
+    /// part of the result.
+    pub fn find_later_occurrences(
+        tracks: &[(usize, FullTrack)],
+        mode: DedupeMode,
+    ) -> Vec<RemovedOccurrence> {
+        let mut first_seen: HashMap<OccurrenceKey, usize> = HashMap::new();
+        let mut removed = Vec::new();
+        for (position, track) in tracks {
+            let Some(key) = OccurrenceKey::of(track, mode) else {
+                continue;
+            };
+            let track_id = track.id.as_ref().map(|id| id.id().to_string()).unwrap_or_default();
+            match first_seen.get(&key) {
+                Some(&kept_position) => removed.push(RemovedOccurrence {
+                    position: *position,
+                    kept_position,
+                    track_id,
+                    name: track.name.clone(),
+                    artists: track
+                        .artists
+                        .iter()
+                        .map(|artist| artist.name.clone())
+                        .collect(),
+                }),
+                None => {
+                    first_seen.insert(key, *position);
+                }
+            }
+        }
+        removed
+    }
+
+    /// Removes the given occurrences from the playlist by position.
+    ///
+    /// Batches are sent from the highest position down so that earlier removals never shift
+    /// the positions of later batches, and each request carries the snapshot ID returned by
+    /// the previous one.
+    ///
+    /// # Returns
+    /// The snapshot ID of the playlist after the last removal. When a request fails, the error
+    /// carries the number of items already removed and the snapshot ID they left behind.
+    pub async fn remove(&self, occurrences: &[RemovedOccurrence]) -> Result<String, PlaylistError> {
+        let span = tracing::span!(Level::INFO, "PlaylistDeduper.remove");
+        let _enter = span.enter();
+
+        let mut snapshot_id = self.xplr.full_playlist.snapshot_id.clone();
+        let mut ordered = occurrences.to_vec();
+        ordered.sort_by_key(|occurrence| std::cmp::Reverse(occurrence.position));
+        let batches = ordered
+            .chunks(BatchLimits::ModifyPlaylistItems.get_limit())
+            .map(|batch| Ok((batch.len(), Self::positions_by_track(batch)?)))
+            .collect::<Result<Vec<(usize, Vec<(TrackId<'static>, Vec<u32>)>)>, PlaylistError>>()?;
+        let mut removed = 0;
+        for (batch_size, positions) in batches {
+            let items = positions
+                .iter()
+                .map(|(track_id, positions)| ItemPositions {
+                    id: PlayableId::Track(track_id.as_ref()),
+                    positions,
+                })
+                .collect::<Vec<ItemPositions>>();
+            match self
+                .xplr
+                .client
+                .playlist_remove_specific_occurrences_of_items(
+                    self.xplr.playlist_id.clone(),
+                    items,
+                    Some(snapshot_id.as_str()),
+                )
+                .await
+            {
+                Ok(result) => {
+                    event!(
+                        Level::INFO,
+                        "Removed {} duplicate items from {}",
+                        batch_size,
+                        self.xplr.full_playlist.name
+                    );
+                    snapshot_id = result.snapshot_id;
+                    removed += batch_size;
+                }
+                Err(err) => {
+                    event!(Level::ERROR, "Error removing duplicate items: {:?}", err);
+                    return Err(PlaylistError::PartiallyRemoved {
+                        removed,
+                        snapshot_id,
+                        source: err,
+                    });
+                }
+            }
+        }
+        Ok(snapshot_id)
+    }
+
+    fn positions_by_track(
+        batch: &[RemovedOccurrence],
+    ) -> Result<Vec<(TrackId<'static>, Vec<u32>)>, PlaylistError> {
+        let mut grouped: Vec<(TrackId<'static>, Vec<u32>)> = Vec::new();
+        for occurrence in batch {
+            let position = occurrence.position as u32;
+            match grouped
+                .iter_mut()
+                .find(|(track_id, _)| track_id.id() == occurrence.track_id)
+            {
+                Some((_, positions)) => positions.push(position),
+                None => {
+                    let track_id = TrackId::from_id(occurrence.track_id.clone())?;
+                    grouped.push((track_id, vec![position]));
+                }
+            }
+        }
+        Ok(grouped)
+    }
+
+    /// Prints the removed (or, for a dry run, removable) occurrences with one-based positions.
+    pub fn print_removed(playlist_name: &str, occurrences: &[RemovedOccurrence], dry_run: bool) {
+        let verb = if dry_run { "Would remove" } else { "Removed" };
+        println!(
+            "{verb} {} duplicate items from {playlist_name}.",
+            occurrences.len()
+        );
+        if occurrences.is_empty() {
+            return;
+        }
+        print_separator();
+        for occurrence in occurrences {
+            println!(
+                "    #{} {} - {} (duplicate of #{})",
+                occurrence.position + 1,
+                occurrence.name,
+                occurrence.artists.join(", "),
+                occurrence.kept_position + 1
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn track(id: char, name: &str, album: &str, isrc: Option<&str>) -> FullTrack {
+        let id = id.to_string().repeat(22);
+        OfflineObjects::full_track(&id, name, "Artist", album, isrc, 200_000)
+    }
+
+    fn positioned(tracks: Vec<FullTrack>) -> Vec<(usize, FullTrack)> {
+        tracks.into_iter().enumerate().collect()
+    }
+
+    #[test]
+    fn exact_mode_reports_only_later_occurrences_of_the_same_id() {
+        let tracks = positioned(vec![
+            track('A', "Song", "Album", Some("ISRC1")),
+            track('B', "Other", "Album", Some("ISRC2")),
+            track('A', "Song", "Album", Some("ISRC1")),
+            track('C', "Song", "Hits", Some("ISRC1")),
+            track('A', "Song", "Album", Some("ISRC1")),
+        ]);
+        let removed = PlaylistDeduper::find_later_occurrences(&tracks, DedupeMode::ExactId);
+        let positions = removed
+            .iter()
+            .map(|occurrence| (occurrence.position, occurrence.kept_position))
+            .collect::<Vec<(usize, usize)>>();
+        assert_eq!(positions, vec![(2, 0), (4, 0)]);
+    }
+
+    #[test]
+    fn fingerprint_mode_matches_the_same_recording_across_ids() {
+        let tracks = positioned(vec![
+            track('A', "Song", "Album", Some("ISRC1")),
+            track('C', "Song", "Hits", Some("ISRC1")),
+            track('D', "Local", "Album", None),
+            track('D', "Local", "Album", None),
+        ]);
+        let removed = PlaylistDeduper::find_later_occurrences(&tracks, DedupeMode::Fingerprint);
+        assert_eq!(removed.len(), 2);
+        assert_eq!(removed[0].track_id, "C".repeat(22));
+        assert_eq!((removed[1].position, removed[1].kept_position), (3, 2));
+    }
+
+    #[test]
+    fn positions_are_grouped_per_track() {
+        let tracks = positioned(vec![
+            track('A', "Song", "Album", Some("ISRC1")),
+            track('A', "Song", "Album", Some("ISRC1")),
+            track('B', "Other", "Album", Some("ISRC2")),
+            track('B', "Other", "Album", Some("ISRC2")),
+            track('A', "Song", "Album", Some("ISRC1")),
+        ]);
+        let removed = PlaylistDeduper::find_later_occurrences(&tracks, DedupeMode::ExactId);
+        let grouped = PlaylistDeduper::positions_by_track(&removed).unwrap();
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[0].1, vec![1, 4]);
+        assert_eq!(grouped[1].1, vec![3]);
+    }
+}
@@ -0,0 +1,500 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rspotify::model::{FullTrack, PlaylistItem};
+use rspotify::prelude::Id;
+use serde::Serialize;
+use tracing::{Level, event};
+
+use crate::actions::exploration::playlist::PlaylistXplr;
+use crate::actions::liked_songs::UserLibrary;
+use crate::actions::playlists::user::UserPlaylists;
+use crate::enums::fs::ProjectDirectories;
+use crate::enums::report::ReportFormat;
+use crate::models::full_track_fingerprint::FullTrackFingerprint;
+use crate::utilities::general::{csv_row, print_separator};
+
+/// Name given to the liked-songs pseudo-playlist in reports.
+pub const LIKED_SONGS_SOURCE: &str = "Liked Songs";
+
+/// A named collection of tracks to scan for duplicates, such as a playlist or the user's
+/// liked songs.
+///
+/// # Fields
+///
+/// * `name` - The display name of the source (playlist name or [`LIKED_SONGS_SOURCE`]).
+/// * `id` - The Spotify ID of the playlist, or `None` for the liked songs.
+/// * `tracks` - Every track of the source paired with its zero-based position.
+#[derive(Clone, Debug)]
+pub struct TrackSource {
+    pub name: String,
+    pub id: Option<String>,
+    pub tracks: Vec<(usize, FullTrack)>,
+}
+
+impl TrackSource {
+    /// Creates a source from already positioned tracks.
+    pub fn new(name: String, id: Option<String>, tracks: Vec<(usize, FullTrack)>) -> Self {
+        TrackSource { name, id, tracks }
+    }
+
+    /// Builds a source from a playlist explorer and the items it was created from, keeping each
+    /// track's position in the playlist.
+    pub fn from_playlist(xplr: &PlaylistXplr, items: Vec<PlaylistItem>) -> Self {
+        TrackSource {
+            name: xplr.full_playlist.name.clone(),
+            id: Some(xplr.playlist_id.id().to_string()),
+            tracks: PlaylistXplr::positions_from_items(items),
+        }
+    }
+
+    /// Builds the liked-songs pseudo-playlist. Positions follow the library order, newest first.
+    pub fn from_liked_songs(library: &UserLibrary) -> Self {
+        TrackSource {
+            name: LIKED_SONGS_SOURCE.to_string(),
+            id: None,
+            tracks: library.full_tracks().into_iter().enumerate().collect(),
+        }
+    }
+}
+
+/// The reason a group of tracks was reported as duplicates.
+///
+/// Variants:
+/// - `SameRecording`: The same recording (same fingerprint) appears more than once in a single source.
+/// - `CrossPlaylist`: The same recording appears in more than one source.
+/// - `AlbumVersions`: Different recordings share a title and artists, e.g. the single and the
+///   album version, or a remaster.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateKind {
+    SameRecording,
+    CrossPlaylist,
+    AlbumVersions,
+}
+
+impl fmt::Display for DuplicateKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DuplicateKind::SameRecording => write!(f, "same recording"),
+            DuplicateKind::CrossPlaylist => write!(f, "multiple playlists"),
+            DuplicateKind::AlbumVersions => write!(f, "album versions"),
+        }
+    }
+}
+
+/// A single occurrence of a track within a source. `position` is zero-based.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TrackLocation {
+    pub source: String,
+    pub source_id: Option<String>,
+    pub position: usize,
+    pub track_id: String,
+    pub album: String,
+    pub isrc: Option<String>,
+}
+
+/// A group of track occurrences considered to be duplicates of each other.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DuplicateCluster {
+    pub kind: DuplicateKind,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub isrc: Option<String>,
+    pub locations: Vec<TrackLocation>,
+}
+
+impl DuplicateCluster {
+    /// Returns the distinct source names the cluster spans, in alphabetical order.
+    pub fn sources(&self) -> Vec<String> {
+        self.locations
+            .iter()
+            .map(|location| location.source.clone())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+/// A library-wide duplicate report built from any number of [`TrackSource`]s.
+///
+/// # Fields
+///
+/// * `sources_scanned` - The number of sources (playlists and liked songs) that were scanned.
+/// * `tracks_scanned` - The number of tracks that could be fingerprinted.
+/// * `skipped_tracks` - Tracks without an ISRC or ID (e.g. local files) that were ignored.
+/// * `clusters` - The duplicate clusters, ordered by kind and then by title.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DuplicateReport {
+    pub sources_scanned: usize,
+    pub tracks_scanned: usize,
+    pub skipped_tracks: usize,
+    pub clusters: Vec<DuplicateCluster>,
+}
+
+impl DuplicateReport {
+    /// Loads every playlist owned by the user plus the liked songs and builds the report.
+    ///
+    /// Each owned playlist is explored with [`PlaylistXplr`] so positions are reported against
+    /// the real playlist order. Playlists are loaded one at a time to stay well within the
+    /// API's rate limits.
+    ///
+    /// # Example
+    /// ```no_run,ignore
+    /// use spotify_assistant_core::actions::playlists::duplicates::DuplicateReport;
+    ///
+    /// async fn main() {
+    ///     let report = DuplicateReport::library().await;
+    ///     report.print_summary();
+    /// }
+    /// ```
+    pub async fn library() -> Self {
+        let span = tracing::span!(Level::INFO, "DuplicateReport.library");
+        let _enter = span.enter();
+
+        let owned = UserPlaylists::new().await.get_owned_playlists().await;
+        let mut sources = Vec::with_capacity(owned.len() + 1);
+        for (index, playlist) in owned.iter().enumerate() {
+            event!(
+                Level::INFO,
+                "Scanning playlist {}/{}: {}",
+                index + 1,
+                owned.len(),
+                playlist.name
+            );
+            let (xplr, items) = PlaylistXplr::with_items(playlist.id.clone(), false).await;
+            sources.push(TrackSource::from_playlist(&xplr, items));
+        }
+        let library = UserLibrary::new().await;
+        sources.push(TrackSource::from_liked_songs(&library));
+        Self::from_sources(&sources)
+    }
+
+    /// Builds the report from the given sources without any network access.
+    ///
+    /// Tracks are grouped by their [`FullTrackFingerprint`]. A group with more than one
+    /// occurrence becomes a `SameRecording` cluster when all occurrences share a source and a
+    /// `CrossPlaylist` cluster otherwise. Distinct fingerprints sharing a base title (ignoring
+    /// bracketed suffixes and " - Remastered"-style tails) and artists become an
+    /// `AlbumVersions` cluster.
+    pub fn from_sources(sources: &[TrackSource]) -> Self {
+        let mut skipped_tracks = 0;
+        let mut tracks_scanned = 0;
+        let mut fingerprint_order: Vec<FullTrackFingerprint> = Vec::new();
+        let mut by_fingerprint: HashMap<FullTrackFingerprint, Vec<TrackLocation>> = HashMap::new();
+        let mut display_names: HashMap<FullTrackFingerprint, (String, Vec<String>)> =
+            HashMap::new();
+
+        for source in sources {
+            for (position, track) in &source.tracks {
+                let Some(fingerprint) = FullTrackFingerprint::try_new(track) else {
+                    skipped_tracks += 1;
+                    continue;
+                };
+                tracks_scanned += 1;
+                let location = TrackLocation {
+                    source: source.name.clone(),
+                    source_id: source.id.clone(),
+                    position: *position,
+                    track_id: fingerprint.id(),
+                    album: track.album.name.clone(),
+                    isrc: fingerprint.isrc(),
+                };
+                if !by_fingerprint.contains_key(&fingerprint) {
+                    fingerprint_order.push(fingerprint.clone());
+                    display_names.insert(
+                        fingerprint.clone(),
+                        (
+                            track.name.clone(),
+                            track.artists.iter().map(|a| a.name.clone()).collect(),
+                        ),
+                    );
+                }
+                by_fingerprint.entry(fingerprint).or_default().push(location);
+            }
+        }
+
+        let mut clusters = Vec::new();
+        let mut versions: HashMap<(String, Vec<String>), Vec<&FullTrackFingerprint>> =
+            HashMap::new();
+        let mut version_order = Vec::new();
+        for fingerprint in &fingerprint_order {
+            let locations = &by_fingerprint[fingerprint];
+            let (title, artists) = display_names[fingerprint].clone();
+            if locations.len() > 1 {
+                let source_count = locations
+                    .iter()
+                    .map(|location| location.source.as_str())
+                    .collect::<BTreeSet<&str>>()
+                    .len();
+                let kind = if source_count == 1 {
+                    DuplicateKind::SameRecording
+                } else {
+                    DuplicateKind::CrossPlaylist
+                };
+                clusters.push(DuplicateCluster {
+                    kind,
+                    title,
+                    artists,
+                    isrc: fingerprint.isrc(),
+                    locations: locations.clone(),
+                });
+            }
+            let key = fingerprint.version_key();
+            if !versions.contains_key(&key) {
+                version_order.push(key.clone());
+            }
+            versions.entry(key).or_default().push(fingerprint);
+        }
+        for key in version_order {
+            let fingerprints = &versions[&key];
+            if fingerprints.len() < 2 {
+                continue;
+            }
+            let (title, artists) = display_names[fingerprints[0]].clone();
+            clusters.push(DuplicateCluster {
+                kind: DuplicateKind::AlbumVersions,
+                title,
+                artists,
+                isrc: None,
+                locations: fingerprints
+                    .iter()
+                    .flat_map(|fingerprint| by_fingerprint[*fingerprint].clone())
+                    .collect(),
+            });
+        }
+        clusters.sort_by(|a, b| {
+            a.kind
+                .cmp(&b.kind)
+                .then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase()))
+        });
+
+        event!(
+            Level::DEBUG,
+            "Duplicate clusters: {} | Tracks scanned: {} | Skipped: {}",
+            clusters.len(),
+            tracks_scanned,
+            skipped_tracks
+        );
+        DuplicateReport {
+            sources_scanned: sources.len(),
+            tracks_scanned,
+            skipped_tracks,
+            clusters,
+        }
+    }
+
+    /// Returns the clusters of the given kind.
+    pub fn clusters_of(&self, kind: DuplicateKind) -> Vec<&DuplicateCluster> {
+        self.clusters
+            .iter()
+            .filter(|cluster| cluster.kind == kind)
+            .collect()
+    }
+
+    /// Serializes the full report as pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Flattens the report into CSV, one row per track location.
+    ///
+    /// The `cluster` column numbers clusters from 1 so rows belonging to the same cluster can
+    /// be grouped in a spreadsheet. Positions are zero-based, as used by the Spotify API.
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec![csv_row(&[
+            "cluster", "kind", "title", "artists", "isrc", "source", "source_id", "position",
+            "track_id", "album",
+        ])];
+        for (index, cluster) in self.clusters.iter().enumerate() {
+            for location in &cluster.locations {
+                lines.push(csv_row(&[
+                    (index + 1).to_string(),
+                    cluster.kind.to_string(),
+                    cluster.title.clone(),
+                    cluster.artists.join("; "),
+                    location.isrc.clone().unwrap_or_default(),
+                    location.source.clone(),
+                    location.source_id.clone().unwrap_or_default(),
+                    location.position.to_string(),
+                    location.track_id.clone(),
+                    location.album.clone(),
+                ]));
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Writes the report to `path` in the given format, creating parent directories as needed.
+    pub fn export(&self, path: &Path, format: ReportFormat) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = match format {
+            ReportFormat::Json => self.to_json()?,
+            ReportFormat::Csv => self.to_csv(),
+        };
+        fs::write(path, contents)?;
+        event!(Level::INFO, "Duplicate report written to {:?}", path);
+        Ok(())
+    }
+
+    /// The default export location: `<data dir>/reports/duplicates.<ext>`.
+    pub fn default_export_path(format: ReportFormat) -> PathBuf {
+        ProjectDirectories::Data
+            .path()
+            .join("reports")
+            .join(format!("duplicates.{}", format.extension()))
+    }
+
+    /// Prints a human-readable summary of the report. Positions are shown one-based.
+    pub fn print_summary(&self) {
+        println!(
+            "Scanned {} tracks across {} sources ({} skipped).",
+            self.tracks_scanned, self.sources_scanned, self.skipped_tracks
+        );
+        for kind in [
+            DuplicateKind::SameRecording,
+            DuplicateKind::CrossPlaylist,
+            DuplicateKind::AlbumVersions,
+        ] {
+            println!("{}: {} clusters", kind, self.clusters_of(kind).len());
+        }
+        for cluster in &self.clusters {
+            print_separator();
+            println!(
+                "[{}] {} - {}",
+                cluster.kind,
+                cluster.title,
+                cluster.artists.join(", ")
+            );
+            for location in &cluster.locations {
+                println!(
+                    "    {} #{} ({})",
+                    location.source,
+                    location.position + 1,
+                    location.album
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn track(id: &str, name: &str, album: &str, isrc: Option<&str>) -> FullTrack {
+        OfflineObjects::full_track(id, name, "Example Artist", album, isrc, 180_000)
+    }
+
+    fn source(name: &str, tracks: Vec<FullTrack>) -> TrackSource {
+        TrackSource::new(
+            name.to_string(),
+            Some(format!("{name}-id")),
+            tracks.into_iter().enumerate().collect(),
+        )
+    }
+
+    #[test]
+    fn base_title_strips_version_suffixes() {
+        assert_eq!(FullTrackFingerprint::base_title("song (remastered 2011)"), "song");
+        assert_eq!(FullTrackFingerprint::base_title("song - radio edit"), "song");
+        assert_eq!(FullTrackFingerprint::base_title("song [live]  again"), "song again");
+    }
+
+    #[test]
+    fn repeated_recording_in_one_playlist_is_same_recording() {
+        let sources = vec![source(
+            "Road Trip",
+            vec![
+                track("AAAAAAAAAAAAAAAAAAAAAA", "Song", "Album", Some("ISRC1")),
+                track("BBBBBBBBBBBBBBBBBBBBBB", "Other", "Album", Some("ISRC2")),
+                track("AAAAAAAAAAAAAAAAAAAAAA", "Song", "Album", Some("ISRC1")),
+            ],
+        )];
+        let report = DuplicateReport::from_sources(&sources);
+        let clusters = report.clusters_of(DuplicateKind::SameRecording);
+        assert_eq!(clusters.len(), 1);
+        let positions: Vec<usize> = clusters[0].locations.iter().map(|l| l.position).collect();
+        assert_eq!(positions, vec![0, 2]);
+        assert_eq!(report.tracks_scanned, 3);
+    }
+
+    #[test]
+    fn recording_in_two_sources_is_cross_playlist() {
+        let song = track("AAAAAAAAAAAAAAAAAAAAAA", "Song", "Album", Some("ISRC1"));
+        let sources = vec![
+            source("Road Trip", vec![song.clone()]),
+            TrackSource::new(LIKED_SONGS_SOURCE.to_string(), None, vec![(4, song)]),
+        ];
+        let report = DuplicateReport::from_sources(&sources);
+        let clusters = report.clusters_of(DuplicateKind::CrossPlaylist);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(
+            clusters[0].sources(),
+            vec![LIKED_SONGS_SOURCE.to_string(), "Road Trip".to_string()]
+        );
+        assert_eq!(clusters[0].locations[1].position, 4);
+    }
+
+    #[test]
+    fn different_isrcs_with_same_base_title_are_album_versions() {
+        let sources = vec![source(
+            "Mix",
+            vec![
+                track("AAAAAAAAAAAAAAAAAAAAAA", "Song", "Single", Some("ISRC1")),
+                track("CCCCCCCCCCCCCCCCCCCCCC", "Song - Remastered 2011", "Deluxe", Some("ISRC9")),
+            ],
+        )];
+        let report = DuplicateReport::from_sources(&sources);
+        assert!(report.clusters_of(DuplicateKind::SameRecording).is_empty());
+        let versions = report.clusters_of(DuplicateKind::AlbumVersions);
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].locations.len(), 2);
+    }
+
+    #[test]
+    fn tracks_without_isrc_are_skipped() {
+        let sources = vec![source(
+            "Local",
+            vec![
+                track("AAAAAAAAAAAAAAAAAAAAAA", "Song", "Album", None),
+                track("AAAAAAAAAAAAAAAAAAAAAA", "Song", "Album", None),
+            ],
+        )];
+        let report = DuplicateReport::from_sources(&sources);
+        assert_eq!(report.skipped_tracks, 2);
+        assert!(report.clusters.is_empty());
+    }
+
+    #[test]
+    fn csv_export_has_one_row_per_location() {
+        let song = track("AAAAAAAAAAAAAAAAAAAAAA", "Song, Pt. 1", "Album", Some("ISRC1"));
+        let sources = vec![source("A", vec![song.clone()]), source("B", vec![song])];
+        let report = DuplicateReport::from_sources(&sources);
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("1,multiple playlists,"));
+        assert!(lines[1].contains("\"Song, Pt. 1\""));
+        assert!(lines[2].contains(",B,B-id,0,"));
+    }
+
+    #[test]
+    fn export_writes_json_to_disk() {
+        let dir = tempfile::tempdir().expect("temporary directory");
+        let path = dir.path().join("nested").join("duplicates.json");
+        let song = track("AAAAAAAAAAAAAAAAAAAAAA", "Song", "Album", Some("ISRC1"));
+        let report = DuplicateReport::from_sources(&[source("A", vec![song.clone(), song])]);
+        report.export(&path, ReportFormat::Json).expect("export report");
+        let parsed: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).expect("read report"))
+                .expect("valid JSON");
+        assert_eq!(parsed["clusters"][0]["kind"], "same_recording");
+    }
+}
@@ -0,0 +1,423 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rspotify::model::{PlayableItem, PlaylistItem};
+use rspotify::prelude::Id;
+use serde::{Deserialize, Serialize};
+use tracing::{Level, event};
+
+use crate::actions::exploration::playlist::PlaylistXplr;
+use crate::actions::playlists::user::UserPlaylists;
+use crate::enums::fs::ProjectDirectories;
+use crate::enums::playlist_format::PlaylistFormat;
+use crate::enums::track_collection::TrackCollection;
+use crate::utilities::general::{csv_row, file_stem, xml_escape};
+
+/// A single exported playlist track.
+///
+/// # Fields
+///
+/// * `position` - The zero-based position of the item in the playlist.
+/// * `name` - The track name.
+/// * `artists` - The names of the track's artists.
+/// * `album` - The album name.
+/// * `isrc` - The track's ISRC, when Spotify provides one.
+/// * `duration_ms` - The track duration in milliseconds.
+/// * `added_at` - When the item was added to the playlist (RFC 3339), if known.
+/// * `added_by` - The ID of the user who added the item, if known.
+/// * `uri` - The Spotify URI of the track (`spotify:local:...` for local files).
+/// * `is_local` - Whether the item is a local file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedTrack {
+    pub position: usize,
+    pub name: String,
+    pub artists: Vec<String>,
+    pub album: String,
+    pub isrc: Option<String>,
+    pub duration_ms: i64,
+    pub added_at: Option<String>,
+    pub added_by: Option<String>,
+    pub uri: String,
+    pub is_local: bool,
+}
+
+/// A playlist snapshot that can be written to JSON, CSV, M3U8 or XSPF.
+///
+/// Only track items are exported; podcast episodes and unavailable items are skipped, but the
+/// positions of the exported tracks still refer to the real playlist order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaylistExport {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub owner: Option<String>,
+    pub snapshot_id: Option<String>,
+    pub exported_at: String,
+    pub tracks: Vec<ExportedTrack>,
+}
+
+impl PlaylistExport {
+    /// Builds an export from raw playlist items without any network access.
+    pub fn new(id: String, name: String, items: Vec<PlaylistItem>) -> Self {
+        PlaylistExport {
+            id,
+            name,
+            description: None,
+            owner: None,
+            snapshot_id: None,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            tracks: Self::tracks_from_items(items),
+        }
+    }
+
+    /// Builds the export from an explored playlist and the items it was created from, with the
+    /// playlist's description, owner and snapshot ID.
+    ///
+    /// # Example
+    /// ```no_run,ignore
+    /// use spotify_assistant_core::actions::exploration::playlist::PlaylistXplr;
+    /// use spotify_assistant_core::actions::playlists::export::PlaylistExport;
+    /// use spotify_assistant_core::enums::playlist_format::PlaylistFormat;
+    ///
+    /// async fn main() {
+    ///     let (xplr, items) = PlaylistXplr::with_items(playlist_id, false).await;
+    ///     let export = PlaylistExport::from_playlist(&xplr, items);
+    ///     export.export(&export.default_path(PlaylistFormat::M3u8), PlaylistFormat::M3u8).unwrap();
+    /// }
+    /// ```
+    pub fn from_playlist(xplr: &PlaylistXplr, items: Vec<PlaylistItem>) -> Self {
+        let span = tracing::span!(Level::INFO, "PlaylistExport.from_playlist");
+        let _enter = span.enter();
+
+        let playlist = &xplr.full_playlist;
+        PlaylistExport {
+            description: playlist.description.clone(),
+            owner: Some(playlist.owner.id.id().to_string()),
+            snapshot_id: Some(playlist.snapshot_id.clone()),
+            ..Self::new(
+                xplr.playlist_id.id().to_string(),
+                playlist.name.clone(),
+                items,
+            )
+        }
+    }
+
+    fn tracks_from_items(items: Vec<PlaylistItem>) -> Vec<ExportedTrack> {
+        let mut positions = Vec::new();
+        let mut additions = Vec::new();
+        let mut tracks = Vec::new();
+        for (position, item) in items.into_iter().enumerate() {
+            if let Some(PlayableItem::Track(track)) = item.track {
+                positions.push(position);
+                additions.push((
+                    item.added_at.map(|added_at| added_at.to_rfc3339()),
+                    item.added_by.map(|user| user.id.id().to_string()),
+                ));
+                tracks.push(track);
+            }
+        }
+
+        let collection = TrackCollection::FullTrack(tracks);
+        let names = collection.names().unwrap_or_default();
+        let artists = collection.artist_names().unwrap_or_default();
+        let albums = collection.album_names().unwrap_or_default();
+        let isrcs = collection.isrcs().unwrap_or_default();
+        let durations = collection.durations_ms().unwrap_or_default();
+        let local_flags = collection.is_local().unwrap_or_default();
+        let uris = collection.uris().unwrap_or_default();
+
+        positions
+            .into_iter()
+            .zip(additions)
+            .enumerate()
+            .map(|(index, (position, (added_at, added_by)))| {
+                let is_local = local_flags[index];
+                let uri = if is_local {
+                    Self::local_uri(&artists[index], &albums[index], &names[index], durations[index])
+                } else {
+                    uris[index].clone()
+                };
+                ExportedTrack {
+                    position,
+                    name: names[index].clone(),
+                    artists: artists[index].clone(),
+                    album: albums[index].clone(),
+                    isrc: isrcs[index].clone(),
+                    duration_ms: durations[index],
+                    added_at,
+                    added_by,
+                    uri,
+                    is_local,
+                }
+            })
+            .collect()
+    }
+
+    /// Local files have no track ID, so their URI is rebuilt the way the Spotify clients do:
+    /// `spotify:local:<artist>:<album>:<title>:<seconds>` with spaces encoded as `+`.
+    fn local_uri(artists: &[String], album: &str, name: &str, duration_ms: i64) -> String {
+        let encode = |text: &str| text.replace(' ', "+");
+        format!(
+            "spotify:local:{}:{}:{}:{}",
+            encode(&artists.join(", ")),
+            encode(album),
+            encode(name),
+            duration_ms / 1000
+        )
+    }
+
+    /// Serializes the export as pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Flattens the export into CSV, one row per track. Artists are joined with `; `.
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec![csv_row(&[
+            "position", "name", "artists", "album", "isrc", "duration_ms", "added_at", "added_by",
+            "uri", "is_local",
+        ])];
+        for track in &self.tracks {
+            lines.push(csv_row(&[
+                track.position.to_string(),
+                track.name.clone(),
+                track.artists.join("; "),
+                track.album.clone(),
+                track.isrc.clone().unwrap_or_default(),
+                track.duration_ms.to_string(),
+                track.added_at.clone().unwrap_or_default(),
+                track.added_by.clone().unwrap_or_default(),
+                track.uri.clone(),
+                track.is_local.to_string(),
+            ]));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Renders the export as an extended M3U playlist with Spotify URIs as locations.
+    pub fn to_m3u8(&self) -> String {
+        let mut lines = vec!["#EXTM3U".to_string(), format!("#PLAYLIST:{}", self.name)];
+        for track in &self.tracks {
+            lines.push(format!(
+                "#EXTINF:{},{} - {}",
+                track.duration_ms / 1000,
+                track.artists.join(", "),
+                track.name
+            ));
+            lines.push(track.uri.clone());
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Renders the export as an XSPF document. ISRCs are written as `isrc:` identifiers.
+    pub fn to_xspf(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n");
+        xml.push_str(&format!("  <title>{}</title>\n", xml_escape(&self.name)));
+        if let Some(description) = self.description.as_ref().filter(|text| !text.is_empty()) {
+            xml.push_str(&format!(
+                "  <annotation>{}</annotation>\n",
+                xml_escape(description)
+            ));
+        }
+        xml.push_str("  <trackList>\n");
+        for track in &self.tracks {
+            xml.push_str("    <track>\n");
+            xml.push_str(&format!(
+                "      <location>{}</location>\n",
+                xml_escape(&track.uri)
+            ));
+            if let Some(isrc) = &track.isrc {
+                xml.push_str(&format!(
+                    "      <identifier>isrc:{}</identifier>\n",
+                    xml_escape(isrc)
+                ));
+            }
+            xml.push_str(&format!("      <title>{}</title>\n", xml_escape(&track.name)));
+            xml.push_str(&format!(
+                "      <creator>{}</creator>\n",
+                xml_escape(&track.artists.join(", "))
+            ));
+            xml.push_str(&format!("      <album>{}</album>\n", xml_escape(&track.album)));
+            xml.push_str(&format!(
+                "      <duration>{}</duration>\n",
+                track.duration_ms
+            ));
+            xml.push_str("    </track>\n");
+        }
+        xml.push_str("  </trackList>\n</playlist>\n");
+        xml
+    }
+
+    /// Renders the export in the requested format.
+    pub fn render(&self, format: PlaylistFormat) -> serde_json::Result<String> {
+        Ok(match format {
+            PlaylistFormat::Json => self.to_json()?,
+            PlaylistFormat::Csv => self.to_csv(),
+            PlaylistFormat::M3u8 => self.to_m3u8(),
+            PlaylistFormat::Xspf => self.to_xspf(),
+        })
+    }
+
+    /// Reads an export previously written as JSON, e.g. to compare against an older snapshot.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Self::from_json(&contents).map_err(io::Error::other)
+    }
+
+    /// Parses an export rendered by [`PlaylistExport::to_json`].
+    pub fn from_json(contents: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(contents)
+    }
+
+    /// Writes the export to `path`, creating parent directories as needed.
+    pub fn export(&self, path: &Path, format: PlaylistFormat) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.render(format)?)?;
+        event!(Level::INFO, "Playlist {} exported to {:?}", self.name, path);
+        Ok(())
+    }
+
+    /// The file name used for this playlist: its sanitized name plus the format's extension.
+    pub fn file_name(&self, format: PlaylistFormat) -> String {
+        format!("{}.{}", file_stem(&self.name), format.extension())
+    }
+
+    /// The default export location: `<data dir>/exports/<playlist name>.<ext>`.
+    pub fn default_path(&self, format: PlaylistFormat) -> PathBuf {
+        Self::default_export_dir().join(self.file_name(format))
+    }
+
+    /// The directory exports are written to when no output is given: `<data dir>/exports`.
+    pub fn default_export_dir() -> PathBuf {
+        ProjectDirectories::Data.path().join("exports")
+    }
+
+    /// Exports every playlist owned by the user into `directory`.
+    ///
+    /// Playlists sharing a name are disambiguated by appending their ID to the file name.
+    /// Playlists are loaded one at a time to stay within the API's rate limits.
+    ///
+    /// # Returns
+    /// The paths of the written files, or the first I/O error encountered.
+    pub async fn export_all(directory: &Path, format: PlaylistFormat) -> io::Result<Vec<PathBuf>> {
+        let span = tracing::span!(Level::INFO, "PlaylistExport.export_all");
+        let _enter = span.enter();
+
+        let owned = UserPlaylists::new().await.get_owned_playlists().await;
+        let mut used_names = HashSet::new();
+        let mut written = Vec::with_capacity(owned.len());
+        for (index, playlist) in owned.iter().enumerate() {
+            event!(
+                Level::INFO,
+                "Exporting playlist {}/{}: {}",
+                index + 1,
+                owned.len(),
+                playlist.name
+            );
+            let (xplr, items) = PlaylistXplr::with_items(playlist.id.clone(), false).await;
+            let export = Self::from_playlist(&xplr, items);
+            let mut file_name = export.file_name(format);
+            if !used_names.insert(file_name.to_lowercase()) {
+                file_name = format!(
+                    "{}-{}.{}",
+                    file_stem(&export.name),
+                    export.id,
+                    format.extension()
+                );
+                used_names.insert(file_name.to_lowercase());
+            }
+            let path = directory.join(file_name);
+            export.export(&path, format)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn export() -> PlaylistExport {
+        let song = OfflineObjects::full_track(
+            &"A".repeat(22),
+            "Rock & Roll",
+            "Artist",
+            "Album",
+            Some("ISRC1"),
+            185_000,
+        );
+        let other = OfflineObjects::full_track(
+            &"B".repeat(22),
+            "Other, Song",
+            "Artist",
+            "Album",
+            None,
+            200_000,
+        );
+        let mut unavailable = OfflineObjects::playlist_item(song.clone());
+        unavailable.track = None;
+        PlaylistExport::new(
+            "playlist".to_string(),
+            "Mix".to_string(),
+            vec![
+                OfflineObjects::playlist_item(song),
+                unavailable,
+                OfflineObjects::playlist_item(other),
+            ],
+        )
+    }
+
+    #[test]
+    fn positions_follow_the_playlist_and_skip_non_tracks() {
+        let export = export();
+        assert_eq!(export.tracks.len(), 2);
+        assert_eq!(export.tracks[0].position, 0);
+        assert_eq!(export.tracks[1].position, 2);
+        assert_eq!(export.tracks[0].uri, format!("spotify:track:{}", "A".repeat(22)));
+        assert_eq!(export.tracks[0].isrc.as_deref(), Some("ISRC1"));
+        assert_eq!(export.tracks[1].isrc, None);
+        assert_eq!(
+            export.tracks[0].added_at.as_deref(),
+            Some("2024-01-01T00:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_has_a_header() {
+        let csv = export().to_csv();
+        let lines = csv.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("position,name,artists"));
+        assert!(lines[2].starts_with("2,\"Other, Song\",Artist,Album,,200000"));
+    }
+
+    #[test]
+    fn m3u8_lists_every_track_with_duration_in_seconds() {
+        let m3u = export().to_m3u8();
+        assert!(m3u.starts_with("#EXTM3U\n#PLAYLIST:Mix\n"));
+        assert!(m3u.contains("#EXTINF:185,Artist - Rock & Roll\nspotify:track:"));
+    }
+
+    #[test]
+    fn xspf_escapes_text() {
+        let xspf = export().to_xspf();
+        assert!(xspf.contains("<title>Rock &amp; Roll</title>"));
+        assert!(xspf.contains("<identifier>isrc:ISRC1</identifier>"));
+        assert_eq!(xspf.matches("<track>").count(), 2);
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let export = export();
+        let parsed: PlaylistExport =
+            serde_json::from_str(&export.to_json().expect("serialize")).expect("deserialize");
+        assert_eq!(parsed, export);
+    }
+}
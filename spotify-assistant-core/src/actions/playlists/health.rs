@@ -0,0 +1,462 @@
+use std::fmt;
+
+use rspotify::ClientError;
+use rspotify::clients::OAuthClient;
+use rspotify::model::{FullTrack, ItemPositions, PlayableId, PlayableItem, PlaylistId, PlaylistItem};
+use rspotify::prelude::Id;
+use tracing::{Level, event};
+
+use crate::actions::exploration::playlist::PlaylistXplr;
+use crate::actions::playlists::import::{ImportEntry, MIN_CANDIDATE, MatchCandidate, PlaylistImporter};
+use crate::enums::track_collection::TrackCollection;
+use crate::models::configuration::Duplicates;
+use crate::models::duplicate_rules::DuplicateRules;
+
+/// Something that keeps a playlist item from playing normally.
+///
+/// Variants:
+/// - `Unavailable`: The item no longer resolves to a track or episode.
+/// - `Unplayable`: The track cannot be played in the current market.
+/// - `Restricted`: The track carries a restriction (`market`, `product` or `explicit`).
+/// - `LocalFile`: The item is a local file, which only plays on devices that have it.
+/// - `Episode`: The item is a podcast episode.
+/// - `Relinked`: Spotify plays another release of the track in the current market.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HealthIssue {
+    Unavailable,
+    Unplayable,
+    Restricted(String),
+    LocalFile,
+    Episode,
+    Relinked,
+}
+
+impl fmt::Display for HealthIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HealthIssue::Unavailable => write!(f, "unavailable"),
+            HealthIssue::Unplayable => write!(f, "unplayable"),
+            HealthIssue::Restricted(reason) => write!(f, "restricted ({reason})"),
+            HealthIssue::LocalFile => write!(f, "local file"),
+            HealthIssue::Episode => write!(f, "episode"),
+            HealthIssue::Relinked => write!(f, "relinked"),
+        }
+    }
+}
+
+/// A playlist item with at least one [`HealthIssue`].
+///
+/// # Fields
+///
+/// * `position` - The zero-based position of the item in the playlist.
+/// * `description` - `Artists - Title` of the item, or a placeholder for unavailable items.
+/// * `track` - The track as returned for the current market, when the item is a track.
+/// * `stored_id` - The ID of the track as stored in the playlist. For relinked tracks this is
+///   the original ID rather than the ID of the track that plays.
+/// * `issues` - Everything wrong with the item.
+#[derive(Clone, Debug)]
+pub struct HealthProblem {
+    pub position: usize,
+    pub description: String,
+    pub track: Option<FullTrack>,
+    pub stored_id: Option<String>,
+    pub issues: Vec<HealthIssue>,
+}
+
+impl HealthProblem {
+    /// Whether the item can be swapped for another track through the API. Local files and
+    /// episodes cannot be matched, and items without an ID cannot be removed by position.
+    pub fn is_replaceable(&self) -> bool {
+        self.stored_id.is_some()
+            && self.issues.iter().all(|issue| {
+                matches!(
+                    issue,
+                    HealthIssue::Unplayable | HealthIssue::Restricted(_) | HealthIssue::Relinked
+                )
+            })
+    }
+
+    /// The track described as an import entry, so replacements are searched the way imports
+    /// are matched.
+    pub fn entry(&self) -> Option<ImportEntry> {
+        let track = self.track.as_ref()?;
+        Some(ImportEntry {
+            line: self.position + 1,
+            raw: self.description.clone(),
+            title: track.name.clone(),
+            artist: track
+                .artists
+                .first()
+                .map(|artist| artist.name.clone())
+                .unwrap_or_default(),
+            album: Some(track.album.name.clone()),
+            isrc: track.external_ids.get("isrc").cloned(),
+            duration_ms: Some(track.duration.num_milliseconds()),
+            track_id: None,
+        })
+    }
+
+    pub fn issues_summary(&self) -> String {
+        self.issues
+            .iter()
+            .map(HealthIssue::to_string)
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+/// Finds playlist items that are unavailable, unplayable or restricted, and swaps them for
+/// playable versions of the same song.
+///
+/// Items are loaded for the configured market, so `is_playable`, the restrictions and track
+/// relinking reflect what the user can actually play.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::playlists::health::PlaylistHealth;
+///
+/// async fn main() {
+///     let health = PlaylistHealth::new(playlist_id).await;
+///     for problem in health.problems().await {
+///         println!("#{} {}: {}", problem.position + 1, problem.description, problem.issues_summary());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PlaylistHealth {
+    xplr: PlaylistXplr,
+    importer: PlaylistImporter,
+    snapshot_id: String,
+    items: Vec<PlaylistItem>,
+    positioned: Vec<(usize, FullTrack)>,
+    rules: DuplicateRules,
+}
+
+impl PlaylistHealth {
+    pub async fn new(playlist_id: PlaylistId<'static>) -> Self {
+        let span = tracing::span!(Level::INFO, "PlaylistHealth.new");
+        let _enter = span.enter();
+
+        let (xplr, items) = PlaylistXplr::with_items(playlist_id, false).await;
+        PlaylistHealth {
+            snapshot_id: xplr.full_playlist.snapshot_id.clone(),
+            rules: Duplicates::load().rules_for(xplr.playlist_id.id(), &xplr.full_playlist.name),
+            xplr,
+            importer: PlaylistImporter::new().await,
+            positioned: PlaylistXplr::positions_from_items(items.clone()),
+            items,
+        }
+    }
+    pub fn name(&self) -> String {
+        self.xplr.full_playlist.name.clone()
+    }
+
+    /// Returns the playlist items, as loaded by [`PlaylistHealth::new`], that have issues, in
+    /// playlist order.
+    pub async fn problems(&self) -> Vec<HealthProblem> {
+        let span = tracing::span!(Level::INFO, "PlaylistHealth.problems");
+        let _enter = span.enter();
+
+        Self::inspect(&self.items)
+    }
+
+    /// Finds the items of a playlist that have issues.
+    pub fn inspect(items: &[PlaylistItem]) -> Vec<HealthProblem> {
+        let mut problems = Vec::new();
+        let mut tracks = Vec::new();
+        for (position, item) in items.iter().enumerate() {
+            match &item.track {
+                None => problems.push(HealthProblem {
+                    position,
+                    description: "(unavailable item)".to_string(),
+                    track: None,
+                    stored_id: None,
+                    issues: vec![HealthIssue::Unavailable],
+                }),
+                Some(PlayableItem::Episode(episode)) => problems.push(HealthProblem {
+                    position,
+                    description: format!("{} - {}", episode.show.name, episode.name),
+                    track: None,
+                    stored_id: None,
+                    issues: vec![HealthIssue::Episode],
+                }),
+                Some(PlayableItem::Track(track)) => tracks.push((position, track.clone())),
+                Some(_) => problems.push(HealthProblem {
+                    position,
+                    description: "(unsupported item)".to_string(),
+                    track: None,
+                    stored_id: None,
+                    issues: vec![HealthIssue::Unavailable],
+                }),
+            }
+        }
+
+        let collection =
+            TrackCollection::FullTrack(tracks.iter().map(|(_, track)| track.clone()).collect());
+        let playable = collection.is_playable().unwrap_or_default();
+        let restrictions = collection.restrictions().unwrap_or_default();
+        let local_flags = collection.is_local().unwrap_or_default();
+        for (index, (position, track)) in tracks.into_iter().enumerate() {
+            let mut issues = Vec::new();
+            if local_flags.get(index).copied().unwrap_or_default() {
+                issues.push(HealthIssue::LocalFile);
+            } else if !playable.get(index).copied().unwrap_or(true) {
+                issues.push(HealthIssue::Unplayable);
+            }
+            if let Some(reason) = restrictions.get(index).filter(|reason| *reason != "none") {
+                issues.push(HealthIssue::Restricted(reason.clone()));
+            }
+            let linked_id = track
+                .linked_from
+                .as_ref()
+                .and_then(|link| link.id.as_ref())
+                .map(|id| id.id().to_string());
+            let playing_id = track.id.as_ref().map(|id| id.id().to_string());
+            if linked_id.is_some() && linked_id != playing_id {
+                issues.push(HealthIssue::Relinked);
+            }
+            if issues.is_empty() {
+                continue;
+            }
+            let artists = track
+                .artists
+                .iter()
+                .map(|artist| artist.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ");
+            problems.push(HealthProblem {
+                position,
+                description: format!("{} - {}", artists, track.name),
+                stored_id: linked_id.or(playing_id),
+                track: Some(track),
+                issues,
+            });
+        }
+        problems.sort_by_key(|problem| problem.position);
+        problems
+    }
+
+    /// Searches playable replacements for a problem item, best match first.
+    ///
+    /// A relinked track is replaced by the release Spotify already plays. Other tracks are
+    /// searched by ISRC, then by title and artist, in the current market.
+    pub async fn replacements(&self, problem: &HealthProblem) -> Vec<MatchCandidate> {
+        let span = tracing::span!(Level::INFO, "PlaylistHealth.replacements");
+        let _enter = span.enter();
+
+        if !problem.is_replaceable() {
+            return Vec::new();
+        }
+        let track = problem.track.as_ref().expect("Replaceable problems are tracks");
+        if problem.issues == vec![HealthIssue::Relinked] {
+            return vec![MatchCandidate {
+                track: track.clone(),
+                confidence: 1.0,
+            }];
+        }
+        let Some(entry) = problem.entry() else {
+            return Vec::new();
+        };
+        let mut candidates = self
+            .importer
+            .candidates(&entry)
+            .await
+            .into_iter()
+            .filter(|candidate| Self::is_healthy_replacement(problem, &candidate.track))
+            .filter(|candidate| candidate.confidence >= MIN_CANDIDATE)
+            .collect::<Vec<MatchCandidate>>();
+        candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        candidates
+    }
+
+    fn is_healthy_replacement(problem: &HealthProblem, track: &FullTrack) -> bool {
+        let track_id = track.id.as_ref().map(|id| id.id().to_string());
+        track_id.is_some()
+            && track_id != problem.stored_id
+            && track.is_playable != Some(false)
+            && track.restrictions.is_none()
+    }
+
+    /// Replaces the item at the problem's position with `replacement`, keeping the position.
+    ///
+    /// Nothing is changed when the playlist's [`DuplicateRules`] reject the replacement
+    /// because another item of the playlist already is a copy of it.
+    ///
+    /// The replacement is inserted at the position before the item, now one further down, is
+    /// removed, so a failed removal leaves both in the playlist rather than neither.
+    ///
+    /// # Returns
+    /// Whether the item was replaced.
+    ///
+    /// # Errors
+    /// Returns the error of adding the replacement or removing the item.
+    pub async fn swap(&mut self, problem: &HealthProblem, replacement: &FullTrack) -> Result<bool, ClientError> {
+        let span = tracing::span!(Level::INFO, "PlaylistHealth.swap");
+        let _enter = span.enter();
+
+        let stored_id = rspotify::model::TrackId::from_id(
+            problem
+                .stored_id
+                .clone()
+                .expect("Only replaceable problems are swapped"),
+        )
+        .expect("Stored track IDs are valid");
+        if !Self::accepts_replacement(&self.rules, &self.positioned, problem, replacement) {
+            event!(Level::INFO, "{} is already in the playlist, #{} kept", replacement.name, problem.position + 1);
+            return Ok(false);
+        }
+        let replacement_id = replacement
+            .id
+            .clone()
+            .expect("Replacements always have an ID");
+        let added = self
+            .xplr
+            .client
+            .playlist_add_items(
+                self.xplr.playlist_id.clone(),
+                [PlayableId::Track(replacement_id)],
+                Some(problem.position as u32),
+            )
+            .await?;
+        self.snapshot_id = added.snapshot_id;
+        let positions = [problem.position as u32 + 1];
+        let removed = self
+            .xplr
+            .client
+            .playlist_remove_specific_occurrences_of_items(
+                self.xplr.playlist_id.clone(),
+                [ItemPositions {
+                    id: PlayableId::Track(stored_id),
+                    positions: &positions,
+                }],
+                Some(self.snapshot_id.as_str()),
+            )
+            .await?;
+        self.snapshot_id = removed.snapshot_id;
+        event!(
+            Level::INFO,
+            "Replaced #{} {} with {}",
+            problem.position + 1,
+            problem.description,
+            replacement.name
+        );
+        if let Some((_, track)) = self
+            .positioned
+            .iter_mut()
+            .find(|(position, _)| *position == problem.position)
+        {
+            *track = replacement.clone();
+        }
+        Ok(true)
+    }
+
+    /// Whether `rules` let `replacement` take the place of the problem item, judged against
+    /// every other item of the playlist.
+    ///
+    /// The problem item is left out by position rather than by ID: a relinked item is loaded
+    /// with the ID that plays, not the stored one, and its replacement is that same track.
+    fn accepts_replacement(
+        rules: &DuplicateRules,
+        positioned: &[(usize, FullTrack)],
+        problem: &HealthProblem,
+        replacement: &FullTrack,
+    ) -> bool {
+        let others = positioned
+            .iter()
+            .filter(|(position, _)| *position != problem.position)
+            .map(|(_, track)| track.clone())
+            .collect::<Vec<FullTrack>>();
+        !rules.filter_additions(&others, vec![replacement.clone()]).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn track_item(id: char) -> PlaylistItem {
+        OfflineObjects::playlist_item(OfflineObjects::full_track(
+            &id.to_string().repeat(22),
+            "Song",
+            "Artist",
+            "Album",
+            Some("ISRC1"),
+            200_000,
+        ))
+    }
+
+    fn relink(item: &mut PlaylistItem, stored_id: char) {
+        let stored_id = stored_id.to_string().repeat(22);
+        if let Some(PlayableItem::Track(track)) = &mut item.track {
+            track.linked_from = serde_json::from_value(serde_json::json!({
+                "external_urls": {},
+                "href": format!("https://api.spotify.com/v1/tracks/{stored_id}"),
+                "id": stored_id,
+                "type": "track",
+                "uri": format!("spotify:track:{stored_id}")
+            }))
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn inspect_reports_every_kind_of_issue() {
+        let mut items = vec![track_item('A'), track_item('B'), track_item('C'), track_item('D'), track_item('E')];
+        items[1].track = None;
+        if let Some(PlayableItem::Track(track)) = &mut items[2].track {
+            track.is_playable = Some(false);
+            track.restrictions = serde_json::from_value(serde_json::json!({"reason": "market"})).unwrap();
+        }
+        if let Some(PlayableItem::Track(track)) = &mut items[3].track {
+            track.is_local = true;
+        }
+        relink(&mut items[4], 'F');
+
+        let problems = PlaylistHealth::inspect(&items);
+        let summary = problems
+            .iter()
+            .map(|problem| (problem.position, problem.issues_summary()))
+            .collect::<Vec<(usize, String)>>();
+        assert_eq!(
+            summary,
+            vec![
+                (1, "unavailable".to_string()),
+                (2, "unplayable, restricted (market)".to_string()),
+                (3, "local file".to_string()),
+                (4, "relinked".to_string()),
+            ]
+        );
+        assert!(!problems[0].is_replaceable());
+        assert!(problems[1].is_replaceable());
+        assert!(!problems[2].is_replaceable());
+        assert_eq!(problems[3].stored_id.as_deref(), Some("FFFFFFFFFFFFFFFFFFFFFF"));
+
+        let entry = problems[1].entry().unwrap();
+        assert_eq!(entry.isrc.as_deref(), Some("ISRC1"));
+        assert_eq!(entry.duration_ms, Some(200_000));
+    }
+
+    #[test]
+    fn relinked_items_are_swapped_for_the_track_that_plays() {
+        let mut items = vec![track_item('A'), track_item('B')];
+        relink(&mut items[0], 'F');
+        if let Some(PlayableItem::Track(track)) = &mut items[1].track {
+            track.name = "Other Song".to_string();
+            track.external_ids.insert("isrc".to_string(), "ISRC2".to_string());
+        }
+        let problem = PlaylistHealth::inspect(&items).remove(0);
+        assert_eq!(problem.issues, vec![HealthIssue::Relinked]);
+        let replacement = problem.track.clone().unwrap();
+        let rules = DuplicateRules::default();
+
+        let positioned = PlaylistXplr::positions_from_items(items.clone());
+        assert!(PlaylistHealth::accepts_replacement(&rules, &positioned, &problem, &replacement));
+
+        // Another copy of the same recording elsewhere in the playlist still blocks the swap.
+        let mut copied = items;
+        copied.push(track_item('A'));
+        let positioned = PlaylistXplr::positions_from_items(copied);
+        assert!(!PlaylistHealth::accepts_replacement(&rules, &positioned, &problem, &replacement));
+    }
+}
@@ -0,0 +1,224 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rspotify::model::SimplifiedPlaylist;
+use tracing::{Level, event};
+
+use crate::actions::playlists::compare::PlaylistDiff;
+use crate::actions::playlists::export::PlaylistExport;
+use crate::enums::fs::ProjectDirectories;
+
+/// The outcome of checking one playlist when the owned playlists are tracked.
+///
+/// # Fields
+///
+/// * `name` - The name of the playlist.
+/// * `recorded` - Whether a new version was recorded.
+/// * `diff` - The changes since the previous version, when a previous version exists.
+#[derive(Clone, Debug)]
+pub struct TrackedPlaylist {
+    pub name: String,
+    pub recorded: bool,
+    pub diff: Option<PlaylistDiff>,
+}
+
+/// The recorded versions of one playlist, oldest first.
+///
+/// Versions are [`PlaylistExport`]s kept by the SQLite backend next to the followed artists
+/// history. A new version is only recorded when the playlist's `snapshot_id` differs from the
+/// latest recorded one. Before SQLite, versions were JSON files in
+/// [`PlaylistHistory::legacy_directory`]; the backend imports them once.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::playlists::history::PlaylistHistory;
+///
+/// fn main() {
+///     let history = PlaylistHistory::new(versions);
+///     let version = history.at(PlaylistHistory::parse_moment("2024-03-01").unwrap());
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PlaylistHistory {
+    versions: Vec<PlaylistExport>,
+}
+
+impl PlaylistHistory {
+    /// The history made of `versions`, which are sorted by the time they were recorded.
+    pub fn new(mut versions: Vec<PlaylistExport>) -> Self {
+        versions.sort_by_key(Self::recorded_at);
+        PlaylistHistory { versions }
+    }
+
+    /// The directory versions were stored in as JSON files, one subdirectory per playlist:
+    /// `<data dir>/history`.
+    pub fn legacy_directory() -> PathBuf {
+        ProjectDirectories::Data.path().join("history")
+    }
+
+    /// Loads the JSON versions of one playlist from a directory of the legacy layout.
+    /// Unreadable files are logged and skipped.
+    pub fn load_directory(directory: &Path) -> Vec<PlaylistExport> {
+        let entries = match fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+        let mut paths = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .collect::<Vec<PathBuf>>();
+        paths.sort();
+        paths
+            .iter()
+            .filter_map(|path| match PlaylistExport::load(path) {
+                Ok(version) => Some(version),
+                Err(err) => {
+                    event!(Level::ERROR, "Could not read the version {:?}: {:?}", path, err);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// The time a version was recorded, falling back to now for an unparsable timestamp.
+    pub fn recorded_at(version: &PlaylistExport) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&version.exported_at)
+            .map(|recorded_at| recorded_at.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now())
+    }
+
+    /// Every recorded version, oldest first.
+    pub fn versions(&self) -> &[PlaylistExport] {
+        &self.versions
+    }
+
+    /// The most recently recorded version, if any.
+    pub fn latest(&self) -> Option<&PlaylistExport> {
+        self.versions.last()
+    }
+
+    /// The version the playlist had at `moment`: the last one recorded at or before it.
+    pub fn at(&self, moment: DateTime<Utc>) -> Option<&PlaylistExport> {
+        self.versions
+            .iter()
+            .take_while(|version| {
+                DateTime::parse_from_rfc3339(&version.exported_at)
+                    .is_ok_and(|recorded| recorded <= moment)
+            })
+            .last()
+    }
+
+    /// Whether `version` differs from the latest recorded version and should be recorded.
+    pub fn is_new(&self, version: &PlaylistExport) -> bool {
+        !self.latest().is_some_and(|latest| {
+            latest.snapshot_id.is_some() && latest.snapshot_id == version.snapshot_id
+        })
+    }
+
+    /// The changes between every pair of consecutive versions, oldest first.
+    pub fn changes(&self) -> Vec<(PlaylistExport, PlaylistDiff)> {
+        self.versions
+            .windows(2)
+            .map(|pair| (pair[1].clone(), PlaylistDiff::new(&pair[0], &pair[1])))
+            .collect()
+    }
+
+    /// Whether `playlist` changed since the latest recorded version.
+    pub fn is_outdated(&self, playlist: &SimplifiedPlaylist) -> bool {
+        self.latest()
+            .and_then(|latest| latest.snapshot_id.as_ref())
+            .is_none_or(|snapshot_id| *snapshot_id != playlist.snapshot_id)
+    }
+
+    /// Parses a moment given on the command line: an RFC 3339 timestamp, or a `YYYY-MM-DD`
+    /// date standing for the end of that day (UTC).
+    ///
+    /// # Example
+    /// ```
+    /// use spotify_assistant_core::actions::playlists::history::PlaylistHistory;
+    /// let moment = PlaylistHistory::parse_moment("2024-03-01").unwrap();
+    /// assert_eq!(moment.to_rfc3339(), "2024-03-01T23:59:59+00:00");
+    /// ```
+    pub fn parse_moment(input: &str) -> Result<DateTime<Utc>, String> {
+        let input = input.trim();
+        if let Ok(moment) = DateTime::parse_from_rfc3339(input) {
+            return Ok(moment.with_timezone(&Utc));
+        }
+        NaiveDate::parse_from_str(input, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(23, 59, 59))
+            .map(|moment| moment.and_utc())
+            .ok_or_else(|| format!("Invalid date: {input} (expected YYYY-MM-DD or RFC 3339)"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::playlist_format::PlaylistFormat;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn version(snapshot_id: &str, exported_at: &str, ids: &[char]) -> PlaylistExport {
+        let items = ids
+            .iter()
+            .map(|id| {
+                let track = OfflineObjects::full_track(&id.to_string().repeat(22), &id.to_string(), "Artist", "Album", None, 200_000);
+                OfflineObjects::playlist_item(track)
+            })
+            .collect();
+        let mut version = PlaylistExport::new("playlist".to_string(), "Road Trip".to_string(), items);
+        version.snapshot_id = Some(snapshot_id.to_string());
+        version.exported_at = exported_at.to_string();
+        version
+    }
+
+    #[test]
+    fn detects_new_snapshots_and_diffs_consecutive_versions() {
+        let history = PlaylistHistory::new(vec![
+            version("s2", "2024-02-01T10:00:00+00:00", &['A', 'C']),
+            version("s1", "2024-01-01T10:00:00+00:00", &['A', 'B']),
+        ]);
+        assert!(!history.is_new(&version("s2", "2024-03-01T10:00:00+00:00", &['A', 'C'])));
+        assert!(history.is_new(&version("s3", "2024-03-01T10:00:00+00:00", &['A'])));
+        assert!(PlaylistHistory::default().is_new(&version("s1", "2024-01-01T10:00:00+00:00", &['A'])));
+
+        let changes = history.changes();
+        assert_eq!(changes.len(), 1);
+        let (latest, diff) = &changes[0];
+        assert_eq!(latest.snapshot_id.as_deref(), Some("s2"));
+        assert_eq!(diff.only_left[0].name, "B");
+        assert_eq!(diff.only_right[0].name, "C");
+    }
+
+    #[test]
+    fn finds_the_version_at_a_moment() {
+        let history = PlaylistHistory::new(vec![
+            version("s1", "2024-01-01T10:00:00+00:00", &['A']),
+            version("s2", "2024-02-01T10:00:00+00:00", &['A', 'B']),
+        ]);
+
+        let at = |input: &str| {
+            history
+                .at(PlaylistHistory::parse_moment(input).unwrap())
+                .and_then(|version| version.snapshot_id.clone())
+        };
+        assert_eq!(at("2023-12-31"), None);
+        assert_eq!(at("2024-01-01"), Some("s1".to_string()));
+        assert_eq!(at("2024-02-01T09:00:00Z"), Some("s1".to_string()));
+        assert_eq!(at("2024-03-01"), Some("s2".to_string()));
+        assert!(PlaylistHistory::parse_moment("March").is_err());
+    }
+
+    #[test]
+    fn loads_legacy_json_versions() {
+        let directory = tempfile::tempdir().unwrap();
+        let first = version("s1", "2024-01-01T10:00:00+00:00", &['A']);
+        first
+            .export(&directory.path().join("20240101T100000.000Z.json"), PlaylistFormat::Json)
+            .unwrap();
+        fs::write(directory.path().join("20240102T100000.000Z.json"), "not json").unwrap();
+        assert_eq!(PlaylistHistory::load_directory(directory.path()), vec![first]);
+        assert!(PlaylistHistory::load_directory(&directory.path().join("missing")).is_empty());
+    }
+}
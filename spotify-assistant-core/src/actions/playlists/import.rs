@@ -0,0 +1,652 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rspotify::clients::BaseClient;
+use rspotify::model::{FullTrack, SearchResult, SearchType, TrackId};
+use rspotify::prelude::Id;
+use rspotify::{AuthCodeSpotify, ClientError, scopes};
+use tracing::{Level, event};
+
+use crate::enums::fs::ProjectDirectories;
+use crate::enums::playlist_format::ImportFormat;
+use crate::models::full_track_fingerprint::FullTrackFingerprint;
+use crate::traits::apis::Api;
+use crate::utilities::general::{csv_row, file_stem, normalize_for_matching, parse_csv, similarity};
+
+/// Confidence at or above which a candidate is accepted without asking.
+pub const AUTO_ACCEPT: f64 = 0.9;
+/// Candidates scoring below this are never offered as a match.
+pub const MIN_CANDIDATE: f64 = 0.5;
+/// How far the best candidate must lead the runner-up to be accepted without asking.
+const AUTO_ACCEPT_MARGIN: f64 = 0.05;
+/// Number of search results considered per query.
+const SEARCH_LIMIT: u32 = 10;
+
+/// A single entry read from an import file.
+///
+/// # Fields
+///
+/// * `line` - The one-based line (or, for CSV, record) number of the entry in the file.
+/// * `raw` - The entry as written in the file, used when reporting unmatched entries.
+/// * `title` - The track title.
+/// * `artist` - The artist as written in the file; may name several artists.
+/// * `album` - The album name, if the file provides one.
+/// * `isrc` - The ISRC, if the file provides one.
+/// * `duration_ms` - The duration in milliseconds, if the file provides one.
+/// * `track_id` - The Spotify track ID when the entry is a Spotify URI or link.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportEntry {
+    pub line: usize,
+    pub raw: String,
+    pub title: String,
+    pub artist: String,
+    pub album: Option<String>,
+    pub isrc: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub track_id: Option<String>,
+}
+
+impl ImportEntry {
+    /// Parses the contents of an import file.
+    ///
+    /// * `Csv` - The first record is a header. Columns are recognized by name (`title`/`name`/
+    ///   `track`, `artist`/`artists`, `album`, `isrc`, `duration_ms`/`duration`, `uri`/`url`).
+    ///   Without a recognizable header every record is read as `artist,title`.
+    /// * `M3u` - `#EXTINF:<seconds>,<Artist> - <Title>` lines describe the location that follows.
+    ///   Locations without `#EXTINF` are described by their file name.
+    /// * `Text` - One `Artist - Title` per line. Lines starting with `#` are comments.
+    ///
+    /// Spotify track URIs and `open.spotify.com` links are recognized in every format.
+    pub fn parse(contents: &str, format: ImportFormat) -> Vec<ImportEntry> {
+        match format {
+            ImportFormat::Csv => Self::parse_csv_records(contents),
+            ImportFormat::M3u => Self::parse_m3u(contents),
+            ImportFormat::Text => Self::parse_text(contents),
+        }
+    }
+
+    fn parse_csv_records(contents: &str) -> Vec<ImportEntry> {
+        let records = parse_csv(contents);
+        let Some(header) = records.first() else {
+            return Vec::new();
+        };
+        let column = |aliases: &[&str]| {
+            header
+                .iter()
+                .position(|name| aliases.contains(&normalize_for_matching(name).as_str()))
+        };
+        let title = column(&["title", "name", "track", "track name", "song", "song name"]);
+        let artist = column(&["artist", "artists", "artist name", "artist names", "artist name s"]);
+        let album = column(&["album", "album name"]);
+        let isrc = column(&["isrc"]);
+        let duration = column(&["duration ms", "duration", "length", "time"]);
+        let uri = column(&["uri", "track uri", "spotify uri", "url", "spotify url", "link"]);
+        let has_header = [title, artist, isrc, uri].iter().any(Option::is_some);
+        let (title, artist, skip) = if has_header {
+            (title, artist, 1)
+        } else {
+            (Some(1), Some(0), 0)
+        };
+
+        records
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .map(|(index, record)| {
+                let field = |column: Option<usize>| {
+                    column
+                        .and_then(|column| record.get(column))
+                        .map(|value| value.trim().to_string())
+                        .filter(|value| !value.is_empty())
+                };
+                ImportEntry {
+                    line: index + 1,
+                    raw: csv_row(record),
+                    title: field(title).unwrap_or_default(),
+                    artist: field(artist).unwrap_or_default(),
+                    album: field(album),
+                    isrc: field(isrc).map(|isrc| isrc.to_uppercase()),
+                    duration_ms: field(duration).and_then(|value| Self::parse_duration(&value)),
+                    track_id: field(uri).and_then(|value| Self::spotify_track_id(&value)),
+                }
+            })
+            .collect()
+    }
+
+    fn parse_m3u(contents: &str) -> Vec<ImportEntry> {
+        let mut entries = Vec::new();
+        let mut info: Option<(usize, String)> = None;
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim().trim_start_matches('\u{feff}');
+            if let Some(extinf) = line.strip_prefix("#EXTINF:") {
+                info = Some((index + 1, extinf.to_string()));
+                continue;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut entry = match info.take() {
+                Some((info_line, extinf)) => {
+                    let (seconds, description) = extinf.split_once(',').unwrap_or(("", &extinf));
+                    let mut entry = Self::from_description(info_line, description);
+                    entry.raw = format!("#EXTINF:{extinf} {line}");
+                    entry.duration_ms = seconds
+                        .trim()
+                        .parse::<i64>()
+                        .ok()
+                        .filter(|seconds| *seconds > 0)
+                        .map(|seconds| seconds * 1000);
+                    entry
+                }
+                None => {
+                    let name = line.rsplit(['/', '\\']).next().unwrap_or(line);
+                    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+                    let mut entry = Self::from_description(index + 1, stem);
+                    entry.raw = line.to_string();
+                    entry
+                }
+            };
+            entry.track_id = Self::spotify_track_id(line);
+            entries.push(entry);
+        }
+        entries
+    }
+
+    fn parse_text(contents: &str) -> Vec<ImportEntry> {
+        contents
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index, line.trim().trim_start_matches('\u{feff}')))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(index, line)| {
+                let mut entry = Self::from_description(index + 1, line);
+                entry.track_id = Self::spotify_track_id(line);
+                entry
+            })
+            .collect()
+    }
+
+    fn from_description(line: usize, description: &str) -> ImportEntry {
+        let description = description.trim();
+        let (artist, title) = [" - ", " – ", " — "]
+            .iter()
+            .find_map(|separator| description.split_once(separator))
+            .unwrap_or(("", description));
+        ImportEntry {
+            line,
+            raw: description.to_string(),
+            title: title.trim().to_string(),
+            artist: artist.trim().to_string(),
+            ..ImportEntry::default()
+        }
+    }
+
+    /// Reads a duration given as `m:ss`, as milliseconds, or as seconds (values below 10000).
+    fn parse_duration(value: &str) -> Option<i64> {
+        if let Some((minutes, seconds)) = value.split_once(':') {
+            let minutes = minutes.trim().parse::<i64>().ok()?;
+            let seconds = seconds.trim().parse::<f64>().ok()?;
+            return Some(minutes * 60_000 + (seconds * 1000.0) as i64);
+        }
+        let number = value.parse::<f64>().ok()?;
+        if number < 10_000.0 {
+            Some((number * 1000.0) as i64)
+        } else {
+            Some(number as i64)
+        }
+    }
+
+    fn spotify_track_id(value: &str) -> Option<String> {
+        let value = value.trim();
+        let id = if let Some(id) = value.strip_prefix("spotify:track:") {
+            id
+        } else {
+            let (_, rest) = value.split_once("open.spotify.com/")?;
+            let (_, id) = rest.split_once("track/")?;
+            id.split(['?', '/', '#']).next()?
+        };
+        TrackId::from_id(id).ok().map(|track_id| track_id.id().to_string())
+    }
+
+    /// A human-readable description of the entry, e.g. `Artist - Title`.
+    pub fn describe(&self) -> String {
+        match (self.artist.is_empty(), self.title.is_empty()) {
+            (false, false) => format!("{} - {}", self.artist, self.title),
+            (true, false) => self.title.clone(),
+            _ => self.raw.clone(),
+        }
+    }
+}
+
+/// A Spotify track that may correspond to an [`ImportEntry`].
+///
+/// # Fields
+///
+/// * `track` - The candidate track.
+/// * `confidence` - How likely the track is the entry, from `0.0` to `1.0`.
+#[derive(Clone, Debug)]
+pub struct MatchCandidate {
+    pub track: FullTrack,
+    pub confidence: f64,
+}
+
+/// The decision reached for an entry's candidates.
+///
+/// Variants:
+/// - `Matched`: A candidate was accepted.
+/// - `Ambiguous`: Several plausible candidates remain; the user has to choose.
+/// - `Unmatched`: No candidate is good enough.
+#[derive(Clone, Debug)]
+pub enum MatchOutcome {
+    Matched(Box<MatchCandidate>),
+    Ambiguous(Vec<MatchCandidate>),
+    Unmatched,
+}
+
+/// A matched import entry.
+#[derive(Clone, Debug)]
+pub struct ImportMatch {
+    pub entry: ImportEntry,
+    pub track: FullTrack,
+    pub confidence: f64,
+}
+
+/// The result of resolving the entries of an import file.
+#[derive(Clone, Debug, Default)]
+pub struct ImportReport {
+    pub matched: Vec<ImportMatch>,
+    pub unmatched: Vec<ImportEntry>,
+}
+
+impl ImportReport {
+    /// The matched tracks in file order.
+    pub fn tracks(&self) -> Vec<FullTrack> {
+        self.matched
+            .iter()
+            .map(|matched| matched.track.clone())
+            .collect()
+    }
+}
+
+/// Imports playlists exported from other services into Spotify.
+///
+/// Every entry is resolved to a Spotify track: Spotify URIs are used directly, ISRCs are
+/// searched next, and title/artist searches are scored last. The matched tracks are written
+/// with a [`PlaylistWriter`](crate::actions::playlists::writer::PlaylistWriter), which only
+/// adds to playlists the user owns and returns an error instead of panicking.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::playlists::import::{ImportEntry, PlaylistImporter};
+/// use spotify_assistant_core::actions::playlists::writer::PlaylistWriter;
+/// use spotify_assistant_core::enums::playlist_format::ImportFormat;
+///
+/// async fn main() {
+///     let entries = ImportEntry::parse(&contents, ImportFormat::Text);
+///     let importer = PlaylistImporter::new().await;
+///     let report = importer.resolve(entries, Some(0.8), |_, _| None).await;
+///     let writer = PlaylistWriter::new("Imported by spotify-assistant").await;
+///     let written = writer.append("Migrated", report.tracks()).await?;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PlaylistImporter {
+    client: AuthCodeSpotify,
+}
+
+impl Api for PlaylistImporter {
+    fn select_scopes() -> HashSet<String> {
+        scopes!("playlist-read-private", "playlist-read-collaborative")
+    }
+}
+
+impl PlaylistImporter {
+    pub async fn new() -> Self {
+        let span = tracing::span!(Level::INFO, "PlaylistImporter.new");
+        let _enter = span.enter();
+
+        PlaylistImporter {
+            client: Self::set_up_client(false, Some(Self::select_scopes())).await,
+        }
+    }
+
+    /// Scores how likely `track` is the track described by `entry`.
+    ///
+    /// A matching ISRC or track ID is a certain match. Otherwise the title (compared with and
+    /// without version suffixes), the artist and the duration are weighted 50/35/15; components
+    /// the entry does not provide are left out of the weighting.
+    pub fn score(entry: &ImportEntry, track: &FullTrack) -> f64 {
+        let track_id = track.id.as_ref().map(|id| id.id().to_string());
+        if entry.track_id.is_some() && entry.track_id == track_id {
+            return 1.0;
+        }
+        let track_isrc = track.external_ids.get("isrc").map(|isrc| isrc.to_uppercase());
+        if entry.isrc.is_some() && entry.isrc == track_isrc {
+            return 1.0;
+        }
+
+        let mut components = vec![(
+            0.5,
+            similarity(&entry.title, &track.name).max(similarity(
+                &FullTrackFingerprint::base_title(&entry.title),
+                &FullTrackFingerprint::base_title(&track.name),
+            )),
+        )];
+        if !entry.artist.is_empty() {
+            let names = track
+                .artists
+                .iter()
+                .map(|artist| artist.name.as_str())
+                .collect::<Vec<&str>>();
+            let lead = entry
+                .artist
+                .split([',', ';', '&'])
+                .next()
+                .unwrap_or(&entry.artist);
+            let best_single = names
+                .iter()
+                .map(|name| similarity(lead, name))
+                .fold(0.0, f64::max);
+            components.push((0.35, similarity(&entry.artist, &names.join(", ")).max(best_single)));
+        }
+        if let Some(duration_ms) = entry.duration_ms {
+            let difference = (duration_ms - track.duration.num_milliseconds()).abs();
+            let score = 1.0 - ((difference - 2_000).max(0) as f64 / 28_000.0).min(1.0);
+            components.push((0.15, score));
+        }
+        let weights = components.iter().map(|(weight, _)| weight).sum::<f64>();
+        components
+            .iter()
+            .map(|(weight, score)| weight * score)
+            .sum::<f64>()
+            / weights
+    }
+
+    /// Decides which candidate, if any, matches an entry.
+    ///
+    /// Candidates below [`MIN_CANDIDATE`] are dropped. With `min_confidence`, the best
+    /// candidate is accepted when it reaches that confidence and the entry is unmatched
+    /// otherwise, so no question is ever asked. Without it, the best candidate is accepted when
+    /// it reaches [`AUTO_ACCEPT`] and clearly leads the others; anything else is ambiguous.
+    pub fn decide(mut candidates: Vec<MatchCandidate>, min_confidence: Option<f64>) -> MatchOutcome {
+        candidates.retain(|candidate| candidate.confidence >= MIN_CANDIDATE);
+        candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        let Some(best) = candidates.first() else {
+            return MatchOutcome::Unmatched;
+        };
+        if let Some(min_confidence) = min_confidence {
+            return if best.confidence >= min_confidence {
+                MatchOutcome::Matched(Box::new(best.clone()))
+            } else {
+                MatchOutcome::Unmatched
+            };
+        }
+        let leads = candidates
+            .get(1)
+            .is_none_or(|runner_up| best.confidence - runner_up.confidence >= AUTO_ACCEPT_MARGIN);
+        if best.confidence >= AUTO_ACCEPT && leads {
+            MatchOutcome::Matched(Box::new(best.clone()))
+        } else {
+            MatchOutcome::Ambiguous(candidates)
+        }
+    }
+
+    /// Looks up the Spotify tracks that may correspond to `entry`, scored with [`Self::score`].
+    ///
+    /// A Spotify URI is fetched directly. Otherwise the ISRC is searched first and the
+    /// title/artist search only runs when the ISRC yields no certain match.
+    pub async fn candidates(&self, entry: &ImportEntry) -> Vec<MatchCandidate> {
+        let span = tracing::span!(Level::DEBUG, "PlaylistImporter.candidates");
+        let _enter = span.enter();
+
+        if let Some(track_id) = entry
+            .track_id
+            .as_deref()
+            .and_then(|track_id| TrackId::from_id(track_id).ok())
+        {
+            match self.client.track(track_id, Some(Self::market())).await {
+                Ok(track) => {
+                    return vec![MatchCandidate {
+                        track,
+                        confidence: 1.0,
+                    }];
+                }
+                Err(err) => event!(Level::WARN, "Could not fetch track {}: {:?}", entry.raw, err),
+            }
+        }
+
+        let mut tracks = Vec::new();
+        if let Some(isrc) = &entry.isrc {
+            tracks = self.search_tracks(&format!("isrc:{isrc}")).await;
+        }
+        let certain = tracks
+            .iter()
+            .any(|track| Self::score(entry, track) >= 1.0);
+        if !certain && !entry.title.is_empty() {
+            let title = entry.title.replace('"', "");
+            let query = if entry.artist.is_empty() {
+                format!("track:\"{title}\"")
+            } else {
+                format!("track:\"{title}\" artist:\"{}\"", entry.artist.replace('"', ""))
+            };
+            let mut found = self.search_tracks(&query).await;
+            if found.is_empty() {
+                found = self
+                    .search_tracks(&format!("{} {}", entry.artist, title).replace('"', ""))
+                    .await;
+            }
+            tracks.extend(found);
+        }
+
+        let mut seen = HashSet::new();
+        tracks
+            .into_iter()
+            .filter(|track| seen.insert(track.id.as_ref().map(|id| id.id().to_string())))
+            .map(|track| MatchCandidate {
+                confidence: Self::score(entry, &track),
+                track,
+            })
+            .collect()
+    }
+
+    async fn search_tracks(&self, query: &str) -> Vec<FullTrack> {
+        self.try_search_tracks(query).await.unwrap_or_else(|err| {
+            event!(Level::ERROR, "Error searching for {query}: {:?}", err);
+            Vec::new()
+        })
+    }
+
+    /// Searches the tracks with the given ISRC in the current market.
+    ///
+    /// # Errors
+    /// Returns the error of the search request, so callers can retry rather than treat the
+    /// ISRC as unknown.
+    pub async fn search_isrc(&self, isrc: &str) -> Result<Vec<FullTrack>, ClientError> {
+        self.try_search_tracks(&format!("isrc:{isrc}")).await
+    }
+
+    async fn try_search_tracks(&self, query: &str) -> Result<Vec<FullTrack>, ClientError> {
+        match self
+            .client
+            .search(
+                query.trim(),
+                SearchType::Track,
+                Some(Self::market()),
+                None,
+                Some(SEARCH_LIMIT),
+                None,
+            )
+            .await?
+        {
+            SearchResult::Tracks(page) => Ok(page.items),
+            other => {
+                event!(Level::WARN, "Unexpected search result: {:?}", other);
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// Resolves every entry to a track.
+    ///
+    /// Ambiguous entries are passed to `choose` together with their candidates (best first);
+    /// it returns the index of the chosen candidate, or `None` to leave the entry unmatched.
+    pub async fn resolve<F>(
+        &self,
+        entries: Vec<ImportEntry>,
+        min_confidence: Option<f64>,
+        mut choose: F,
+    ) -> ImportReport
+    where
+        F: FnMut(&ImportEntry, &[MatchCandidate]) -> Option<usize>,
+    {
+        let span = tracing::span!(Level::INFO, "PlaylistImporter.resolve");
+        let _enter = span.enter();
+
+        let mut report = ImportReport::default();
+        let total = entries.len();
+        for (index, entry) in entries.into_iter().enumerate() {
+            event!(Level::DEBUG, "Resolving entry {}/{}: {}", index + 1, total, entry.raw);
+            let candidates = self.candidates(&entry).await;
+            let chosen = match Self::decide(candidates, min_confidence) {
+                MatchOutcome::Matched(candidate) => Some(*candidate),
+                MatchOutcome::Ambiguous(candidates) => choose(&entry, &candidates)
+                    .and_then(|choice| candidates.get(choice).cloned()),
+                MatchOutcome::Unmatched => None,
+            };
+            match chosen {
+                Some(candidate) => report.matched.push(ImportMatch {
+                    entry,
+                    track: candidate.track,
+                    confidence: candidate.confidence,
+                }),
+                None => report.unmatched.push(entry),
+            }
+        }
+        report
+    }
+
+    /// Writes the unmatched entries of an import to `path`, one `line <n>: <entry>` per line.
+    pub fn write_unmatched(path: &Path, source: &Path, entries: &[ImportEntry]) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut lines = vec![format!("# Unmatched entries from {}", source.display())];
+        lines.extend(
+            entries
+                .iter()
+                .map(|entry| format!("line {}: {}", entry.line, entry.raw)),
+        );
+        fs::write(path, lines.join("\n") + "\n")?;
+        event!(Level::INFO, "Unmatched entries written to {:?}", path);
+        Ok(())
+    }
+
+    /// The default report location: `<data dir>/imports/<file stem>-unmatched.txt`.
+    pub fn default_unmatched_path(source: &Path) -> PathBuf {
+        let stem = source
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        ProjectDirectories::Data
+            .path()
+            .join("imports")
+            .join(format!("{}-unmatched.txt", file_stem(&stem)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn track(id: char, name: &str, artist: &str, isrc: Option<&str>, duration_ms: u32) -> FullTrack {
+        let id = id.to_string().repeat(22);
+        OfflineObjects::full_track(&id, name, artist, "Album", isrc, duration_ms)
+    }
+
+    fn candidate(id: char, confidence: f64) -> MatchCandidate {
+        MatchCandidate {
+            track: track(id, "Song", "Artist", None, 200_000),
+            confidence,
+        }
+    }
+
+    #[test]
+    fn parses_csv_with_named_columns() {
+        let contents = "Track Name,Artist Name(s),ISRC,Duration (ms)\n\
+                        \"Song, Pt. 2\",Band,usabc1234567,215000\n";
+        let entries = ImportEntry::parse(contents, ImportFormat::Csv);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].line, 2);
+        assert_eq!(entries[0].title, "Song, Pt. 2");
+        assert_eq!(entries[0].artist, "Band");
+        assert_eq!(entries[0].isrc.as_deref(), Some("USABC1234567"));
+        assert_eq!(entries[0].duration_ms, Some(215_000));
+    }
+
+    #[test]
+    fn parses_extended_m3u_and_spotify_uris() {
+        let uri = format!("spotify:track:{}", "A".repeat(22));
+        let contents = format!(
+            "#EXTM3U\n#EXTINF:215,Band - Song\n/music/song.mp3\n{uri}\n/music/Other - Tune.flac\n"
+        );
+        let entries = ImportEntry::parse(&contents, ImportFormat::M3u);
+        assert_eq!(entries.len(), 3);
+        assert_eq!((entries[0].artist.as_str(), entries[0].title.as_str()), ("Band", "Song"));
+        assert_eq!(entries[0].duration_ms, Some(215_000));
+        assert_eq!(entries[1].track_id, Some("A".repeat(22)));
+        assert_eq!((entries[2].artist.as_str(), entries[2].title.as_str()), ("Other", "Tune"));
+    }
+
+    #[test]
+    fn parses_plain_text_lines() {
+        let contents = "# migrated\nBand - Song\n\nJust A Title\n";
+        let entries = ImportEntry::parse(contents, ImportFormat::Text);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].line, 2);
+        assert_eq!(entries[0].describe(), "Band - Song");
+        assert_eq!(entries[1].artist, "");
+        assert_eq!(entries[1].title, "Just A Title");
+    }
+
+    #[test]
+    fn isrc_match_is_certain_and_metadata_is_weighted() {
+        let entry = ImportEntry {
+            title: "Song (Remastered)".to_string(),
+            artist: "Band".to_string(),
+            isrc: Some("ISRC1".to_string()),
+            duration_ms: Some(200_000),
+            ..ImportEntry::default()
+        };
+        let same_isrc = track('A', "Something Else", "Nobody", Some("ISRC1"), 1_000);
+        assert_eq!(PlaylistImporter::score(&entry, &same_isrc), 1.0);
+
+        let same_song = track('B', "Song", "Band", Some("ISRC2"), 201_000);
+        assert!((PlaylistImporter::score(&entry, &same_song) - 1.0).abs() < 1e-9);
+
+        let wrong_artist = track('C', "Song", "Someone", Some("ISRC3"), 200_000);
+        assert!(PlaylistImporter::score(&entry, &wrong_artist) < AUTO_ACCEPT);
+    }
+
+    #[test]
+    fn decide_accepts_clear_winners_and_defers_close_calls() {
+        let outcome = PlaylistImporter::decide(vec![candidate('A', 0.95), candidate('B', 0.6)], None);
+        assert!(matches!(outcome, MatchOutcome::Matched(ref matched) if matched.confidence == 0.95));
+
+        let outcome = PlaylistImporter::decide(vec![candidate('A', 0.93), candidate('B', 0.92)], None);
+        assert!(matches!(outcome, MatchOutcome::Ambiguous(ref candidates) if candidates.len() == 2));
+
+        let outcome = PlaylistImporter::decide(vec![candidate('A', 0.7), candidate('B', 0.3)], None);
+        assert!(matches!(outcome, MatchOutcome::Ambiguous(ref candidates) if candidates.len() == 1));
+
+        let outcome = PlaylistImporter::decide(vec![candidate('A', 0.7)], Some(0.8));
+        assert!(matches!(outcome, MatchOutcome::Unmatched));
+
+        let outcome = PlaylistImporter::decide(vec![candidate('A', 0.93), candidate('B', 0.92)], Some(0.8));
+        assert!(matches!(outcome, MatchOutcome::Matched(_)));
+
+        assert!(matches!(PlaylistImporter::decide(Vec::new(), None), MatchOutcome::Unmatched));
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::HashSet;
+
+use regex::Regex;
+use rspotify::clients::OAuthClient;
+use rspotify::model::{PlaylistId, SimplifiedPlaylist};
+use rspotify::{AuthCodeSpotify, scopes};
+use tracing::{Level, event};
+
+use crate::actions::playlists::user::UserPlaylists;
+use crate::traits::apis::Api;
+
+/// The metadata changes applied to every playlist matched by a [`PlaylistMetadataEditor`].
+///
+/// # Fields
+///
+/// * `rename` - The replacement for the matched part of the name. Capture groups can be
+///   referenced as `$1` or `${name}`.
+/// * `description` - The new description.
+/// * `public` - Whether the playlists become public or private.
+/// * `collaborative` - Whether the playlists become collaborative. Collaborative playlists are
+///   always made private, as Spotify requires.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MetadataChanges {
+    pub rename: Option<String>,
+    pub description: Option<String>,
+    pub public: Option<bool>,
+    pub collaborative: Option<bool>,
+}
+
+impl MetadataChanges {
+    pub fn is_empty(&self) -> bool {
+        self.rename.is_none()
+            && self.description.is_none()
+            && self.public.is_none()
+            && self.collaborative.is_none()
+    }
+}
+
+/// The change planned for one playlist. Fields are `None` when they are left untouched.
+///
+/// # Fields
+///
+/// * `id` - The ID of the playlist.
+/// * `name` - The current name of the playlist.
+/// * `new_name` - The name after the edit.
+/// * `description` - The description after the edit.
+/// * `public` - The visibility after the edit.
+/// * `collaborative` - The collaborative flag after the edit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedEdit {
+    pub id: PlaylistId<'static>,
+    pub name: String,
+    pub new_name: Option<String>,
+    pub description: Option<String>,
+    pub public: Option<bool>,
+    pub collaborative: Option<bool>,
+}
+
+/// Changes the name, description, visibility and collaborative flag of many owned playlists
+/// at once, selecting them with a regular expression over their names.
+///
+/// # Example
+/// ```no_run,ignore
+/// use regex::Regex;
+/// use spotify_assistant_core::actions::playlists::metadata::{MetadataChanges, PlaylistMetadataEditor};
+///
+/// async fn main() {
+///     let editor = PlaylistMetadataEditor::new().await;
+///     let changes = MetadataChanges { rename: Some("Mix $1".to_string()), ..Default::default() };
+///     let planned = editor.plan(&Regex::new("^Daily (\\d+)$").unwrap(), &changes).await;
+///     editor.apply(&planned).await;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PlaylistMetadataEditor {
+    client: AuthCodeSpotify,
+}
+
+impl Api for PlaylistMetadataEditor {
+    fn select_scopes() -> HashSet<String> {
+        scopes!(
+            "playlist-read-private",
+            "playlist-read-collaborative",
+            "playlist-modify-public",
+            "playlist-modify-private"
+        )
+    }
+}
+
+impl PlaylistMetadataEditor {
+    pub async fn new() -> Self {
+        let span = tracing::span!(Level::INFO, "PlaylistMetadataEditor.new");
+        let _enter = span.enter();
+
+        PlaylistMetadataEditor {
+            client: Self::set_up_client(false, Some(Self::select_scopes())).await,
+        }
+    }
+
+    /// Plans the changes for every owned playlist whose name matches `pattern`.
+    pub async fn plan(&self, pattern: &Regex, changes: &MetadataChanges) -> Vec<PlannedEdit> {
+        let span = tracing::span!(Level::INFO, "PlaylistMetadataEditor.plan");
+        let _enter = span.enter();
+
+        let owned = UserPlaylists::new().await.get_owned_playlists().await;
+        Self::plan_edits(&owned, pattern, changes)
+    }
+
+    /// Plans the changes for the playlists whose name matches `pattern`.
+    ///
+    /// Playlists the changes would leave as they are are not part of the plan. The
+    /// description is always considered a change since simplified playlists do not carry it.
+    pub fn plan_edits(
+        playlists: &[SimplifiedPlaylist],
+        pattern: &Regex,
+        changes: &MetadataChanges,
+    ) -> Vec<PlannedEdit> {
+        playlists
+            .iter()
+            .filter(|playlist| pattern.is_match(&playlist.name))
+            .filter_map(|playlist| {
+                let new_name = changes
+                    .rename
+                    .as_ref()
+                    .map(|replacement| pattern.replace_all(&playlist.name, replacement.as_str()).to_string())
+                    .filter(|new_name| new_name != &playlist.name);
+                let collaborative = changes
+                    .collaborative
+                    .filter(|&collaborative| collaborative != playlist.collaborative);
+                let public = match collaborative {
+                    Some(true) => Some(false),
+                    _ => changes.public,
+                }
+                .filter(|&public| Some(public) != playlist.public);
+                let edit = PlannedEdit {
+                    id: playlist.id.clone(),
+                    name: playlist.name.clone(),
+                    new_name,
+                    description: changes.description.clone(),
+                    public,
+                    collaborative,
+                };
+                let changed = edit.new_name.is_some()
+                    || edit.description.is_some()
+                    || edit.public.is_some()
+                    || edit.collaborative.is_some();
+                changed.then_some(edit)
+            })
+            .collect()
+    }
+
+    /// Sends the planned changes to Spotify, one request per playlist.
+    ///
+    /// # Returns
+    /// The number of playlists that were updated. Failed updates are logged and skipped.
+    pub async fn apply(&self, edits: &[PlannedEdit]) -> usize {
+        let span = tracing::span!(Level::INFO, "PlaylistMetadataEditor.apply");
+        let _enter = span.enter();
+
+        let mut updated = 0;
+        for edit in edits {
+            match self
+                .client
+                .playlist_change_detail(
+                    edit.id.clone(),
+                    edit.new_name.as_deref(),
+                    edit.public,
+                    edit.description.as_deref(),
+                    edit.collaborative,
+                )
+                .await
+            {
+                Ok(_) => {
+                    event!(Level::INFO, "Updated the details of {}", edit.name);
+                    updated += 1;
+                }
+                Err(err) => {
+                    event!(Level::ERROR, "Could not update {}: {:?}", edit.name, err);
+                }
+            }
+        }
+        updated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn playlists() -> Vec<SimplifiedPlaylist> {
+        vec![
+            OfflineObjects::simplified_playlist(&"A".repeat(22), "Daily 01", "me", true),
+            OfflineObjects::simplified_playlist(&"B".repeat(22), "Daily 02", "me", false),
+            OfflineObjects::simplified_playlist(&"C".repeat(22), "Workout", "me", true),
+        ]
+    }
+
+    #[test]
+    fn renames_with_capture_groups_and_skips_unchanged_playlists() {
+        let pattern = Regex::new(r"^Daily (\d+)$").unwrap();
+        let changes = MetadataChanges {
+            rename: Some("Mix #$1".to_string()),
+            public: Some(false),
+            ..MetadataChanges::default()
+        };
+        let planned = PlaylistMetadataEditor::plan_edits(&playlists(), &pattern, &changes);
+        assert_eq!(planned.len(), 2);
+        assert_eq!(planned[0].new_name.as_deref(), Some("Mix #01"));
+        assert_eq!(planned[0].public, Some(false));
+        assert_eq!(planned[1].public, None);
+
+        let unchanged = MetadataChanges {
+            public: Some(false),
+            ..MetadataChanges::default()
+        };
+        let planned = PlaylistMetadataEditor::plan_edits(&playlists(), &pattern, &unchanged);
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].name, "Daily 01");
+    }
+
+    #[test]
+    fn collaborative_playlists_are_made_private() {
+        let pattern = Regex::new("Workout").unwrap();
+        let changes = MetadataChanges {
+            collaborative: Some(true),
+            public: Some(true),
+            ..MetadataChanges::default()
+        };
+        let planned = PlaylistMetadataEditor::plan_edits(&playlists(), &pattern, &changes);
+        assert_eq!(planned[0].collaborative, Some(true));
+        assert_eq!(planned[0].public, Some(false));
+        assert!(MetadataChanges::default().is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+use chrono::{DateTime, Duration, Utc};
+use rspotify::model::{FullTrack, SavedTrack};
+use rspotify::prelude::Id;
+use tracing::{Level, event};
+
+use crate::actions::liked_songs::UserLibrary;
+use crate::actions::playlists::smart::SmartRefresh;
+use crate::actions::playlists::writer::PlaylistWriter;
+use crate::enums::mirror::MirrorPeriod;
+use crate::errors::playlists::PlaylistError;
+
+/// The naming template used for mirror playlists when none is given.
+pub const DEFAULT_MIRROR_TEMPLATE: &str = "Liked {period}";
+
+/// The liked songs of one period, in the order they were liked (newest first).
+///
+/// # Fields
+///
+/// * `period` - The label of the period: `2025`, `2025-10` or `last 90 days`.
+/// * `tracks` - The songs liked during the period.
+#[derive(Clone, Debug)]
+pub struct MirrorGroup {
+    pub period: String,
+    pub tracks: Vec<FullTrack>,
+}
+
+impl MirrorGroup {
+    /// Names the mirror playlist of the group.
+    ///
+    /// The template placeholder is `{period}` (the period label). The name is how the playlist
+    /// is found again on the next run, so it holds nothing that changes between runs.
+    ///
+    /// # Example
+    /// ```
+    /// use spotify_assistant_core::actions::playlists::mirror::MirrorGroup;
+    /// let group = MirrorGroup { period: "2025-10".to_string(), tracks: Vec::new() };
+    /// assert_eq!(group.name("Liked {period}"), "Liked 2025-10");
+    /// ```
+    pub fn name(&self, template: &str) -> String {
+        template.replace("{period}", &self.period)
+    }
+}
+
+/// Mirrors the liked songs into playlists grouped by the time they were liked, such as
+/// "Liked 2025", "Liked 2025-10" or "Liked last 90 days".
+///
+/// Every run replaces the contents of the mirror playlists, so songs liked or unliked since
+/// the previous run are added or removed. Playlists that are already up to date are left
+/// untouched.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::playlists::mirror::{DEFAULT_MIRROR_TEMPLATE, LibraryMirror};
+/// use spotify_assistant_core::enums::mirror::MirrorPeriod;
+///
+/// async fn main() {
+///     let mirror = LibraryMirror::new(MirrorPeriod::Year, DEFAULT_MIRROR_TEMPLATE).await;
+///     for refresh in mirror.mirror(None).await.unwrap() {
+///         println!("{}", refresh.summary());
+///     }
+/// }
+/// ```
+pub struct LibraryMirror {
+    writer: PlaylistWriter,
+    period: MirrorPeriod,
+    template: String,
+    force: bool,
+}
+
+impl LibraryMirror {
+    pub async fn new(period: MirrorPeriod, template: &str) -> Self {
+        let span = tracing::span!(Level::INFO, "LibraryMirror.new");
+        let _enter = span.enter();
+
+        LibraryMirror {
+            writer: PlaylistWriter::new("Mirror of your liked songs by spotify-assistant").await,
+            period,
+            template: template.to_string(),
+            force: false,
+        }
+    }
+
+    /// Whether to replace the contents of playlists spotify-assistant did not create, see
+    /// [`PlaylistWriter::replace`].
+    pub fn set_force(&mut self, force: bool) {
+        self.force = force;
+    }
+
+    /// Groups the liked songs by the period they were liked in.
+    ///
+    /// # Arguments
+    /// * `saved_tracks` - The liked songs, newest first.
+    /// * `period` - How the songs are grouped.
+    /// * `now` - The end of a `Recent` period.
+    /// * `since` - For years and months, the earliest period to keep (`YYYY` or `YYYY-MM`).
+    ///
+    /// # Returns
+    /// The groups, most recent period first. A `Recent` period always yields one group, even
+    /// when it is empty, so its playlist is emptied once nothing was liked recently.
+    pub fn group(
+        saved_tracks: &[SavedTrack],
+        period: MirrorPeriod,
+        now: DateTime<Utc>,
+        since: Option<&str>,
+    ) -> Vec<MirrorGroup> {
+        let playable = saved_tracks
+            .iter()
+            .filter(|saved_track| saved_track.track.id.is_some());
+        if let MirrorPeriod::Recent(days) = period {
+            let start = now - Duration::days(i64::from(days));
+            return vec![MirrorGroup {
+                period: format!("last {days} days"),
+                tracks: playable
+                    .filter(|saved_track| saved_track.added_at >= start)
+                    .map(|saved_track| saved_track.track.clone())
+                    .collect(),
+            }];
+        }
+        let format = match period {
+            MirrorPeriod::Month => "%Y-%m",
+            _ => "%Y",
+        };
+        let mut groups: Vec<MirrorGroup> = Vec::new();
+        for saved_track in playable {
+            let label = saved_track.added_at.format(format).to_string();
+            if since.is_some_and(|since| label.as_str() < since) {
+                continue;
+            }
+            match groups.iter_mut().find(|group| group.period == label) {
+                Some(group) => group.tracks.push(saved_track.track.clone()),
+                None => groups.push(MirrorGroup {
+                    period: label,
+                    tracks: vec![saved_track.track.clone()],
+                }),
+            }
+        }
+        groups.sort_by(|a, b| b.period.cmp(&a.period));
+        groups
+    }
+
+    /// The groups the current liked songs form. The library is synchronized first.
+    pub async fn plan(&self, since: Option<&str>) -> Vec<MirrorGroup> {
+        let span = tracing::span!(Level::INFO, "LibraryMirror.plan");
+        let _enter = span.enter();
+
+        let library = UserLibrary::new().await;
+        Self::group(&library.tracks(), self.period, Utc::now(), since)
+    }
+
+    /// Writes every group to its mirror playlist.
+    ///
+    /// # Errors
+    /// Returns the error of the first group that cannot be written, such as a playlist of the
+    /// same name not created by spotify-assistant when the mirror is not forced. The groups
+    /// before it are written.
+    pub async fn write(&self, groups: Vec<MirrorGroup>) -> Result<Vec<SmartRefresh>, PlaylistError> {
+        let span = tracing::span!(Level::INFO, "LibraryMirror.write");
+        let _enter = span.enter();
+
+        let mut refreshed = Vec::with_capacity(groups.len());
+        for group in groups {
+            let name = group.name(&self.template);
+            let current_ids = group
+                .tracks
+                .iter()
+                .filter_map(|track| track.id.as_ref().map(|id| id.id().to_string()))
+                .collect::<Vec<String>>();
+            let matched = group.tracks.len();
+            let written = self.writer.replace(&name, group.tracks, self.force).await?;
+            let (added, removed) = SmartRefresh::changes(&written.previous_ids, &current_ids);
+            event!(Level::INFO, "Mirrored {} liked songs into {}", matched, name);
+            refreshed.push(SmartRefresh {
+                name: written.name,
+                created: written.created,
+                matched,
+                written: written.written,
+                added,
+                removed,
+            });
+        }
+        Ok(refreshed)
+    }
+
+    /// Synchronizes the liked songs and brings every mirror playlist up to date.
+    pub async fn mirror(&self, since: Option<&str>) -> Result<Vec<SmartRefresh>, PlaylistError> {
+        let groups = self.plan(since).await;
+        self.write(groups).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn library() -> Vec<SavedTrack> {
+        vec![
+            OfflineObjects::saved_track('D', "2025-10-20T00:00:00Z"),
+            OfflineObjects::saved_track('C', "2025-10-02T00:00:00Z"),
+            OfflineObjects::saved_track('B', "2025-03-01T00:00:00Z"),
+            OfflineObjects::saved_track('A', "2024-12-31T23:00:00Z"),
+        ]
+    }
+
+    fn summary(groups: &[MirrorGroup]) -> Vec<(String, Vec<String>)> {
+        groups
+            .iter()
+            .map(|group| {
+                (
+                    group.period.clone(),
+                    group.tracks.iter().map(|track| track.name.clone()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn groups_by_year_and_month_newest_first() {
+        let now = Utc::now();
+        let by_year = LibraryMirror::group(&library(), MirrorPeriod::Year, now, None);
+        assert_eq!(
+            summary(&by_year),
+            vec![
+                ("2025".to_string(), vec!["D".to_string(), "C".to_string(), "B".to_string()]),
+                ("2024".to_string(), vec!["A".to_string()]),
+            ]
+        );
+        let by_month = LibraryMirror::group(&library(), MirrorPeriod::Month, now, Some("2025-03"));
+        assert_eq!(
+            by_month.iter().map(|group| group.period.as_str()).collect::<Vec<&str>>(),
+            vec!["2025-10", "2025-03"]
+        );
+        assert_eq!(by_month[0].name("Liked {period}"), "Liked 2025-10");
+    }
+
+    #[test]
+    fn recent_period_keeps_an_empty_group() {
+        let now = DateTime::parse_from_rfc3339("2025-10-21T00:00:00Z").unwrap().with_timezone(&Utc);
+        let recent = LibraryMirror::group(&library(), MirrorPeriod::Recent(30), now, None);
+        assert_eq!(
+            summary(&recent),
+            vec![("last 30 days".to_string(), vec!["D".to_string(), "C".to_string()])]
+        );
+        let later = now + Duration::days(365);
+        let recent = LibraryMirror::group(&library(), MirrorPeriod::Recent(30), later, None);
+        assert_eq!(recent.len(), 1);
+        assert!(recent[0].tracks.is_empty());
+    }
+}
@@ -1,3 +1,16 @@
 pub mod user;
 pub mod compare;
 pub mod query;
+pub mod duplicates;
+pub mod dedupe;
+pub mod export;
+pub mod import;
+pub mod writer;
+pub mod combine;
+pub mod smart;
+pub mod split;
+pub mod sort;
+pub mod history;
+pub mod metadata;
+pub mod health;
+pub mod mirror;
@@ -0,0 +1,199 @@
+use std::collections::{HashMap, HashSet};
+
+use rspotify::model::PlaylistId;
+use rspotify::prelude::Id;
+use tracing::{Level, event};
+
+use crate::actions::playlists::combine::CombineSource;
+use crate::actions::playlists::user::UserPlaylists;
+use crate::actions::playlists::writer::PlaylistWriter;
+use crate::errors::playlists::PlaylistError;
+use crate::models::smart_playlist::SmartPlaylist;
+
+/// The outcome of refreshing one generated playlist, such as a smart or a mirror playlist.
+///
+/// # Fields
+///
+/// * `name` - The name of the smart playlist.
+/// * `created` - Whether the Spotify playlist was created by the refresh.
+/// * `matched` - The number of source tracks that satisfied the rules, before any limit.
+/// * `written` - The number of tracks written to the playlist.
+/// * `added` - The number of tracks that were not in the playlist before the refresh.
+/// * `removed` - The number of tracks that are no longer in the playlist.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SmartRefresh {
+    pub name: String,
+    pub created: bool,
+    pub matched: usize,
+    pub written: usize,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl SmartRefresh {
+    /// Counts the tracks added and removed between the previous and the new contents.
+    pub fn changes(previous_ids: &[String], current_ids: &[String]) -> (usize, usize) {
+        let previous = previous_ids.iter().collect::<HashSet<&String>>();
+        let current = current_ids.iter().collect::<HashSet<&String>>();
+        (
+            current.difference(&previous).count(),
+            previous.difference(&current).count(),
+        )
+    }
+
+    pub fn summary(&self) -> String {
+        if self.created {
+            format!("{}: created with {} tracks", self.name, self.written)
+        } else if self.added == 0 && self.removed == 0 {
+            format!("{}: unchanged ({} tracks)", self.name, self.written)
+        } else {
+            format!(
+                "{}: +{} -{} ({} tracks)",
+                self.name, self.added, self.removed, self.written
+            )
+        }
+    }
+}
+
+/// Evaluates smart playlist definitions and writes the results to Spotify.
+///
+/// Sources are loaded once per refresher, so refreshing several definitions over the liked
+/// songs only loads the library once.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::playlists::smart::SmartRefresher;
+/// use spotify_assistant_core::models::smart_playlist::SmartPlaylists;
+///
+/// async fn main() {
+///     let mut refresher = SmartRefresher::new().await;
+///     for refresh in refresher.refresh_all(SmartPlaylists::load().unwrap().playlists()).await {
+///         println!("{}", refresh.summary());
+///     }
+/// }
+/// ```
+pub struct SmartRefresher {
+    writer: PlaylistWriter,
+    sources: HashMap<String, CombineSource>,
+    force: bool,
+}
+
+impl SmartRefresher {
+    pub async fn new() -> Self {
+        let span = tracing::span!(Level::INFO, "SmartRefresher.new");
+        let _enter = span.enter();
+
+        SmartRefresher {
+            writer: PlaylistWriter::new("Smart playlist by spotify-assistant").await,
+            sources: HashMap::new(),
+            force: false,
+        }
+    }
+
+    /// Whether to replace the contents of playlists spotify-assistant did not create, see
+    /// [`PlaylistWriter::replace`].
+    pub fn set_force(&mut self, force: bool) {
+        self.force = force;
+    }
+
+    /// Regenerates a smart playlist, replacing its contents with the tracks matching its rules.
+    ///
+    /// # Errors
+    /// Returns [`PlaylistError::NotFound`] if no playlist matches the source, and
+    /// [`PlaylistError::NotCreatedByTool`] if an existing playlist of the same name was not
+    /// created by spotify-assistant and the refresher is not forced. Failed writes return the
+    /// error of the request.
+    pub async fn refresh(&mut self, definition: &SmartPlaylist) -> Result<SmartRefresh, PlaylistError> {
+        let span = tracing::span!(Level::INFO, "SmartRefresher.refresh");
+        let _enter = span.enter();
+
+        let source = self.source(&definition.source).await?;
+        let matching = definition.matching(&source.tracks);
+        let matched = matching.len();
+        let tracks = definition.arrange(matching);
+        let current_ids = tracks
+            .iter()
+            .filter_map(|track| track.id.as_ref().map(|id| id.id().to_string()))
+            .collect::<Vec<String>>();
+
+        self.writer.set_description(&definition.describe());
+        let written = self
+            .writer
+            .replace(&definition.name, tracks, self.force)
+            .await?;
+        let (added, removed) = SmartRefresh::changes(&written.previous_ids, &current_ids);
+        event!(Level::INFO, "Refreshed smart playlist {}", definition.name);
+        Ok(SmartRefresh {
+            name: written.name,
+            created: written.created,
+            matched,
+            written: written.written,
+            added,
+            removed,
+        })
+    }
+
+    /// Refreshes every definition. Definitions that cannot be refreshed are logged and
+    /// skipped.
+    pub async fn refresh_all(&mut self, definitions: &[SmartPlaylist]) -> Vec<SmartRefresh> {
+        let mut refreshed = Vec::new();
+        for definition in definitions {
+            match self.refresh(definition).await {
+                Ok(refresh) => refreshed.push(refresh),
+                Err(err) => event!(Level::ERROR, "Could not refresh {}: {}", definition.name, err),
+            }
+        }
+        refreshed
+    }
+
+    async fn source(&mut self, input: &str) -> Result<&CombineSource, PlaylistError> {
+        let key = input.trim().to_lowercase();
+        if !self.sources.contains_key(&key) {
+            let source = if CombineSource::is_liked_songs(input) {
+                CombineSource::liked_songs().await
+            } else {
+                let playlist_id = match UserPlaylists::new().await.find_playlist(input).await {
+                    Some(playlist) => playlist.id,
+                    None => PlaylistId::from_id_or_uri(input.trim())
+                        .map(|id| id.into_static())
+                        .map_err(|_| PlaylistError::NotFound(input.to_string()))?,
+                };
+                CombineSource::playlist(playlist_id).await
+            };
+            self.sources.insert(key.clone(), source);
+        }
+        Ok(&self.sources[&key])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn changes_count_set_differences() {
+        assert_eq!(SmartRefresh::changes(&ids(&["a", "b", "c"]), &ids(&["b", "c", "d", "e"])), (2, 1));
+        assert_eq!(SmartRefresh::changes(&ids(&["a"]), &ids(&["a"])), (0, 0));
+    }
+
+    #[test]
+    fn summary_describes_the_refresh() {
+        let mut refresh = SmartRefresh {
+            name: "Deep Cuts".to_string(),
+            written: 12,
+            added: 3,
+            removed: 1,
+            ..SmartRefresh::default()
+        };
+        assert_eq!(refresh.summary(), "Deep Cuts: +3 -1 (12 tracks)");
+        refresh.added = 0;
+        refresh.removed = 0;
+        assert_eq!(refresh.summary(), "Deep Cuts: unchanged (12 tracks)");
+        refresh.created = true;
+        assert_eq!(refresh.summary(), "Deep Cuts: created with 12 tracks");
+    }
+}
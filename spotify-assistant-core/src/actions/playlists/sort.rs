@@ -0,0 +1,366 @@
+use std::cmp::Ordering;
+
+use rspotify::clients::OAuthClient;
+use rspotify::model::{FullTrack, PlayableItem, PlaylistId, PlaylistItem};
+use tracing::{Level, event};
+
+use crate::actions::exploration::playlist::PlaylistXplr;
+use crate::enums::sort::SortKey;
+use crate::errors::playlists::PlaylistError;
+
+/// A single call to the playlist reorder endpoint.
+///
+/// # Fields
+///
+/// * `range_start` - The position of the first item to move.
+/// * `range_length` - The number of consecutive items to move.
+/// * `insert_before` - The position the items are inserted before, counted before the move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReorderMove {
+    pub range_start: usize,
+    pub range_length: usize,
+    pub insert_before: usize,
+}
+
+impl ReorderMove {
+    /// Applies the move to a local copy of the playlist, the way the Spotify API applies it.
+    pub fn apply<T>(&self, items: &mut Vec<T>) {
+        let moved = items
+            .drain(self.range_start..self.range_start + self.range_length)
+            .collect::<Vec<T>>();
+        let destination = if self.insert_before > self.range_start {
+            self.insert_before - self.range_length
+        } else {
+            self.insert_before
+        };
+        items.splice(destination..destination, moved);
+    }
+}
+
+/// A comparable component of a sort key.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue {
+    Number(i64),
+    Text(String),
+}
+
+/// Sorts an existing playlist in place through the reorder endpoint.
+///
+/// Items are moved rather than replaced, so every item keeps its `added_at` and `added_by`.
+/// Only the items outside the longest run already in the target order are moved, and items
+/// that travel together are moved as one range.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::playlists::sort::PlaylistSorter;
+/// use spotify_assistant_core::enums::sort::SortKey;
+///
+/// async fn main() {
+///     let sorter = PlaylistSorter::new(playlist_id, SortKey::ReleaseDate, false).await;
+///     let moves = sorter.plan().await;
+///     sorter.apply(&moves).await.unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PlaylistSorter {
+    xplr: PlaylistXplr,
+    items: Vec<PlaylistItem>,
+    key: SortKey,
+    descending: bool,
+}
+
+impl PlaylistSorter {
+    pub async fn new(playlist_id: PlaylistId<'static>, key: SortKey, descending: bool) -> Self {
+        let span = tracing::span!(Level::INFO, "PlaylistSorter.new");
+        let _enter = span.enter();
+
+        let (xplr, items) = PlaylistXplr::with_items(playlist_id, false).await;
+        PlaylistSorter {
+            xplr,
+            items,
+            key,
+            descending,
+        }
+    }
+    pub fn name(&self) -> String {
+        self.xplr.full_playlist.name.clone()
+    }
+
+    /// Plans the moves that put the playlist items, as loaded by [`PlaylistSorter::new`], in
+    /// the target order.
+    pub async fn plan(&self) -> Vec<ReorderMove> {
+        let span = tracing::span!(Level::INFO, "PlaylistSorter.plan");
+        let _enter = span.enter();
+
+        Self::plan_moves(&Self::sort_order(&self.items, self.key, self.descending))
+    }
+
+    /// Computes the target order of the items.
+    ///
+    /// Sorting is stable, so items with equal keys keep their current relative order. Items
+    /// without a value for the key (e.g. episodes when sorting by album) go last in both
+    /// directions.
+    ///
+    /// # Returns
+    /// The current positions of the items, in target order.
+    pub fn sort_order(items: &[PlaylistItem], key: SortKey, descending: bool) -> Vec<usize> {
+        let keys = items
+            .iter()
+            .map(|item| Self::key(item, key))
+            .collect::<Vec<Option<Vec<SortValue>>>>();
+        let mut order = (0..items.len()).collect::<Vec<usize>>();
+        order.sort_by(|&a, &b| match (&keys[a], &keys[b]) {
+            (Some(a), Some(b)) if descending => b.cmp(a),
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+        order
+    }
+
+    fn key(item: &PlaylistItem, key: SortKey) -> Option<Vec<SortValue>> {
+        if key == SortKey::AddedAt {
+            return item
+                .added_at
+                .map(|added_at| vec![SortValue::Number(added_at.timestamp_millis())]);
+        }
+        let track = match &item.track {
+            Some(PlayableItem::Track(track)) => track,
+            _ => return None,
+        };
+        let text = |text: &str| SortValue::Text(text.to_lowercase());
+        let position = |track: &FullTrack| {
+            [
+                SortValue::Number(i64::from(track.disc_number)),
+                SortValue::Number(i64::from(track.track_number)),
+            ]
+        };
+        let values = match key {
+            SortKey::ReleaseDate => {
+                let mut values = vec![text(track.album.release_date.as_deref()?), text(&track.album.name)];
+                values.extend(position(track));
+                values
+            }
+            SortKey::Artist => {
+                let mut values = vec![text(&track.artists.first()?.name), text(&track.album.name)];
+                values.extend(position(track));
+                values
+            }
+            SortKey::Album => {
+                let mut values = vec![text(&track.album.name)];
+                values.extend(position(track));
+                values
+            }
+            SortKey::Popularity => vec![SortValue::Number(i64::from(track.popularity))],
+            SortKey::Duration => vec![SortValue::Number(track.duration.num_milliseconds())],
+            SortKey::AddedAt => unreachable!("handled above"),
+        };
+        Some(values)
+    }
+
+    /// Plans the range moves that turn the current order into `order`.
+    ///
+    /// The items on a longest increasing run of target ranks stay where they are. Every other
+    /// item is moved right behind the item that precedes it in the target order, together with
+    /// the following items when they are already adjacent and in order.
+    ///
+    /// # Arguments
+    /// * `order` - The current positions of the items, in target order (see [`Self::sort_order`]).
+    pub fn plan_moves(order: &[usize]) -> Vec<ReorderMove> {
+        let mut ranks = vec![0; order.len()];
+        for (rank, &position) in order.iter().enumerate() {
+            ranks[position] = rank;
+        }
+        let kept = Self::increasing_run(&ranks);
+
+        let mut current = ranks;
+        let mut moves = Vec::new();
+        let mut rank = 0;
+        while rank < current.len() {
+            if kept[rank] {
+                rank += 1;
+                continue;
+            }
+            let range_start = current
+                .iter()
+                .position(|&current_rank| current_rank == rank)
+                .expect("Every rank is in the playlist");
+            let mut range_length = 1;
+            while rank + range_length < current.len()
+                && !kept[rank + range_length]
+                && current.get(range_start + range_length) == Some(&(rank + range_length))
+            {
+                range_length += 1;
+            }
+            let insert_before = match rank {
+                0 => 0,
+                _ => {
+                    current
+                        .iter()
+                        .position(|&current_rank| current_rank == rank - 1)
+                        .expect("Every rank is in the playlist")
+                        + 1
+                }
+            };
+            if insert_before != range_start {
+                let reorder = ReorderMove {
+                    range_start,
+                    range_length,
+                    insert_before,
+                };
+                reorder.apply(&mut current);
+                moves.push(reorder);
+            }
+            rank += range_length;
+        }
+        moves
+    }
+
+    /// Marks the ranks on a longest increasing subsequence of `ranks`.
+    fn increasing_run(ranks: &[usize]) -> Vec<bool> {
+        let mut tails: Vec<usize> = Vec::new();
+        let mut previous: Vec<Option<usize>> = vec![None; ranks.len()];
+        for (index, rank) in ranks.iter().enumerate() {
+            let slot = tails.partition_point(|&tail| ranks[tail] < *rank);
+            previous[index] = slot.checked_sub(1).map(|slot| tails[slot]);
+            if slot == tails.len() {
+                tails.push(index);
+            } else {
+                tails[slot] = index;
+            }
+        }
+        let mut kept = vec![false; ranks.len()];
+        let mut cursor = tails.last().copied();
+        while let Some(index) = cursor {
+            kept[ranks[index]] = true;
+            cursor = previous[index];
+        }
+        kept
+    }
+
+    /// Sends the moves to Spotify in order, passing the snapshot ID returned by each move to
+    /// the next one.
+    ///
+    /// # Returns
+    /// The snapshot ID of the playlist after the last move. When a request fails, the error
+    /// carries the number of moves already applied and the snapshot ID they left behind.
+    pub async fn apply(&self, moves: &[ReorderMove]) -> Result<String, PlaylistError> {
+        let span = tracing::span!(Level::INFO, "PlaylistSorter.apply");
+        let _enter = span.enter();
+
+        let mut snapshot_id = self.xplr.full_playlist.snapshot_id.clone();
+        for (applied, reorder) in moves.iter().enumerate() {
+            match self
+                .xplr
+                .client
+                .playlist_reorder_items(
+                    self.xplr.playlist_id.clone(),
+                    Some(reorder.range_start as i32),
+                    Some(reorder.insert_before as i32),
+                    Some(reorder.range_length as u32),
+                    Some(snapshot_id.as_str()),
+                )
+                .await
+            {
+                Ok(result) => snapshot_id = result.snapshot_id,
+                Err(err) => {
+                    event!(Level::ERROR, "Error reordering playlist items: {:?}", err);
+                    return Err(PlaylistError::PartiallyReordered {
+                        applied,
+                        snapshot_id,
+                        source: err,
+                    });
+                }
+            }
+        }
+        event!(
+            Level::INFO,
+            "Sorted {} with {} moves",
+            self.xplr.full_playlist.name,
+            moves.len()
+        );
+        Ok(snapshot_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn item(id: char, artist: &str, release_date: &str, popularity: u32) -> PlaylistItem {
+        let mut track = OfflineObjects::full_track(&id.to_string().repeat(22), "Song", artist, "Album", None, 200_000);
+        track.album.release_date = Some(release_date.to_string());
+        track.popularity = popularity;
+        OfflineObjects::playlist_item(track)
+    }
+
+    fn sorted_by_plan(order: &[usize]) -> (Vec<usize>, usize) {
+        let moves = PlaylistSorter::plan_moves(order);
+        let mut items = (0..order.len()).collect::<Vec<usize>>();
+        for reorder in &moves {
+            reorder.apply(&mut items);
+        }
+        (items, moves.len())
+    }
+
+    #[test]
+    fn apply_matches_the_api_semantics() {
+        let mut items = vec!['a', 'b', 'c', 'd', 'e'];
+        ReorderMove { range_start: 0, range_length: 2, insert_before: 4 }.apply(&mut items);
+        assert_eq!(items, vec!['c', 'd', 'a', 'b', 'e']);
+        ReorderMove { range_start: 4, range_length: 1, insert_before: 0 }.apply(&mut items);
+        assert_eq!(items, vec!['e', 'c', 'd', 'a', 'b']);
+    }
+
+    #[test]
+    fn plans_reach_the_target_order_with_few_moves() {
+        assert_eq!(sorted_by_plan(&[0, 1, 2, 3]), (vec![0, 1, 2, 3], 0));
+        assert_eq!(sorted_by_plan(&[3, 0, 1, 2]), (vec![3, 0, 1, 2], 1));
+        assert_eq!(sorted_by_plan(&[2, 3, 0, 1]), (vec![2, 3, 0, 1], 1));
+        assert_eq!(sorted_by_plan(&[4, 3, 2, 1, 0]).0, vec![4, 3, 2, 1, 0]);
+        let order = vec![5, 2, 7, 0, 3, 6, 1, 4];
+        assert_eq!(sorted_by_plan(&order).0, order);
+    }
+
+    #[test]
+    fn every_permutation_is_sorted() {
+        fn permutations(items: Vec<usize>) -> Vec<Vec<usize>> {
+            if items.len() <= 1 {
+                return vec![items];
+            }
+            let mut result = Vec::new();
+            for index in 0..items.len() {
+                let mut rest = items.clone();
+                let first = rest.remove(index);
+                for mut permutation in permutations(rest) {
+                    permutation.insert(0, first);
+                    result.push(permutation);
+                }
+            }
+            result
+        }
+        for order in permutations((0..6).collect()) {
+            let (sorted, moves) = sorted_by_plan(&order);
+            assert_eq!(sorted, order);
+            assert!(moves < order.len());
+        }
+    }
+
+    #[test]
+    fn sort_order_is_stable_and_puts_missing_keys_last() {
+        let items = vec![
+            item('A', "Oasis", "1995", 40),
+            item('B', "Blur", "1994-04-25", 70),
+            item('C', "Oasis", "1994-08", 40),
+        ];
+        assert_eq!(PlaylistSorter::sort_order(&items, SortKey::Artist, false), vec![1, 0, 2]);
+        assert_eq!(PlaylistSorter::sort_order(&items, SortKey::ReleaseDate, false), vec![1, 2, 0]);
+        assert_eq!(PlaylistSorter::sort_order(&items, SortKey::Popularity, true), vec![1, 0, 2]);
+
+        let mut items = items;
+        items[1].added_at = None;
+        assert_eq!(PlaylistSorter::sort_order(&items, SortKey::AddedAt, true), vec![0, 2, 1]);
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::{HashMap, HashSet};
+
+use rspotify::model::{ArtistId, FullTrack};
+use rspotify::prelude::Id;
+use tracing::{Level, event};
+
+use crate::actions::general::{FullProfiles, FullProfilesClient};
+use crate::actions::playlists::combine::SourceTrack;
+use crate::enums::split::SplitBy;
+use crate::enums::validation::BatchLimits;
+
+/// The naming template used for child playlists when none is given.
+pub const DEFAULT_SPLIT_TEMPLATE: &str = "{playlist} - {group}";
+
+/// The group of tracks whose key cannot be determined, e.g. a lead artist without genres.
+const UNKNOWN_GROUP: &str = "Unknown";
+
+/// The tracks of a split playlist that share a key.
+#[derive(Clone, Debug)]
+pub struct SplitGroup {
+    pub key: String,
+    pub tracks: Vec<FullTrack>,
+}
+
+impl SplitGroup {
+    /// Names the child playlist of the group.
+    ///
+    /// The template placeholders are `{playlist}` (the source name), `{group}` (the group
+    /// key) and `{by}` (the split key). The name is how the child playlist is found again when
+    /// the split is repeated, so it holds nothing that changes between runs.
+    ///
+    /// # Example
+    /// ```
+    /// use spotify_assistant_core::actions::playlists::split::SplitGroup;
+    /// use spotify_assistant_core::enums::split::SplitBy;
+    /// let group = SplitGroup { key: "1990s".to_string(), tracks: Vec::new() };
+    /// assert_eq!(group.name("{playlist} ({group})", "Everything", SplitBy::Decade), "Everything (1990s)");
+    /// ```
+    pub fn name(&self, template: &str, playlist: &str, by: SplitBy) -> String {
+        template
+            .replace("{playlist}", playlist)
+            .replace("{group}", &self.key)
+            .replace("{by}", &by.to_string())
+    }
+}
+
+/// Splits one playlist into child playlists grouped by genre, decade, lead artist, added month
+/// or album type.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::playlists::split::PlaylistSplitter;
+/// use spotify_assistant_core::enums::split::SplitBy;
+///
+/// async fn main() {
+///     let genres = PlaylistSplitter::lead_artist_genres(&FullProfiles::new().await, &source.tracks).await;
+///     let groups = PlaylistSplitter::group(&source.tracks, SplitBy::Genre, &genres);
+///     let (kept, skipped) = PlaylistSplitter::partition(groups, 20);
+/// }
+/// ```
+pub struct PlaylistSplitter;
+
+impl PlaylistSplitter {
+    /// Looks up the genres of every lead artist of `tracks`, once per artist and in batches of
+    /// [`BatchLimits::Artists`]. A failed batch is logged and its artists end up without genres.
+    ///
+    /// # Returns
+    /// The genres keyed by artist ID.
+    pub async fn lead_artist_genres<C: FullProfilesClient>(
+        profiles: &FullProfiles<C>,
+        tracks: &[SourceTrack],
+    ) -> HashMap<String, Vec<String>> {
+        let span = tracing::span!(Level::INFO, "PlaylistSplitter.lead_artist_genres");
+        let _enter = span.enter();
+
+        let mut seen = HashSet::new();
+        let artist_ids = tracks
+            .iter()
+            .filter_map(|track| track.track.artists.first().and_then(|artist| artist.id.clone()))
+            .filter(|artist_id| seen.insert(artist_id.id().to_string()))
+            .collect::<Vec<ArtistId<'static>>>();
+        let mut genres = HashMap::with_capacity(artist_ids.len());
+        for chunk in artist_ids.chunks(BatchLimits::Artists.get_limit()) {
+            match profiles.artists(chunk.to_vec()).await {
+                Ok(artists) => {
+                    for artist in artists {
+                        genres.insert(artist.id.id().to_string(), artist.genres);
+                    }
+                }
+                Err(err) => event!(Level::ERROR, "Could not load a batch of artists: {:?}", err),
+            }
+        }
+        event!(Level::DEBUG, "Looked up the genres of {} artists", genres.len());
+        genres
+    }
+
+    /// Groups the tracks by `by`. `genres` is only used when splitting by genre.
+    ///
+    /// # Returns
+    /// The groups, largest first. Ties are ordered by key. Tracks keep their source order
+    /// within a group, and local files are skipped.
+    pub fn group(
+        tracks: &[SourceTrack],
+        by: SplitBy,
+        genres: &HashMap<String, Vec<String>>,
+    ) -> Vec<SplitGroup> {
+        let mut groups: Vec<SplitGroup> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for track in tracks.iter().filter(|track| track.track.id.is_some()) {
+            let key = Self::key(track, by, genres);
+            match positions.get(&key) {
+                Some(&position) => groups[position].tracks.push(track.track.clone()),
+                None => {
+                    positions.insert(key.clone(), groups.len());
+                    groups.push(SplitGroup {
+                        key,
+                        tracks: vec![track.track.clone()],
+                    });
+                }
+            }
+        }
+        groups.sort_by(|a, b| b.tracks.len().cmp(&a.tracks.len()).then_with(|| a.key.cmp(&b.key)));
+        groups
+    }
+
+    /// Separates the groups with at least `min_size` tracks from the smaller ones.
+    pub fn partition(groups: Vec<SplitGroup>, min_size: usize) -> (Vec<SplitGroup>, Vec<SplitGroup>) {
+        groups
+            .into_iter()
+            .partition(|group| group.tracks.len() >= min_size)
+    }
+
+    fn key(track: &SourceTrack, by: SplitBy, genres: &HashMap<String, Vec<String>>) -> String {
+        let key = match by {
+            SplitBy::Genre => Self::lead_artist_id(&track.track)
+                .and_then(|artist_id| genres.get(&artist_id))
+                .and_then(|genres| genres.first())
+                .cloned(),
+            SplitBy::Decade => track
+                .track
+                .album
+                .release_date
+                .as_deref()
+                .and_then(|date| date.get(..4))
+                .and_then(|year| year.parse::<u32>().ok())
+                .map(|year| format!("{}s", year / 10 * 10)),
+            SplitBy::LeadArtist => track
+                .track
+                .artists
+                .first()
+                .map(|artist| artist.name.clone()),
+            SplitBy::AddedMonth => track
+                .added_at
+                .map(|added_at| added_at.format("%Y-%m").to_string()),
+            SplitBy::AlbumType => track.track.album.album_type.clone(),
+        };
+        key.filter(|key| !key.is_empty())
+            .unwrap_or_else(|| UNKNOWN_GROUP.to_string())
+    }
+
+    fn lead_artist_id(track: &FullTrack) -> Option<String> {
+        track
+            .artists
+            .first()
+            .and_then(|artist| artist.id.as_ref())
+            .map(|artist_id| artist_id.id().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+    use chrono::DateTime;
+
+    fn track(id: char, artist: &str, release_date: &str, added_at: i64) -> SourceTrack {
+        let mut track = OfflineObjects::full_track(&id.to_string().repeat(22), "Song", artist, "Album", None, 200_000);
+        track.album.release_date = Some(release_date.to_string());
+        SourceTrack {
+            track,
+            added_at: DateTime::from_timestamp(added_at, 0),
+        }
+    }
+
+    fn sizes(groups: &[SplitGroup]) -> Vec<(String, usize)> {
+        groups
+            .iter()
+            .map(|group| (group.key.clone(), group.tracks.len()))
+            .collect()
+    }
+
+    fn tracks() -> Vec<SourceTrack> {
+        vec![
+            track('A', "Blur", "1994-04-25", 1_700_000_000),
+            track('B', "Oasis", "1995", 1_700_000_000),
+            track('C', "Blur", "2003-05", 1_710_000_000),
+            track('D', "Burial", "", 1_710_000_000),
+        ]
+    }
+
+    #[test]
+    fn groups_by_decade_and_added_month() {
+        let tracks = tracks();
+        let by_decade = PlaylistSplitter::group(&tracks, SplitBy::Decade, &HashMap::new());
+        assert_eq!(
+            sizes(&by_decade),
+            vec![("1990s".to_string(), 2), ("2000s".to_string(), 1), ("Unknown".to_string(), 1)]
+        );
+        let by_month = PlaylistSplitter::group(&tracks, SplitBy::AddedMonth, &HashMap::new());
+        assert_eq!(
+            sizes(&by_month),
+            vec![("2023-11".to_string(), 2), ("2024-03".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn groups_by_lead_artist_genre() {
+        let tracks = tracks();
+        let lead_artist = |index: usize| PlaylistSplitter::lead_artist_id(&tracks[index].track).unwrap();
+        let genres = HashMap::from([
+            (lead_artist(0), vec!["britpop".to_string(), "rock".to_string()]),
+            (lead_artist(1), vec!["britpop".to_string()]),
+            (lead_artist(3), Vec::new()),
+        ]);
+        let groups = PlaylistSplitter::group(&tracks, SplitBy::Genre, &genres);
+        assert_eq!(sizes(&groups), vec![("britpop".to_string(), 3), ("Unknown".to_string(), 1)]);
+
+        let (kept, skipped) = PlaylistSplitter::partition(groups, 2);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(skipped[0].key, "Unknown");
+        assert_eq!(
+            kept[0].name(DEFAULT_SPLIT_TEMPLATE, "Everything", SplitBy::Genre),
+            "Everything - britpop"
+        );
+    }
+}
@@ -1,11 +1,58 @@
+use crate::actions::exploration::playlist::PlaylistXplr;
+use crate::enums::validation::BatchLimits;
+use crate::models::configuration::Duplicates;
 use crate::paginator::PaginatorRunner;
 use crate::traits::apis::Api;
 use rspotify::clients::{BaseClient, OAuthClient};
-use rspotify::model::{FullPlaylist, PlaylistId, SimplifiedPlaylist};
-use rspotify::{scopes, AuthCodeSpotify};
+use rspotify::model::{FullPlaylist, FullTrack, Id, ItemPositions, PlayableId, PlaylistId, SimplifiedPlaylist, TrackId};
+use rspotify::{scopes, AuthCodeSpotify, ClientError};
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
 use tracing::{event, Level};
 
+/// A machine-readable summary of one playlist in the user's library.
+///
+/// # Fields
+///
+/// * `id` - The ID of the playlist.
+/// * `name` - The name of the playlist.
+/// * `owner` - The display name of the owner, or their user ID when no display name is set.
+/// * `owner_id` - The user ID of the owner.
+/// * `public` - Whether the playlist is public. `None` when Spotify does not report it.
+/// * `collaborative` - Whether the playlist is collaborative.
+/// * `tracks` - The number of items in the playlist.
+/// * `snapshot_id` - The version identifier of the playlist.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PlaylistSummary {
+    pub id: String,
+    pub name: String,
+    pub owner: String,
+    pub owner_id: String,
+    pub public: Option<bool>,
+    pub collaborative: bool,
+    pub tracks: u32,
+    pub snapshot_id: String,
+}
+
+impl From<&SimplifiedPlaylist> for PlaylistSummary {
+    fn from(playlist: &SimplifiedPlaylist) -> Self {
+        PlaylistSummary {
+            id: playlist.id.id().to_string(),
+            name: playlist.name.clone(),
+            owner: playlist
+                .owner
+                .display_name
+                .clone()
+                .unwrap_or_else(|| playlist.owner.id.id().to_string()),
+            owner_id: playlist.owner.id.id().to_string(),
+            public: playlist.public,
+            collaborative: playlist.collaborative,
+            tracks: playlist.tracks.total,
+            snapshot_id: playlist.snapshot_id.clone(),
+        }
+    }
+}
+
 /// The `UserPlaylists` struct is a representation of user playlists
 /// within the Spotify API integration. It enables interaction with
 /// a user's playlists utilizing an authenticated Spotify client.
@@ -33,7 +80,13 @@ pub struct UserPlaylists {
 
 impl Api for UserPlaylists {
     fn select_scopes() -> HashSet<String> {
-        scopes!("playlist-read-private", "playlist-read-collaborative", "user-library-read")
+        scopes!(
+            "playlist-read-private",
+            "playlist-read-collaborative",
+            "playlist-modify-public",
+            "playlist-modify-private",
+            "user-library-read"
+        )
     }
 }
 
@@ -310,4 +363,273 @@ impl UserPlaylists {
             Vec::new()
         })
     }
+
+    /// Fetches the playlists owned by the current user.
+    ///
+    /// `get_user_playlists` also returns playlists the user merely follows. Write-oriented
+    /// features (deduplication, reports, bulk edits) should only operate on playlists the user
+    /// owns, so this method filters the list down by comparing each playlist's owner against
+    /// the authenticated user.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<SimplifiedPlaylist>` containing only the playlists owned by the current user. If the
+    /// current user cannot be retrieved, the error is logged and an empty vector is returned.
+    ///
+    /// # Example
+    /// ```no_run,ignore
+    /// use spotify_assistant_core::actions::playlists::user::UserPlaylists;
+    /// async fn main() {
+    ///     let user_playlists = UserPlaylists::new().await;
+    ///     for playlist in user_playlists.get_owned_playlists().await {
+    ///         println!("{} ({} tracks)", playlist.name, playlist.tracks.total);
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_owned_playlists(&self) -> Vec<SimplifiedPlaylist> {
+        let span = tracing::span!(Level::INFO, "UserPlaylists.get_owned_playlists");
+        let _enter = span.enter();
+
+        let user_id = match self.client.me().await {
+            Ok(user) => user.id,
+            Err(err) => {
+                event!(Level::ERROR, "Could not retrieve the current user: {:?}", err);
+                return Vec::new();
+            }
+        };
+        let owned = self
+            .get_user_playlists()
+            .await
+            .into_iter()
+            .filter(|playlist| playlist.owner.id == user_id)
+            .collect::<Vec<SimplifiedPlaylist>>();
+        event!(Level::DEBUG, "Owned playlists: {}", owned.len());
+        owned
+    }
+
+    /// Finds one of the user's playlists by name, ID or URI.
+    ///
+    /// Names are compared case-insensitively and with surrounding whitespace trimmed. IDs and
+    /// URIs only match playlists in the user's library, so callers that also accept playlists
+    /// the user does not follow should fall back to parsing the input as an ID.
+    ///
+    /// # Returns
+    ///
+    /// The matching `SimplifiedPlaylist`, or `None` when nothing in the library matches.
+    ///
+    /// # Example
+    /// ```no_run,ignore
+    /// use spotify_assistant_core::actions::playlists::user::UserPlaylists;
+    /// async fn main() {
+    ///     let user_playlists = UserPlaylists::new().await;
+    ///     if let Some(playlist) = user_playlists.find_playlist("Road Trip").await {
+    ///         println!("{}", playlist.id);
+    ///     }
+    /// }
+    /// ```
+    pub async fn find_playlist(&self, input: &str) -> Option<SimplifiedPlaylist> {
+        let span = tracing::span!(Level::INFO, "UserPlaylists.find_playlist");
+        let _enter = span.enter();
+
+        Self::match_playlist(&self.get_user_playlists().await, input)
+    }
+
+    /// Offline counterpart of [`UserPlaylists::find_playlist`] operating on an already
+    /// retrieved list of playlists. A name match wins over an ID match.
+    pub fn match_playlist(playlists: &[SimplifiedPlaylist], input: &str) -> Option<SimplifiedPlaylist> {
+        let normalized = input.trim().to_lowercase();
+        let id = PlaylistId::from_id_or_uri(input.trim())
+            .ok()
+            .map(|id| id.id().to_string());
+        playlists
+            .iter()
+            .find(|playlist| playlist.name.trim().to_lowercase() == normalized)
+            .or_else(|| {
+                playlists
+                    .iter()
+                    .find(|playlist| Some(playlist.id.id().to_string()) == id)
+            })
+            .cloned()
+    }
+
+    /// Creates a playlist for the current user.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the new playlist.
+    /// * `description` - The description of the new playlist, if any.
+    /// * `public` - Whether the playlist is public.
+    ///
+    /// # Errors
+    /// Returns the error of loading the current user or creating the playlist.
+    pub async fn create_playlist(&self, name: &str, description: Option<&str>, public: bool) -> Result<FullPlaylist, ClientError> {
+        let span = tracing::span!(Level::INFO, "UserPlaylists.create_playlist");
+        let _enter = span.enter();
+
+        let user = self.client.me().await?;
+        let playlist = self
+            .client
+            .user_playlist_create(user.id, name, Some(public), Some(false), description)
+            .await?;
+        event!(Level::INFO, "Created playlist {} ({})", playlist.name, playlist.id.id());
+        Ok(playlist)
+    }
+
+    /// Appends tracks to a playlist in batches of at most 100, skipping the ones the
+    /// playlist's duplicate rules in `duplicates` reject.
+    ///
+    /// # Returns
+    /// The tracks that were added.
+    ///
+    /// # Errors
+    /// Returns the error of the first batch that cannot be added; the batches before it are
+    /// added.
+    pub async fn add_tracks(
+        &self,
+        playlist_id: &PlaylistId<'static>,
+        tracks: Vec<FullTrack>,
+        duplicates: &Duplicates,
+    ) -> Result<Vec<FullTrack>, ClientError> {
+        let span = tracing::span!(Level::INFO, "UserPlaylists.add_tracks");
+        let _enter = span.enter();
+
+        let xplorer = PlaylistXplr::new(playlist_id.clone(), false).await;
+        let rules = duplicates.rules_for(playlist_id.id(), &xplorer.full_playlist.name);
+        let additions = rules.filter_additions(&xplorer.tracks(), tracks);
+        let track_ids = additions
+            .iter()
+            .filter_map(|track| track.id.clone())
+            .collect::<Vec<TrackId<'static>>>();
+        for chunk in track_ids.chunks(BatchLimits::ModifyPlaylistItems.get_limit()) {
+            self.client
+                .playlist_add_items(
+                    playlist_id.clone(),
+                    chunk.iter().map(|track_id| PlayableId::Track(track_id.clone())),
+                    None,
+                )
+                .await?;
+        }
+        event!(Level::DEBUG, "Added {} tracks to {}", track_ids.len(), playlist_id.id());
+        Ok(additions)
+    }
+
+    /// Removes every occurrence of the tracks from a playlist in batches of at most 100.
+    ///
+    /// # Errors
+    /// Returns the error of the first batch that cannot be removed; the batches before it
+    /// are removed.
+    pub async fn remove_tracks(&self, playlist_id: &PlaylistId<'static>, track_ids: &[TrackId<'static>]) -> Result<(), ClientError> {
+        let span = tracing::span!(Level::INFO, "UserPlaylists.remove_tracks");
+        let _enter = span.enter();
+
+        for chunk in track_ids.chunks(BatchLimits::ModifyPlaylistItems.get_limit()) {
+            self.client
+                .playlist_remove_all_occurrences_of_items(
+                    playlist_id.clone(),
+                    chunk.iter().map(|track_id| PlayableId::Track(track_id.clone())),
+                    None,
+                )
+                .await?;
+        }
+        event!(Level::DEBUG, "Removed {} tracks from {}", track_ids.len(), playlist_id.id());
+        Ok(())
+    }
+
+    /// Moves tracks from one playlist to another.
+    ///
+    /// The tracks are appended to `to` before they are removed from `from`, so an interrupted
+    /// move leaves the tracks in both playlists rather than in neither. Tracks the duplicate
+    /// rules of `to` reject are already there, so they are only removed from `from`.
+    ///
+    /// The occurrences in `from` are removed by position against the snapshot read before the
+    /// move, from the highest position down, so copies added to `from` in the meantime stay.
+    ///
+    /// # Returns
+    /// The tracks that were added to `to`.
+    ///
+    /// # Errors
+    /// Returns the error of adding or removing a batch of tracks.
+    pub async fn move_tracks(
+        &self,
+        from: &PlaylistId<'static>,
+        to: &PlaylistId<'static>,
+        tracks: Vec<FullTrack>,
+        duplicates: &Duplicates,
+    ) -> Result<Vec<FullTrack>, ClientError> {
+        let span = tracing::span!(Level::INFO, "UserPlaylists.move_tracks");
+        let _enter = span.enter();
+
+        let moved_ids = tracks
+            .iter()
+            .filter_map(|track| track.id.as_ref().map(|id| id.id().to_string()))
+            .collect::<HashSet<String>>();
+        let (source, items) = PlaylistXplr::with_items(from.clone(), false).await;
+        let mut occurrences = PlaylistXplr::positions_from_items(items)
+            .into_iter()
+            .filter_map(|(position, track)| track.id.map(|track_id| (position as u32, track_id)))
+            .filter(|(_, track_id)| moved_ids.contains(track_id.id()))
+            .collect::<Vec<(u32, TrackId<'static>)>>();
+        occurrences.sort_by_key(|(position, _)| std::cmp::Reverse(*position));
+
+        let added = self.add_tracks(to, tracks, duplicates).await?;
+        let mut snapshot_id = source.full_playlist.snapshot_id.clone();
+        for batch in occurrences.chunks(BatchLimits::ModifyPlaylistItems.get_limit()) {
+            let mut grouped: Vec<(TrackId<'static>, Vec<u32>)> = Vec::new();
+            for (position, track_id) in batch {
+                match grouped.iter_mut().find(|(grouped_id, _)| grouped_id == track_id) {
+                    Some((_, positions)) => positions.push(*position),
+                    None => grouped.push((track_id.clone(), vec![*position])),
+                }
+            }
+            let items = grouped
+                .iter()
+                .map(|(track_id, positions)| ItemPositions {
+                    id: PlayableId::Track(track_id.as_ref()),
+                    positions,
+                })
+                .collect::<Vec<ItemPositions>>();
+            snapshot_id = self
+                .client
+                .playlist_remove_specific_occurrences_of_items(from.clone(), items, Some(snapshot_id.as_str()))
+                .await?
+                .snapshot_id;
+        }
+        event!(Level::INFO, "Moved {} tracks from {} to {}", moved_ids.len(), from.id(), to.id());
+        Ok(added)
+    }
+
+    /// Removes a playlist from the user's library.
+    ///
+    /// Spotify has no delete operation: unfollowing an owned playlist is how it is deleted.
+    ///
+    /// # Errors
+    /// Returns the error of unfollowing the playlist.
+    pub async fn unfollow(&self, playlist_id: &PlaylistId<'static>) -> Result<(), ClientError> {
+        let span = tracing::span!(Level::INFO, "UserPlaylists.unfollow");
+        let _enter = span.enter();
+
+        self.client.playlist_unfollow(playlist_id.clone()).await?;
+        event!(Level::INFO, "Unfollowed playlist {}", playlist_id.id());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    #[test]
+    fn summarizes_playlists_for_machine_readable_output() {
+        let playlist = OfflineObjects::simplified_playlist(&"A".repeat(22), "Road Trip", "someone", true);
+        let summary = PlaylistSummary::from(&playlist);
+        assert_eq!(summary.id, "A".repeat(22));
+        assert_eq!(summary.owner, "someone");
+        assert_eq!(summary.public, Some(true));
+        assert_eq!(summary.tracks, 0);
+        assert_eq!(summary.snapshot_id, "snapshot");
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["name"], "Road Trip");
+        assert_eq!(json["collaborative"], false);
+    }
 }
@@ -0,0 +1,255 @@
+use std::collections::HashSet;
+
+use rspotify::clients::{BaseClient, OAuthClient};
+use rspotify::model::{FullTrack, PlayableId, PlaylistId, TrackId};
+use rspotify::prelude::Id;
+use rspotify::{AuthCodeSpotify, scopes};
+use tracing::{Level, event};
+
+use crate::actions::exploration::playlist::PlaylistXplr;
+use crate::actions::playlists::user::UserPlaylists;
+use crate::enums::validation::BatchLimits;
+use crate::errors::playlists::PlaylistError;
+use crate::models::configuration::Duplicates;
+use crate::traits::apis::Api;
+
+/// Marks the descriptions of the playlists the writer creates. Only playlists whose
+/// description carries it are replaced without being forced.
+pub const CREATED_BY_MARKER: &str = "spotify-assistant";
+
+/// The playlist a [`PlaylistWriter`] wrote to.
+///
+/// # Fields
+///
+/// * `id` - The ID of the playlist.
+/// * `name` - The name of the playlist.
+/// * `created` - Whether the playlist was created by the write.
+/// * `written` - The number of tracks written.
+/// * `previous_ids` - The IDs of the tracks the playlist held before the write.
+#[derive(Clone, Debug)]
+pub struct WrittenPlaylist {
+    pub id: PlaylistId<'static>,
+    pub name: String,
+    pub created: bool,
+    pub written: usize,
+    pub previous_ids: Vec<String>,
+}
+
+/// Writes generated track lists into a playlist the user owns, looked up by name or ID.
+///
+/// A private playlist is created when the user owns no playlist matching the given name; its
+/// description carries [`CREATED_BY_MARKER`]. The contents of a playlist without the marker
+/// are only replaced when forced. The target playlist's
+/// [`DuplicateRules`](crate::models::duplicate_rules::DuplicateRules) are applied to
+/// every write, looked up in the [`Duplicates`] settings loaded when the writer is created.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::playlists::writer::PlaylistWriter;
+///
+/// async fn main() {
+///     let writer = PlaylistWriter::new("Created by spotify-assistant").await;
+///     let written = writer.replace("Workout minus Running", tracks, false).await.unwrap();
+///     println!("Wrote {} tracks to {}", written.written, written.name);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PlaylistWriter {
+    client: AuthCodeSpotify,
+    description: String,
+    duplicates: Duplicates,
+}
+
+impl Api for PlaylistWriter {
+    fn select_scopes() -> HashSet<String> {
+        scopes!(
+            "playlist-read-private",
+            "playlist-read-collaborative",
+            "playlist-modify-public",
+            "playlist-modify-private"
+        )
+    }
+}
+
+impl PlaylistWriter {
+    /// Creates a writer. `description` is used for playlists the writer creates, followed by
+    /// [`CREATED_BY_MARKER`] unless it already mentions it.
+    pub async fn new(description: &str) -> Self {
+        let span = tracing::span!(Level::INFO, "PlaylistWriter.new");
+        let _enter = span.enter();
+
+        PlaylistWriter {
+            client: Self::set_up_client(false, Some(Self::select_scopes())).await,
+            description: description.to_string(),
+            duplicates: Duplicates::load(),
+        }
+    }
+
+    /// Changes the description used for playlists the writer creates from now on.
+    pub fn set_description(&mut self, description: &str) {
+        self.description = description.to_string();
+    }
+
+    /// Appends `tracks` to the playlist, skipping the ones its duplicate rules reject.
+    ///
+    /// # Errors
+    /// Returns the error of creating the playlist or adding the tracks. Tracks added before
+    /// a failed request stay in the playlist.
+    pub async fn append(&self, playlist: &str, tracks: Vec<FullTrack>) -> Result<WrittenPlaylist, PlaylistError> {
+        let span = tracing::span!(Level::INFO, "PlaylistWriter.append");
+        let _enter = span.enter();
+
+        let (mut target, existing) = self.target(playlist).await?;
+        let rules = self.duplicates.rules_for(target.id.id(), &target.name);
+        let track_ids = Self::track_ids(rules.filter_additions(&existing, tracks));
+        for chunk in track_ids.chunks(BatchLimits::ModifyPlaylistItems.get_limit()) {
+            self.add_chunk(&target.id, chunk).await?;
+        }
+        event!(Level::INFO, "Added {} tracks to {}", track_ids.len(), target.name);
+        target.written = track_ids.len();
+        Ok(target)
+    }
+
+    /// Replaces the contents of the playlist with `tracks`, in the given order. Nothing is
+    /// written when the playlist already holds exactly these tracks in this order.
+    ///
+    /// # Arguments
+    /// * `playlist` - The name or ID of the playlist.
+    /// * `tracks` - The new contents of the playlist.
+    /// * `force` - Whether to replace a playlist the writer did not create.
+    ///
+    /// # Errors
+    /// Returns [`PlaylistError::NotCreatedByTool`] for an existing playlist without
+    /// [`CREATED_BY_MARKER`] unless `force` is set, and the error of any failed request.
+    pub async fn replace(&self, playlist: &str, tracks: Vec<FullTrack>, force: bool) -> Result<WrittenPlaylist, PlaylistError> {
+        let span = tracing::span!(Level::INFO, "PlaylistWriter.replace");
+        let _enter = span.enter();
+
+        let (mut target, _) = self.target(playlist).await?;
+        if !target.created && !force && !self.created_by_tool(&target.id).await? {
+            return Err(PlaylistError::NotCreatedByTool(target.name));
+        }
+        let rules = self.duplicates.rules_for(target.id.id(), &target.name);
+        let track_ids = Self::track_ids(rules.resolve(tracks));
+        if !target.created
+            && track_ids
+                .iter()
+                .map(|track_id| track_id.id())
+                .eq(target.previous_ids.iter().map(String::as_str))
+        {
+            event!(Level::DEBUG, "{} is already up to date", target.name);
+            target.written = track_ids.len();
+            return Ok(target);
+        }
+        let mut chunks = track_ids.chunks(BatchLimits::ModifyPlaylistItems.get_limit());
+        let first = chunks.next().unwrap_or_default();
+        self.client
+            .playlist_replace_items(
+                target.id.clone(),
+                first.iter().map(|track_id| PlayableId::Track(track_id.clone())),
+            )
+            .await?;
+        for chunk in chunks {
+            self.add_chunk(&target.id, chunk).await?;
+        }
+        event!(Level::INFO, "Replaced {} with {} tracks", target.name, track_ids.len());
+        target.written = track_ids.len();
+        Ok(target)
+    }
+
+    /// Whether the description of the playlist carries [`CREATED_BY_MARKER`].
+    pub fn is_marked(description: Option<&str>) -> bool {
+        description.is_some_and(|description| description.contains(CREATED_BY_MARKER))
+    }
+
+    async fn created_by_tool(&self, playlist_id: &PlaylistId<'static>) -> Result<bool, PlaylistError> {
+        let playlist = self
+            .client
+            .playlist(playlist_id.clone(), None, Some(Self::market()))
+            .await?;
+        Ok(Self::is_marked(playlist.description.as_deref()))
+    }
+
+    /// Finds the owned playlist by name or ID, or creates it, and returns its current tracks.
+    /// Playlists the user follows but does not own are never written to.
+    async fn target(&self, playlist: &str) -> Result<(WrittenPlaylist, Vec<FullTrack>), PlaylistError> {
+        let owned = UserPlaylists::new().await.get_owned_playlists().await;
+        match UserPlaylists::match_playlist(&owned, playlist) {
+            Some(found) => {
+                let existing = PlaylistXplr::new(found.id.clone(), false).await.tracks();
+                let target = WrittenPlaylist {
+                    id: found.id,
+                    name: found.name,
+                    created: false,
+                    written: 0,
+                    previous_ids: existing
+                        .iter()
+                        .filter_map(|track| track.id.as_ref().map(|id| id.id().to_string()))
+                        .collect(),
+                };
+                Ok((target, existing))
+            }
+            None => {
+                let target = WrittenPlaylist {
+                    id: self.create_playlist(playlist).await?,
+                    name: playlist.to_string(),
+                    created: true,
+                    written: 0,
+                    previous_ids: Vec::new(),
+                };
+                Ok((target, Vec::new()))
+            }
+        }
+    }
+
+    async fn create_playlist(&self, name: &str) -> Result<PlaylistId<'static>, PlaylistError> {
+        let description = if Self::is_marked(Some(&self.description)) {
+            self.description.clone()
+        } else {
+            format!("{} ({})", self.description, CREATED_BY_MARKER)
+        };
+        let user = self.client.me().await?;
+        let playlist = self
+            .client
+            .user_playlist_create(
+                user.id,
+                name,
+                Some(false),
+                Some(false),
+                Some(description.as_str()),
+            )
+            .await?;
+        event!(Level::INFO, "Created playlist {}", name);
+        Ok(playlist.id)
+    }
+
+    async fn add_chunk(&self, playlist_id: &PlaylistId<'static>, chunk: &[TrackId<'static>]) -> Result<(), PlaylistError> {
+        self.client
+            .playlist_add_items(
+                playlist_id.clone(),
+                chunk
+                    .iter()
+                    .map(|track_id| PlayableId::Track(track_id.clone())),
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn track_ids(tracks: Vec<FullTrack>) -> Vec<TrackId<'static>> {
+        tracks.into_iter().filter_map(|track| track.id).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_marked_descriptions_count_as_created_by_the_writer() {
+        assert!(PlaylistWriter::is_marked(Some("Smart playlist by spotify-assistant")));
+        assert!(PlaylistWriter::is_marked(Some("Union of A, B (spotify-assistant)")));
+        assert!(!PlaylistWriter::is_marked(Some("My summer favourites")));
+        assert!(!PlaylistWriter::is_marked(None));
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::{fs, io};
+
+use rspotify::model::{AlbumId, FullAlbum, FullTrack, SavedAlbum, SavedTrack, TrackId};
+use rspotify::prelude::{BaseClient, Id, OAuthClient};
+use rspotify::{AuthCodeSpotify, scopes};
+use serde::Serialize;
+use tracing::{Level, event};
+
+use crate::enums::fs::ProjectDirectories;
+use crate::enums::validation::BatchLimits;
+use crate::paginator::PaginatorRunner;
+use crate::traits::apis::Api;
+
+/// The thresholds an album must reach to appear in the album completion report. An album is
+/// reported when either threshold is reached.
+///
+/// # Fields
+///
+/// * `min_tracks` - The number of liked tracks from the album.
+/// * `min_fraction` - The share of the album's tracks that are liked, between 0 and 1. Only
+///   albums with at least two liked tracks are considered, so singles are never reported.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompletionCriteria {
+    pub min_tracks: usize,
+    pub min_fraction: f64,
+}
+
+impl Default for CompletionCriteria {
+    fn default() -> Self {
+        CompletionCriteria {
+            min_tracks: 5,
+            min_fraction: 0.5,
+        }
+    }
+}
+
+/// How much of an album is among the liked songs.
+///
+/// # Fields
+///
+/// * `album_id` - The ID of the album.
+/// * `name` - The name of the album.
+/// * `artist` - The names of the album's artists.
+/// * `liked` - The number of the album's tracks that are liked.
+/// * `total` - The number of tracks on the album.
+/// * `saved` - Whether the album itself is saved.
+/// * `missing_track_ids` - The IDs of the album's tracks that are not liked, in album order.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AlbumCompletion {
+    pub album_id: String,
+    pub name: String,
+    pub artist: String,
+    pub liked: usize,
+    pub total: usize,
+    pub saved: bool,
+    pub missing_track_ids: Vec<String>,
+}
+
+impl AlbumCompletion {
+    /// The share of the album's tracks that are liked.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.liked as f64 / self.total as f64
+    }
+
+    /// Evaluates an album against the liked track IDs.
+    ///
+    /// # Returns
+    /// The completion of the album, or `None` when it does not reach the criteria.
+    pub fn evaluate(
+        album: &FullAlbum,
+        track_ids: &[String],
+        liked_ids: &HashSet<String>,
+        saved: bool,
+        criteria: CompletionCriteria,
+    ) -> Option<Self> {
+        let liked = track_ids.iter().filter(|id| liked_ids.contains(*id)).count();
+        let completion = AlbumCompletion {
+            album_id: album.id.id().to_string(),
+            name: album.name.clone(),
+            artist: album
+                .artists
+                .iter()
+                .map(|artist| artist.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", "),
+            liked,
+            total: track_ids.len(),
+            saved,
+            missing_track_ids: track_ids
+                .iter()
+                .filter(|id| !liked_ids.contains(*id))
+                .cloned()
+                .collect(),
+        };
+        let reached = completion.liked >= criteria.min_tracks
+            || completion.fraction() >= criteria.min_fraction;
+        (completion.liked >= 2 && reached).then_some(completion)
+    }
+}
+
+/// The albums saved in the user's library, cached in `saved_albums.json` in the data
+/// directory, and the album completion report built from them and the liked songs.
+///
+/// # Example
+/// ```no_run,ignore
+/// use spotify_assistant_core::actions::liked_songs::UserLibrary;
+/// use spotify_assistant_core::actions::saved_albums::{CompletionCriteria, SavedAlbums};
+///
+/// async fn main() {
+///     let saved_albums = SavedAlbums::new().await;
+///     let liked = UserLibrary::new().await.tracks();
+///     for completion in saved_albums.completion(&liked, CompletionCriteria::default()).await {
+///         println!("{}: {}/{}", completion.name, completion.liked, completion.total);
+///     }
+/// }
+/// ```
+pub struct SavedAlbums {
+    client: AuthCodeSpotify,
+    saved_albums: Vec<SavedAlbum>,
+}
+
+impl Api for SavedAlbums {
+    fn select_scopes() -> HashSet<String> {
+        scopes!("user-library-read", "user-library-modify")
+    }
+}
+
+impl SavedAlbums {
+    /// Loads the saved albums from the cache, or from Spotify when there is no cache yet.
+    pub async fn new() -> Self {
+        let span = tracing::span!(Level::INFO, "SavedAlbums.new");
+        let _enter = span.enter();
+
+        let mut saved_albums = SavedAlbums {
+            client: Self::set_up_client(false, Some(Self::select_scopes())).await,
+            saved_albums: Vec::new(),
+        };
+        match Self::load_from_file() {
+            Ok(cached) => saved_albums.saved_albums = cached,
+            Err(_) => {
+                if let Err(err) = saved_albums.sync().await {
+                    event!(Level::ERROR, "Failed to fetch saved albums: {:?}", err);
+                }
+            }
+        }
+        saved_albums
+    }
+
+    /// The cache location: `<data dir>/saved_albums.json`.
+    pub fn cache_path() -> PathBuf {
+        ProjectDirectories::Data.path().join("saved_albums.json")
+    }
+
+    fn load_from_file() -> io::Result<Vec<SavedAlbum>> {
+        let contents = fs::read_to_string(Self::cache_path())?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    fn save_to_file(&self) -> io::Result<()> {
+        let path = Self::cache_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(&self.saved_albums)?)
+    }
+
+    /// Fetches every saved album from Spotify and refreshes the cache.
+    ///
+    /// # Errors
+    /// Returns the error of the failed request. The cache is left untouched then.
+    pub async fn sync(&mut self) -> Result<usize, rspotify::ClientError> {
+        let span = tracing::span!(Level::INFO, "SavedAlbums.sync");
+        let _enter = span.enter();
+
+        let paginator = PaginatorRunner::new(self.client.current_user_saved_albums(Some(Self::market())), ());
+        self.saved_albums = paginator.run().await?;
+        if let Err(err) = self.save_to_file() {
+            event!(Level::ERROR, "Failed to save the saved albums to cache: {:?}", err);
+        }
+        event!(Level::INFO, "Synchronized {} saved albums", self.saved_albums.len());
+        Ok(self.saved_albums.len())
+    }
+
+    /// The saved albums, most recently saved first.
+    pub fn albums(&self) -> &[SavedAlbum] {
+        &self.saved_albums
+    }
+
+    /// Whether the album with the given ID is saved, according to the cache.
+    pub fn is_saved(&self, album_id: &str) -> bool {
+        self.saved_albums
+            .iter()
+            .any(|saved_album| saved_album.album.id.id() == album_id)
+    }
+
+    /// Saves albums to the library and refreshes the cache.
+    ///
+    /// # Errors
+    /// Returns the error of the first failed request.
+    pub async fn save(&mut self, album_ids: &[AlbumId<'static>]) -> Result<(), rspotify::ClientError> {
+        let span = tracing::span!(Level::INFO, "SavedAlbums.save");
+        let _enter = span.enter();
+
+        for chunk in album_ids.chunks(BatchLimits::ModifyCurrentUserAlbums.get_limit()) {
+            self.client
+                .current_user_saved_albums_add(chunk.iter().cloned())
+                .await?;
+        }
+        event!(Level::INFO, "Saved {} albums", album_ids.len());
+        self.sync().await.map(|_| ())
+    }
+
+    /// Removes albums from the library and refreshes the cache.
+    ///
+    /// # Errors
+    /// Returns the error of the first failed request.
+    pub async fn remove(&mut self, album_ids: &[AlbumId<'static>]) -> Result<(), rspotify::ClientError> {
+        let span = tracing::span!(Level::INFO, "SavedAlbums.remove");
+        let _enter = span.enter();
+
+        for chunk in album_ids.chunks(BatchLimits::ModifyCurrentUserAlbums.get_limit()) {
+            self.client
+                .current_user_saved_albums_delete(chunk.iter().cloned())
+                .await?;
+        }
+        event!(Level::INFO, "Removed {} albums", album_ids.len());
+        self.sync().await.map(|_| ())
+    }
+
+    /// Groups the liked track IDs by the ID of their album.
+    pub fn liked_by_album(liked: &[SavedTrack]) -> HashMap<String, HashSet<String>> {
+        let mut by_album: HashMap<String, HashSet<String>> = HashMap::new();
+        for saved_track in liked {
+            let (Some(album_id), Some(track_id)) = (&saved_track.track.album.id, &saved_track.track.id) else {
+                continue;
+            };
+            by_album
+                .entry(album_id.id().to_string())
+                .or_default()
+                .insert(track_id.id().to_string());
+        }
+        by_album
+    }
+
+    /// Builds the album completion report: the albums with enough liked tracks, most complete
+    /// first.
+    ///
+    /// Only albums with at least two liked tracks are looked up. Saved albums are read from the
+    /// cache; the others are loaded 20 at a time.
+    pub async fn completion(&self, liked: &[SavedTrack], criteria: CompletionCriteria) -> Vec<AlbumCompletion> {
+        let span = tracing::span!(Level::INFO, "SavedAlbums.completion");
+        let _enter = span.enter();
+
+        let by_album = Self::liked_by_album(liked);
+        let liked_ids = by_album.values().flatten().cloned().collect::<HashSet<String>>();
+        let mut albums = Vec::new();
+        let mut to_fetch = Vec::new();
+        for (album_id, tracks) in &by_album {
+            if tracks.len() < 2 {
+                continue;
+            }
+            match self
+                .saved_albums
+                .iter()
+                .find(|saved_album| saved_album.album.id.id() == album_id)
+            {
+                Some(saved_album) => albums.push(saved_album.album.clone()),
+                None => {
+                    if let Ok(album_id) = AlbumId::from_id(album_id.clone()) {
+                        to_fetch.push(album_id);
+                    }
+                }
+            }
+        }
+        for chunk in to_fetch.chunks(BatchLimits::Albums.get_limit()) {
+            match self.client.albums(chunk.iter().cloned(), Some(Self::market())).await {
+                Ok(fetched) => albums.extend(fetched),
+                Err(err) => event!(Level::ERROR, "Could not load a batch of albums: {:?}", err),
+            }
+        }
+
+        let mut report = Vec::new();
+        for album in &albums {
+            let track_ids = self.album_track_ids(album).await;
+            let saved = self.is_saved(album.id.id());
+            if let Some(completion) = AlbumCompletion::evaluate(album, &track_ids, &liked_ids, saved, criteria) {
+                report.push(completion);
+            }
+        }
+        report.sort_by(|a, b| {
+            b.fraction()
+                .total_cmp(&a.fraction())
+                .then_with(|| b.liked.cmp(&a.liked))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        event!(Level::DEBUG, "Albums in the completion report: {}", report.len());
+        report
+    }
+
+    /// The IDs of every track on the album, loading the tracks beyond the first page.
+    async fn album_track_ids(&self, album: &FullAlbum) -> Vec<String> {
+        let mut tracks = album.tracks.items.clone();
+        if album.tracks.next.is_some() {
+            let paginator = PaginatorRunner::new(self.client.album_track(album.id.clone(), Some(Self::market())), ());
+            match paginator.run().await {
+                Ok(all_tracks) => tracks = all_tracks,
+                Err(err) => event!(Level::ERROR, "Could not load the tracks of {}: {:?}", album.name, err),
+            }
+        }
+        tracks
+            .iter()
+            .filter_map(|track| track.id.as_ref().map(|id| id.id().to_string()))
+            .collect()
+    }
+
+    /// Loads the tracks of the albums that are not liked yet, in report order.
+    pub async fn missing_tracks(&self, report: &[AlbumCompletion]) -> Vec<FullTrack> {
+        let span = tracing::span!(Level::INFO, "SavedAlbums.missing_tracks");
+        let _enter = span.enter();
+
+        let track_ids = report
+            .iter()
+            .flat_map(|completion| completion.missing_track_ids.iter())
+            .filter_map(|id| TrackId::from_id(id.clone()).ok())
+            .collect::<Vec<TrackId<'static>>>();
+        let mut tracks = Vec::with_capacity(track_ids.len());
+        for chunk in track_ids.chunks(BatchLimits::Tracks.get_limit()) {
+            match self.client.tracks(chunk.iter().cloned(), Some(Self::market())).await {
+                Ok(fetched) => tracks.extend(fetched),
+                Err(err) => event!(Level::ERROR, "Could not load a batch of tracks: {:?}", err),
+            }
+        }
+        tracks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+    use chrono::Utc;
+
+    fn album() -> FullAlbum {
+        let (_, album) = OfflineObjects::sample_full_album();
+        album
+    }
+
+    fn ids(ids: &[char]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string().repeat(22)).collect()
+    }
+
+    #[test]
+    fn evaluates_albums_against_both_thresholds() {
+        let album = album();
+        let tracks = ids(&['A', 'B', 'C', 'D']);
+        let liked = ids(&['A', 'C', 'X']).into_iter().collect::<HashSet<String>>();
+        let criteria = CompletionCriteria {
+            min_tracks: 3,
+            min_fraction: 0.5,
+        };
+        let completion = AlbumCompletion::evaluate(&album, &tracks, &liked, false, criteria).unwrap();
+        assert_eq!((completion.liked, completion.total), (2, 4));
+        assert_eq!(completion.fraction(), 0.5);
+        assert_eq!(completion.missing_track_ids, ids(&['B', 'D']));
+
+        let strict = CompletionCriteria {
+            min_tracks: 3,
+            min_fraction: 0.75,
+        };
+        assert!(AlbumCompletion::evaluate(&album, &tracks, &liked, false, strict).is_none());
+
+        let one_liked = ids(&['A']).into_iter().collect::<HashSet<String>>();
+        assert!(AlbumCompletion::evaluate(&album, &ids(&['A', 'B']), &one_liked, false, criteria).is_none());
+    }
+
+    #[test]
+    fn groups_liked_tracks_by_album() {
+        let liked = ["A", "B"]
+            .iter()
+            .map(|id| SavedTrack {
+                added_at: Utc::now(),
+                track: OfflineObjects::full_track(&id.repeat(22), id, "Artist", "Album", None, 200_000),
+            })
+            .collect::<Vec<SavedTrack>>();
+        let by_album = SavedAlbums::liked_by_album(&liked);
+        assert_eq!(by_album.values().map(HashSet::len).sum::<usize>(), 2);
+    }
+}
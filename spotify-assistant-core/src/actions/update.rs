@@ -2,12 +2,15 @@ use std::collections::HashSet;
 
 use crate::actions::exploration::playlist::PlaylistXplr;
 use crate::enums::pl::PlaylistType;
+use crate::enums::validation::BatchLimits;
 use crate::models::blacklist::{Blacklist, BlacklistArtist};
+use crate::models::configuration::Duplicates;
+use crate::models::duplicate_rules::DuplicateRules;
 use crate::traits::apis::Api;
 use rspotify::model::Id;
 use rspotify::model::{AlbumId, FullPlaylist, FullTrack, PlayableItem, PlaylistId, TrackId};
 use rspotify::prelude::*;
-use rspotify::{AuthCodeSpotify, scopes};
+use rspotify::{AuthCodeSpotify, ClientError, scopes};
 use tracing::{Level, error, event};
 
 /// The `Editor` struct is used to manage and handle Spotify playlists, serving as a utility
@@ -50,6 +53,7 @@ pub struct Editor {
     target_id: PlaylistId<'static>,
     ref_pl: FullPlaylist,
     target_pl: FullPlaylist,
+    rules: DuplicateRules,
 }
 
 impl Api for Editor {
@@ -182,12 +186,14 @@ impl Editor {
         let client = Self::set_up_client(false, Some(Self::select_scopes())).await;
         let target_pl = Self::playlist_from_id(&client, target_id.clone()).await;
         let ref_pl = Self::playlist_from_id(&client, ref_id.clone()).await;
+        let rules = Duplicates::load().rules_for(target_id.id(), &target_pl.name);
         Editor {
             client,
             ref_id,
             target_id,
             ref_pl,
             target_pl,
+            rules,
         }
     }
 
@@ -768,7 +774,8 @@ impl Editor {
     /// Updates the target playlist with tracks obtained from an album reference.
     ///
     /// This asynchronous function performs the following tasks:
-    /// 1. Retrieves album track IDs from a reference.
+    /// 1. Retrieves album track IDs from a reference and applies the target playlist's
+    ///    [`DuplicateRules`] to the corresponding tracks.
     /// 2. Checks if the stock release radar ID has been used.
     /// 3. Updates the playlist in chunks of 20 tracks, taking care to:
     ///    - Set the playlist's description for the first chunk.
@@ -788,6 +795,10 @@ impl Editor {
     /// - If replacing playlist items with the initial chunk fails.
     /// - If adding items to the playlist from subsequent chunks fails.
     ///
+    /// # Errors
+    /// Returns the error of loading a batch of the reference tracks, before the target playlist
+    /// is changed, so an update never silently drops tracks.
+    ///
     /// # Example
     /// ```no_run,ignore
     /// use spotify_assistant_core::actions::update::Editor;
@@ -795,17 +806,22 @@ impl Editor {
     ///
     /// async fn main() {
     ///     let editor = Editor::new(PlaylistType::StockRR.get_id(), PlaylistType::MyRR.get_id()).await;
-    ///     editor.update_playlist().await;
+    ///     editor.update_playlist().await.unwrap();
     /// }
     /// ```
     ///
     /// # Notes
     /// - This function assumes that the `target_id` is valid and properly configured.
     /// - The `wipe_reference_playlist` method is called at the end to clean up the reference playlist.
-    pub async fn update_playlist(&self) {
+    pub async fn update_playlist(&self) -> Result<(), ClientError> {
         let span = tracing::span!(Level::DEBUG, "Editor.update_playlist");
         let _enter = span.enter();
         let ids = self.get_album_tracks_from_reference().await;
+        let tracks = self.duplicate_rules().resolve(self.full_tracks(&ids).await?);
+        let ids = tracks
+            .into_iter()
+            .filter_map(|track| track.id)
+            .collect::<Vec<TrackId>>();
         self.check_if_stock_release_radar_id_was_used(ids.len());
 
         let mut first_chunk = true;
@@ -820,13 +836,13 @@ impl Editor {
                 .await;
         }
         self.wipe_reference_playlist().await;
+        Ok(())
     }
     pub async fn update_rr_from_xplorer(&self) {
         let span = tracing::span!(Level::DEBUG, "Editor.update_playlist_from_xplorer");
         let _enter = span.enter();
         let mut xplorer = PlaylistXplr::new(self.ref_id.clone(), false).await;
-        xplorer.set_tracks_to_unique_from_expanded();
-        // xplorer.tracks = xplorer.unique_tracks();
+        xplorer.set_tracks(self.duplicate_rules().resolve(xplorer.tracks()));
         let track_ids = xplorer.playable_ids();
         self.check_if_stock_release_radar_id_was_used(track_ids.len());
 
@@ -838,6 +854,28 @@ impl Editor {
         }
     }
 
+    /// Returns the duplicate rules configured for the target playlist, read when the editor
+    /// was created.
+    pub fn duplicate_rules(&self) -> DuplicateRules {
+        self.rules
+    }
+
+    /// Loads the tracks in batches.
+    ///
+    /// # Errors
+    /// Returns the error of the first batch that cannot be loaded.
+    async fn full_tracks(&self, ids: &[TrackId<'_>]) -> Result<Vec<FullTrack>, ClientError> {
+        let mut tracks = Vec::with_capacity(ids.len());
+        for chunk in ids.chunks(BatchLimits::Tracks.get_limit()) {
+            tracks.extend(
+                self.client
+                    .tracks(chunk.iter().map(|id| id.as_ref()), Some(Self::market()))
+                    .await?,
+            );
+        }
+        Ok(tracks)
+    }
+
     pub async fn update_playlist_from_chunk(
         &self,
         chunk: Vec<PlayableId<'_>>,
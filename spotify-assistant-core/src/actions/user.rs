@@ -610,7 +610,12 @@ impl UserData {
         }
         followed_artists
     }
-    pub async fn update_followed_artists(&self) {
+    /// Fetches the followed artists and stores them as a dated JSON snapshot in the
+    /// `followers` data directory.
+    ///
+    /// # Returns
+    /// The followed artists, so callers can also record them in the followed artists history.
+    pub async fn update_followed_artists(&self) -> Vec<FullArtist> {
         let span = tracing::span!(Level::INFO, "UserData.update-artists");
         let _enter = span.enter();
         let artists = self.artists().await;
@@ -623,6 +628,7 @@ impl UserData {
         let artists_json = serde_json::to_string_pretty(&artists).unwrap();
         std::fs::write(file_path.clone(), artists_json).unwrap();
         info!("Stored {} artists to file path: {:?}", follower_length, file_path);
+        artists
     }
     fn follower_file_directory() -> PathBuf {
         let data_path = ProjectDirectories::Data.path();
@@ -0,0 +1,102 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Set operations used to combine two or more playlists.
+///
+/// Variants:
+/// - `Union`: Tracks in any source.
+/// - `Intersection`: Tracks in every source.
+/// - `Difference`: Tracks in the first source and in none of the others.
+/// - `SymmetricDifference`: Tracks in exactly one of the sources.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetOperation {
+    Union,
+    Intersection,
+    Difference,
+    SymmetricDifference,
+}
+
+impl FromStr for SetOperation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "union" | "or" => Ok(SetOperation::Union),
+            "intersection" | "intersect" | "and" => Ok(SetOperation::Intersection),
+            "difference" | "minus" => Ok(SetOperation::Difference),
+            "symmetric-difference" | "symmetric_difference" | "xor" => {
+                Ok(SetOperation::SymmetricDifference)
+            }
+            other => Err(format!("Unsupported set operation: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for SetOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetOperation::Union => write!(f, "union"),
+            SetOperation::Intersection => write!(f, "intersection"),
+            SetOperation::Difference => write!(f, "difference"),
+            SetOperation::SymmetricDifference => write!(f, "symmetric-difference"),
+        }
+    }
+}
+
+/// The order tracks are written in when combining playlists.
+///
+/// Variants:
+/// - `Source`: The order the tracks first appear in, going through the sources in turn.
+/// - `AddedAt`: Oldest addition first. Tracks without an addition date go last.
+/// - `Artist`: Alphabetically by first artist, then by title.
+/// - `Title`: Alphabetically by title.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CombineOrder {
+    #[default]
+    Source,
+    #[serde(rename = "added", alias = "added-at")]
+    AddedAt,
+    Artist,
+    Title,
+}
+
+impl FromStr for CombineOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "source" => Ok(CombineOrder::Source),
+            "added" | "added-at" | "added_at" => Ok(CombineOrder::AddedAt),
+            "artist" => Ok(CombineOrder::Artist),
+            "title" | "name" => Ok(CombineOrder::Title),
+            other => Err(format!("Unsupported order: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for CombineOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CombineOrder::Source => write!(f, "source"),
+            CombineOrder::AddedAt => write!(f, "added"),
+            CombineOrder::Artist => write!(f, "artist"),
+            CombineOrder::Title => write!(f, "title"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cli_values_and_aliases() {
+        assert_eq!("XOR".parse::<SetOperation>(), Ok(SetOperation::SymmetricDifference));
+        assert_eq!("minus".parse::<SetOperation>(), Ok(SetOperation::Difference));
+        assert_eq!("added".parse::<CombineOrder>(), Ok(CombineOrder::AddedAt));
+        assert!("merge".parse::<SetOperation>().is_err());
+    }
+}
@@ -1,5 +1,98 @@
-#[derive(Clone, Copy, Debug)]
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Whether duplicate tracks are allowed in a playlist.
+///
+/// Variants:
+/// - `Keep`: Duplicates are left untouched.
+/// - `Remove`: Only one copy of each duplicated track is kept. Which copy survives is decided
+///   by a [`DuplicatePreference`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DuplicatePolicy {
     Keep,
+    #[default]
     Remove,
 }
+
+/// Decides which copy of a duplicated track survives when duplicates are removed.
+///
+/// The survivor always takes the position of the earliest copy, so the playlist order is
+/// preserved regardless of the preference.
+///
+/// Variants:
+/// - `KeepEarliest`: The copy that appears first is kept.
+/// - `PreferOriginalAlbum`: A copy from a regular album or single is kept over one from a compilation.
+/// - `PreferExplicit`: The explicit version is kept over the clean one.
+/// - `PreferClean`: The clean version is kept over the explicit one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePreference {
+    #[default]
+    KeepEarliest,
+    PreferOriginalAlbum,
+    PreferExplicit,
+    PreferClean,
+}
+
+impl DuplicatePreference {
+    /// Explicit and clean versions of a song are separate recordings with their own ISRCs, so
+    /// these preferences need duplicates to be matched by title and artists instead of by
+    /// recording fingerprint.
+    pub fn matches_versions(&self) -> bool {
+        matches!(
+            self,
+            DuplicatePreference::PreferExplicit | DuplicatePreference::PreferClean
+        )
+    }
+}
+
+/// How two playlist items are recognized as the same track when deduplicating a playlist.
+///
+/// Variants:
+/// - `ExactId`: Items are duplicates only when they point at the same Spotify track ID.
+/// - `Fingerprint`: Items are duplicates when they share a [`FullTrackFingerprint`], which also
+///   catches the same recording released on several albums under different track IDs.
+///
+/// [`FullTrackFingerprint`]: crate::models::full_track_fingerprint::FullTrackFingerprint
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DedupeMode {
+    #[default]
+    ExactId,
+    Fingerprint,
+}
+
+impl FromStr for DedupeMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "exact" | "id" => Ok(DedupeMode::ExactId),
+            "fuzzy" | "fingerprint" => Ok(DedupeMode::Fingerprint),
+            other => Err(format!("Unsupported dedupe mode: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for DedupeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DedupeMode::ExactId => write!(f, "exact"),
+            DedupeMode::Fingerprint => write!(f, "fuzzy"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedupe_mode_parses_cli_values() {
+        assert_eq!("exact".parse::<DedupeMode>(), Ok(DedupeMode::ExactId));
+        assert_eq!("Fuzzy".parse::<DedupeMode>(), Ok(DedupeMode::Fingerprint));
+        assert!("loose".parse::<DedupeMode>().is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// File formats an artist collaboration graph can be exported to.
+///
+/// Variants:
+/// - `GraphMl`: A GraphML document, which Gephi, yEd and Cytoscape open directly.
+/// - `Dot`: A Graphviz DOT graph.
+/// - `Json`: The nodes and edges as JSON, intended for scripting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphFormat {
+    GraphMl,
+    Dot,
+    Json,
+}
+
+impl GraphFormat {
+    /// Returns the file extension (without the leading dot) used when exporting this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            GraphFormat::GraphMl => "graphml",
+            GraphFormat::Dot => "dot",
+            GraphFormat::Json => "json",
+        }
+    }
+
+    /// Guesses the format from the file extension, treating anything unknown as GraphML.
+    pub fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| extension.parse::<GraphFormat>().ok())
+            .unwrap_or(GraphFormat::GraphMl)
+    }
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "graphml" | "xml" => Ok(GraphFormat::GraphMl),
+            "dot" | "gv" => Ok(GraphFormat::Dot),
+            "json" => Ok(GraphFormat::Json),
+            other => Err(format!("Unsupported graph format: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for GraphFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_format_is_guessed_from_the_extension() {
+        assert_eq!(GraphFormat::from_path(Path::new("artists.GV")), GraphFormat::Dot);
+        assert_eq!(GraphFormat::from_path(Path::new("artists.json")), GraphFormat::Json);
+        assert_eq!(GraphFormat::from_path(Path::new("artists")), GraphFormat::GraphMl);
+        assert!("gexf".parse::<GraphFormat>().is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The number of days mirrored by `recent` when no count is given.
+pub const DEFAULT_RECENT_DAYS: u32 = 90;
+
+/// How liked songs are grouped into mirror playlists.
+///
+/// Variants:
+/// - `Year`: One playlist per year the songs were liked in, e.g. `2025`.
+/// - `Month`: One playlist per month the songs were liked in, e.g. `2025-10`.
+/// - `Recent`: A single playlist with the songs liked in the last given number of days.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MirrorPeriod {
+    Year,
+    Month,
+    Recent(u32),
+}
+
+impl FromStr for MirrorPeriod {
+    type Err = String;
+
+    /// Parses `year`, `month`, `recent` or `recent:<days>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase();
+        match normalized.split_once(':') {
+            None => match normalized.as_str() {
+                "year" | "yearly" => Ok(MirrorPeriod::Year),
+                "month" | "monthly" => Ok(MirrorPeriod::Month),
+                "recent" => Ok(MirrorPeriod::Recent(DEFAULT_RECENT_DAYS)),
+                other => Err(format!("Unsupported mirror period: {other}")),
+            },
+            Some(("recent", days)) => match days.trim().parse::<u32>() {
+                Ok(days) if days > 0 => Ok(MirrorPeriod::Recent(days)),
+                _ => Err(format!("Invalid number of days: {days}")),
+            },
+            Some(_) => Err(format!("Unsupported mirror period: {normalized}")),
+        }
+    }
+}
+
+impl fmt::Display for MirrorPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MirrorPeriod::Year => write!(f, "year"),
+            MirrorPeriod::Month => write!(f, "month"),
+            MirrorPeriod::Recent(days) => write!(f, "recent:{days}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cli_values() {
+        assert_eq!("Year".parse::<MirrorPeriod>(), Ok(MirrorPeriod::Year));
+        assert_eq!("recent".parse::<MirrorPeriod>(), Ok(MirrorPeriod::Recent(90)));
+        assert_eq!("recent:30".parse::<MirrorPeriod>(), Ok(MirrorPeriod::Recent(30)));
+        assert_eq!(MirrorPeriod::Recent(7).to_string(), "recent:7");
+        assert!("recent:0".parse::<MirrorPeriod>().is_err());
+        assert!("week".parse::<MirrorPeriod>().is_err());
+    }
+}
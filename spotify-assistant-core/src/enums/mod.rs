@@ -3,3 +3,11 @@ pub mod pl;
 pub mod validation;
 pub mod track_collection;
 pub mod duplication;
+pub mod report;
+pub mod playlist_format;
+pub mod combine;
+pub mod split;
+pub mod sort;
+pub mod mirror;
+pub mod prune;
+pub mod graph_format;
@@ -0,0 +1,116 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// File formats a playlist can be exported to.
+///
+/// Variants:
+/// - `Json`: The full playlist with metadata, intended for backups and scripting.
+/// - `Csv`: One row per track with a header row, intended for spreadsheets.
+/// - `M3u8`: An extended M3U playlist (UTF-8) that other players can import.
+/// - `Xspf`: An XML Shareable Playlist Format document.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaylistFormat {
+    Json,
+    Csv,
+    M3u8,
+    Xspf,
+}
+
+impl PlaylistFormat {
+    /// Returns the file extension (without the leading dot) used when exporting this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PlaylistFormat::Json => "json",
+            PlaylistFormat::Csv => "csv",
+            PlaylistFormat::M3u8 => "m3u8",
+            PlaylistFormat::Xspf => "xspf",
+        }
+    }
+}
+
+impl FromStr for PlaylistFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "json" => Ok(PlaylistFormat::Json),
+            "csv" => Ok(PlaylistFormat::Csv),
+            "m3u8" | "m3u" => Ok(PlaylistFormat::M3u8),
+            "xspf" => Ok(PlaylistFormat::Xspf),
+            other => Err(format!("Unsupported playlist format: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for PlaylistFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+/// File formats a playlist can be imported from.
+///
+/// Variants:
+/// - `Csv`: One row per track with a header row naming the columns.
+/// - `M3u`: An (extended) M3U or M3U8 playlist.
+/// - `Text`: One `Artist - Title` entry per line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    M3u,
+    Text,
+}
+
+impl ImportFormat {
+    /// Guesses the format from the file extension, treating anything unknown as plain text.
+    pub fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| extension.parse::<ImportFormat>().ok())
+            .unwrap_or(ImportFormat::Text)
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(ImportFormat::Csv),
+            "m3u" | "m3u8" => Ok(ImportFormat::M3u),
+            "txt" | "text" => Ok(ImportFormat::Text),
+            other => Err(format!("Unsupported import format: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for ImportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportFormat::Csv => write!(f, "csv"),
+            ImportFormat::M3u => write!(f, "m3u"),
+            ImportFormat::Text => write!(f, "text"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_formats_and_aliases() {
+        assert_eq!("M3U".parse::<PlaylistFormat>(), Ok(PlaylistFormat::M3u8));
+        assert_eq!("xspf".parse::<PlaylistFormat>(), Ok(PlaylistFormat::Xspf));
+        assert!("pls".parse::<PlaylistFormat>().is_err());
+    }
+
+    #[test]
+    fn import_format_is_guessed_from_the_extension() {
+        assert_eq!(ImportFormat::from_path(Path::new("mix.M3U8")), ImportFormat::M3u);
+        assert_eq!(ImportFormat::from_path(Path::new("mix.csv")), ImportFormat::Csv);
+        assert_eq!(ImportFormat::from_path(Path::new("mix.list")), ImportFormat::Text);
+        assert_eq!(ImportFormat::from_path(Path::new("mix")), ImportFormat::Text);
+    }
+}
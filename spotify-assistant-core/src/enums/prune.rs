@@ -0,0 +1,85 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A rule selecting liked songs to unlike.
+///
+/// Variants:
+/// - `Blacklist`: Songs by an artist on the blacklist.
+/// - `Genre`: Songs whose lead artist has a genre containing the text, ignoring case.
+/// - `Before`: Songs released before the year.
+/// - `After`: Songs released after the year.
+/// - `Duplicates`: Every copy of a recording except the one liked first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PruneRule {
+    Blacklist,
+    Genre(String),
+    Before(i32),
+    After(i32),
+    Duplicates,
+}
+
+impl PruneRule {
+    /// Whether the rule needs the genres of the lead artists to be looked up.
+    pub fn needs_genres(&self) -> bool {
+        matches!(self, PruneRule::Genre(_))
+    }
+}
+
+impl FromStr for PruneRule {
+    type Err = String;
+
+    /// Parses `blacklist`, `duplicates`, `genre:<text>`, `before:<year>` or `after:<year>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        match trimmed.split_once(':') {
+            None => match trimmed.to_lowercase().as_str() {
+                "blacklist" | "blacklisted" => Ok(PruneRule::Blacklist),
+                "duplicates" | "dupes" => Ok(PruneRule::Duplicates),
+                other => Err(format!("Unsupported prune rule: {other}")),
+            },
+            Some((name, value)) => {
+                let value = value.trim();
+                let year = || {
+                    value
+                        .parse::<i32>()
+                        .map_err(|_| format!("Invalid release year: {value}"))
+                };
+                match name.trim().to_lowercase().as_str() {
+                    "genre" if !value.is_empty() => Ok(PruneRule::Genre(value.to_lowercase())),
+                    "genre" => Err("The genre rule needs a genre".to_string()),
+                    "before" => year().map(PruneRule::Before),
+                    "after" => year().map(PruneRule::After),
+                    other => Err(format!("Unsupported prune rule: {other}")),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for PruneRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PruneRule::Blacklist => write!(f, "blacklisted artist"),
+            PruneRule::Genre(genre) => write!(f, "genre {genre}"),
+            PruneRule::Before(year) => write!(f, "released before {year}"),
+            PruneRule::After(year) => write!(f, "released after {year}"),
+            PruneRule::Duplicates => write!(f, "duplicate"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cli_values() {
+        assert_eq!("Blacklist".parse::<PruneRule>(), Ok(PruneRule::Blacklist));
+        assert_eq!("genre:Christmas".parse::<PruneRule>(), Ok(PruneRule::Genre("christmas".to_string())));
+        assert_eq!("before:1990".parse::<PruneRule>(), Ok(PruneRule::Before(1990)));
+        assert_eq!("after: 2020".parse::<PruneRule>(), Ok(PruneRule::After(2020)));
+        assert!("before:nineties".parse::<PruneRule>().is_err());
+        assert!("genre:".parse::<PruneRule>().is_err());
+        assert!("explicit".parse::<PruneRule>().is_err());
+    }
+}
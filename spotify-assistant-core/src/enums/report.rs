@@ -0,0 +1,59 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Output formats supported by the report-style commands (duplicates, diffs, statistics).
+///
+/// Variants:
+/// - `Json`: Pretty-printed JSON, intended for scripting and other tools.
+/// - `Csv`: Comma-separated values with a header row, intended for spreadsheets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+impl ReportFormat {
+    /// Returns the file extension (without the leading dot) used when exporting this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "json",
+            ReportFormat::Csv => "csv",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "json" => Ok(ReportFormat::Json),
+            "csv" => Ok(ReportFormat::Csv),
+            other => Err(format!("Unsupported report format: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_known_formats_case_insensitively() {
+        assert_eq!("JSON".parse::<ReportFormat>(), Ok(ReportFormat::Json));
+        assert_eq!(" csv ".parse::<ReportFormat>(), Ok(ReportFormat::Csv));
+        assert!("xml".parse::<ReportFormat>().is_err());
+    }
+
+    #[test]
+    fn extension_matches_display() {
+        assert_eq!(ReportFormat::Json.to_string(), "json");
+        assert_eq!(ReportFormat::Csv.extension(), "csv");
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The field an existing playlist is sorted by.
+///
+/// Variants:
+/// - `ReleaseDate`: The album release date, then the disc and track number.
+/// - `Artist`: The first artist, then the album and the disc and track number.
+/// - `Album`: The album name, then the disc and track number.
+/// - `Popularity`: The track popularity.
+/// - `Duration`: The track duration.
+/// - `AddedAt`: The time the item was added to the playlist.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    ReleaseDate,
+    Artist,
+    Album,
+    Popularity,
+    Duration,
+    AddedAt,
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "release-date" | "release_date" | "released" => Ok(SortKey::ReleaseDate),
+            "artist" => Ok(SortKey::Artist),
+            "album" => Ok(SortKey::Album),
+            "popularity" => Ok(SortKey::Popularity),
+            "duration" => Ok(SortKey::Duration),
+            "added-at" | "added_at" | "added" => Ok(SortKey::AddedAt),
+            other => Err(format!("Unsupported sort key: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortKey::ReleaseDate => write!(f, "release-date"),
+            SortKey::Artist => write!(f, "artist"),
+            SortKey::Album => write!(f, "album"),
+            SortKey::Popularity => write!(f, "popularity"),
+            SortKey::Duration => write!(f, "duration"),
+            SortKey::AddedAt => write!(f, "added-at"),
+        }
+    }
+}
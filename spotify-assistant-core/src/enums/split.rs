@@ -0,0 +1,59 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The key used to split a playlist into child playlists.
+///
+/// Variants:
+/// - `Genre`: The first genre of the track's lead artist.
+/// - `Decade`: The decade of the album release, e.g. `1990s`.
+/// - `LeadArtist`: The first credited artist.
+/// - `AddedMonth`: The month the track was added to the source, e.g. `2024-03`.
+/// - `AlbumType`: The album type (`album`, `single` or `compilation`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitBy {
+    Genre,
+    Decade,
+    LeadArtist,
+    AddedMonth,
+    AlbumType,
+}
+
+impl FromStr for SplitBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "genre" => Ok(SplitBy::Genre),
+            "decade" => Ok(SplitBy::Decade),
+            "lead-artist" | "lead_artist" | "artist" => Ok(SplitBy::LeadArtist),
+            "added-month" | "added_month" | "month" => Ok(SplitBy::AddedMonth),
+            "album-type" | "album_type" => Ok(SplitBy::AlbumType),
+            other => Err(format!("Unsupported split key: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for SplitBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SplitBy::Genre => write!(f, "genre"),
+            SplitBy::Decade => write!(f, "decade"),
+            SplitBy::LeadArtist => write!(f, "lead-artist"),
+            SplitBy::AddedMonth => write!(f, "added-month"),
+            SplitBy::AlbumType => write!(f, "album-type"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cli_values() {
+        assert_eq!("lead-artist".parse::<SplitBy>(), Ok(SplitBy::LeadArtist));
+        assert_eq!("Added_Month".parse::<SplitBy>(), Ok(SplitBy::AddedMonth));
+        assert_eq!(SplitBy::AlbumType.to_string(), "album-type");
+        assert!("mood".parse::<SplitBy>().is_err());
+    }
+}
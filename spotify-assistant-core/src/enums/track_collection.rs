@@ -1,6 +1,7 @@
 use crate::collect_track_field;
 use crate::utilities::general::format_duration;
 use rspotify::model::{AlbumId, ArtistId, FullTrack, Image, PlayableId, Restriction, RestrictionReason, SavedTrack, SimplifiedArtist, SimplifiedTrack, TrackId, TrackLink};
+use rspotify::prelude::Id;
 // #[macro_export]
 // macro_rules! collect_track_field {
 //     ($tracks:expr, $field_path:expr, $default:expr) => {
@@ -234,26 +235,17 @@ impl TrackCollection {
         match self {
             TrackCollection::SavedTracks(tracks) => {
                 collect_track_field!(tracks, |track: &SavedTrack| {
-                    match track.clone().track.linked_from {
-                        Some(linked_from) => linked_from.uri,
-                        None => "spotify:track:unknown".to_string()
-                    }
+                    Self::uri_helper(&track.track.id, &track.track.linked_from)
                 })
             },
             TrackCollection::FullTrack(tracks) => {
                 collect_track_field!(tracks, |track: &FullTrack| {
-                    match track.clone().linked_from {
-                        Some(linked_from) => linked_from.uri,
-                        None => "spotify:track:unknown".into()
-                    }
+                    Self::uri_helper(&track.id, &track.linked_from)
                 })
             },
             TrackCollection::SimplifiedTrack(tracks) => {
                 collect_track_field!(tracks, |track: &SimplifiedTrack| {
-                    match track.clone().linked_from {
-                        Some(linked_from) => linked_from.uri,
-                        None => "spotify:track:unknown".into()
-                    }
+                    Self::uri_helper(&track.id, &track.linked_from)
                 })
             },
             TrackCollection::TrackLink(tracks) => {
@@ -262,6 +254,42 @@ impl TrackCollection {
         }
     }
 
+    fn uri_helper(id: &Option<TrackId<'_>>, linked_from: &Option<TrackLink>) -> String {
+        match (id, linked_from) {
+            (Some(id), _) => id.uri(),
+            (None, Some(linked_from)) => linked_from.uri.clone(),
+            (None, None) => "spotify:track:unknown".to_string(),
+        }
+    }
+
+    pub fn isrcs(&self) -> Option<Vec<Option<String>>> {
+        match self {
+            TrackCollection::SavedTracks(tracks) => {
+                collect_track_field!(tracks, |track: &SavedTrack| track.track.external_ids.get("isrc").cloned())
+            },
+            TrackCollection::FullTrack(tracks) => {
+                collect_track_field!(tracks, |track: &FullTrack| track.external_ids.get("isrc").cloned())
+            },
+            TrackCollection::SimplifiedTrack(_) => None,
+            TrackCollection::TrackLink(_) => None
+        }
+    }
+
+    pub fn durations_ms(&self) -> Option<Vec<i64>> {
+        match self {
+            TrackCollection::SavedTracks(tracks) => {
+                collect_track_field!(tracks, |track: &SavedTrack| track.track.duration.num_milliseconds())
+            },
+            TrackCollection::FullTrack(tracks) => {
+                collect_track_field!(tracks, |track: &FullTrack| track.duration.num_milliseconds())
+            },
+            TrackCollection::SimplifiedTrack(tracks) => {
+                collect_track_field!(tracks, |track: &SimplifiedTrack| track.duration.num_milliseconds())
+            },
+            TrackCollection::TrackLink(_) => None
+        }
+    }
+
     fn restrictions_to_string_helper(
         restrictions: &Option<Restriction>
     ) -> String {
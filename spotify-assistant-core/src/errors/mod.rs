@@ -1,8 +1,10 @@
 pub mod collections;
 pub mod configuration;
+pub mod playlists;
 
 use crate::errors::collections::CollectionError;
 use crate::errors::configuration::ConfigurationError;
+use crate::errors::playlists::PlaylistError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -11,6 +13,8 @@ pub enum SpotifyAssistantError {
     EnumError(#[from] CollectionError),
     #[error("Configuration error: {0}")]
     ConfigurationError(#[from] ConfigurationError),
+    #[error("Playlist error: {0}")]
+    PlaylistError(#[from] PlaylistError),
     #[error("Unknown error occurred")]
     Unknown,
 }
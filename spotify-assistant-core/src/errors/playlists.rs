@@ -0,0 +1,31 @@
+use rspotify::ClientError;
+use rspotify::model::IdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PlaylistError {
+    #[error("The Spotify request failed: {0}")]
+    Client(#[from] ClientError),
+    #[error("{0} was not created by spotify-assistant; force the write to replace its contents anyway")]
+    NotCreatedByTool(String),
+    #[error("No playlist matches {0}")]
+    NotFound(String),
+    #[error("Removed {removed} items before a request failed, leaving the playlist at snapshot {snapshot_id}: {source}")]
+    PartiallyRemoved {
+        removed: usize,
+        snapshot_id: String,
+        #[source]
+        source: ClientError,
+    },
+    #[error("Applied {applied} moves before a request failed, leaving the playlist at snapshot {snapshot_id}: {source}")]
+    PartiallyReordered {
+        applied: usize,
+        snapshot_id: String,
+        #[source]
+        source: ClientError,
+    },
+    #[error("Invalid Spotify ID: {0}")]
+    InvalidId(#[from] IdError),
+    #[error("Unknown error occurred in playlist processing")]
+    Unknown,
+}
@@ -9,6 +9,7 @@ use unicode_normalization::UnicodeNormalization;
 use unicode_normalization::char::is_combining_mark;
 
 use crate::enums::fs::ProjectDirectories;
+use crate::errors::configuration::ConfigurationError;
 
 /// Represents an artist who is blacklisted.
 ///
@@ -200,6 +201,16 @@ mod tests {
             "Malformed blacklist TOML should trigger a panic"
         );
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn blacklist_load_reports_invalid_toml() {
+        let _guard = ENV_MUTEX.lock().unwrap_or_else(|err| err.into_inner());
+        let env = unsafe { TestEnvironment::new() };
+        fs::write(env.config_file("blacklist.toml"), invalid_blacklist_toml())
+            .expect("failed to write malformed blacklist fixture");
+        assert!(matches!(Blacklist::load(), Err(ConfigurationError::TomlDeserialize(_))));
+    }
 }
 
 /// A struct representing data for managing a blacklist of artists.
@@ -508,15 +519,27 @@ impl Blacklist {
     /// - The file is read as a string using `fs::read_to_string`.
     /// - The string is then deserialized into a `Blacklist` object using `toml::from_str`.
     fn read_blacklist() -> Blacklist {
-        match fs::read_to_string(Self::blacklist_file_path()) {
-            Ok(string) => match toml::from_str(&string) {
-                Ok(blacklist) => blacklist,
-                Err(err) => panic!("Error deserializing toml string into the blacklist: {err:?}"),
-            },
-            Err(err) => panic!("Error reading the blacklist file: {err:?}"),
+        match Self::load() {
+            Ok(blacklist) => blacklist,
+            Err(ConfigurationError::TomlDeserialize(err)) => {
+                panic!("Error deserializing toml string into the blacklist: {err}")
+            }
+            Err(err) => panic!("Error reading the blacklist file: {err}"),
         }
     }
 
+    /// Reads the blacklist file without panicking.
+    ///
+    /// # Errors
+    /// Returns [`ConfigurationError::FileNotFound`] when the file cannot be read and
+    /// [`ConfigurationError::TomlDeserialize`] when it is not a valid blacklist.
+    pub fn load() -> Result<Blacklist, ConfigurationError> {
+        let path = Self::blacklist_file_path();
+        let string = fs::read_to_string(&path)
+            .map_err(|err| ConfigurationError::FileNotFound(format!("{}: {err}", path.display())))?;
+        toml::from_str(&string).map_err(|err| ConfigurationError::TomlDeserialize(err.to_string()))
+    }
+
     ///
     /// Compares two names for equality after normalizing them.
     ///
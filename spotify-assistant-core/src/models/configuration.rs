@@ -1,7 +1,11 @@
 use crate::enums::fs::ProjectDirectories;
+use crate::errors::configuration::ConfigurationError;
+use crate::models::duplicate_rules::DuplicateRules;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use tracing::{Level, event};
 
 /// The `Configuration` structure represents the application's configuration settings,
 /// organized into several distinct sections as fields. Each field contains settings
@@ -68,6 +72,33 @@ impl Default for Configuration {
     }
 }
 impl Configuration {
+    /// Loads the configuration file without panicking.
+    ///
+    /// This is the fallible counterpart of [`Configuration::default`], intended for code paths
+    /// that can fall back to sensible defaults when the user has no configuration file yet.
+    ///
+    /// # Errors
+    /// - `ConfigurationError::FileNotFound` if the configuration file cannot be read.
+    /// - `ConfigurationError::TomlDeserialize` if the file is not a valid configuration.
+    ///
+    /// # Example
+    /// ```no_run,ignore
+    /// use spotify_assistant_core::models::configuration::Configuration;
+    ///
+    /// match Configuration::try_load() {
+    ///     Ok(config) => println!("{:?}", config.behavior()),
+    ///     Err(err) => eprintln!("{err}"),
+    /// }
+    /// ```
+    pub fn try_load() -> Result<Configuration, ConfigurationError> {
+        let path = Self::configuration_file_path();
+        let contents = fs::read_to_string(&path).map_err(|err| {
+            ConfigurationError::FileNotFound(format!("{}: {err}", path.display()))
+        })?;
+        toml::from_str(&contents)
+            .map_err(|err| ConfigurationError::TomlDeserialize(err.to_string()))
+    }
+
     /// Returns the file path to the configuration file.
     ///
     /// This function utilizes the `directories` crate to determine the operating system-specific
@@ -284,12 +315,77 @@ impl Behavior {
 ///   for blacklist entries when managing duplicates. If `true`, this enables additional filtering logic
 ///   against a blacklist during duplicate checks.
 ///
+/// * `default` - The [`DuplicateRules`] applied when writing to any playlist without its own entry.
+///   Read from the optional `[behavior.duplicates.default]` table.
+///
+/// * `playlists` - Per-playlist [`DuplicateRules`], keyed by playlist ID or name. Read from the optional
+///   `[behavior.duplicates.playlists."<id or name>"]` tables.
+///
 /// This struct is derived from `Serialize`, `Deserialize`, `Debug`, and `Clone` to enable serialization,
 /// debugging, and cloning operations where necessary.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Duplicates {
     custom_release_radar: bool,
     query_playlist_for_blacklist: bool,
+    #[serde(default)]
+    default: DuplicateRules,
+    #[serde(default)]
+    playlists: BTreeMap<String, DuplicateRules>,
+}
+
+impl Duplicates {
+    /// Reads the duplicate settings from the configuration file.
+    ///
+    /// Commands load them once and look up the [`DuplicateRules`] of every playlist they write
+    /// to with [`Duplicates::rules_for`]. When the configuration file is missing or cannot be
+    /// parsed, the defaults are used so that writes keep removing duplicates as they always have.
+    ///
+    /// # Example
+    /// ```no_run,ignore
+    /// use spotify_assistant_core::models::configuration::Duplicates;
+    ///
+    /// let duplicates = Duplicates::load();
+    /// let rules = duplicates.rules_for("37i9dQZEVXbdINACbjb1qu", "Release Radar");
+    /// let tracks = rules.resolve(tracks);
+    /// ```
+    pub fn load() -> Self {
+        match Configuration::try_load() {
+            Ok(configuration) => configuration.behavior().duplicates(),
+            Err(err) => {
+                event!(Level::WARN, "Using default duplicate rules, configuration unavailable: {err}");
+                Duplicates::default()
+            }
+        }
+    }
+    pub fn custom_release_radar(&self) -> bool {
+        self.custom_release_radar
+    }
+    pub fn query_playlist_for_blacklist(&self) -> bool {
+        self.query_playlist_for_blacklist
+    }
+
+    /// Returns the duplicate rules for a playlist.
+    ///
+    /// An entry keyed by the playlist ID wins over one keyed by its name, and names are
+    /// compared case-insensitively. Playlists without an entry use the `default` rules.
+    ///
+    /// # Arguments
+    /// * `id` - The Spotify ID of the playlist being written to.
+    /// * `name` - The display name of the playlist being written to.
+    ///
+    /// # Returns
+    /// The `DuplicateRules` that apply to the playlist.
+    pub fn rules_for(&self, id: &str, name: &str) -> DuplicateRules {
+        if let Some(rules) = self.playlists.get(id) {
+            return *rules;
+        }
+        let name = name.to_lowercase();
+        self.playlists
+            .iter()
+            .find(|(key, _)| key.to_lowercase() == name)
+            .map(|(_, rules)| *rules)
+            .unwrap_or(self.default)
+    }
 }
 
 /// Represents the structure for command-line interface (CLI) configuration settings.
@@ -574,6 +670,7 @@ pub struct Utility {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::enums::duplication::{DuplicatePolicy, DuplicatePreference};
     use crate::test_support::{
         configuration_toml, invalid_configuration_toml, TestEnvironment, ENV_MUTEX,
     };
@@ -633,4 +730,20 @@ mod tests {
             .expect("failed to write valid configuration fixture");
 
     }
+
+    #[test]
+    fn duplicate_rules_resolve_by_id_then_name_then_default() {
+        let env = unsafe { TestEnvironment::new() };
+        let duplicates = configuration_fixture(&env).behavior().duplicates();
+
+        let workout = duplicates.rules_for("unknown-id", "workout");
+        assert_eq!(workout.preference(), DuplicatePreference::PreferClean);
+
+        let fallback = duplicates.rules_for("unknown-id", "Unconfigured");
+        assert_eq!(fallback.policy(), DuplicatePolicy::Remove);
+        assert_eq!(
+            fallback.preference(),
+            DuplicatePreference::PreferOriginalAlbum
+        );
+    }
 }
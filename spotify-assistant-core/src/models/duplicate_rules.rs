@@ -0,0 +1,252 @@
+use std::collections::{HashMap, HashSet};
+
+use rspotify::model::FullTrack;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::enums::duplication::{DuplicatePolicy, DuplicatePreference};
+use crate::models::full_track_fingerprint::{FullTrackFingerprint, PlaylistFingerprints};
+
+/// Duplicate handling applied whenever tracks are written to a playlist.
+///
+/// The rules are read from the `[behavior.duplicates]` section of the configuration file. A
+/// `[behavior.duplicates.default]` table applies to every playlist and
+/// `[behavior.duplicates.playlists."<id or name>"]` tables override it per playlist.
+///
+/// # Fields
+///
+/// * `policy` - Whether duplicates are kept or removed.
+/// * `preference` - Which copy survives when duplicates are removed.
+///
+/// # Example
+/// ```toml
+/// [behavior.duplicates.default]
+/// policy = "remove"
+/// preference = "keep_earliest"
+///
+/// [behavior.duplicates.playlists."Workout"]
+/// policy = "remove"
+/// preference = "prefer_clean"
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DuplicateRules {
+    #[serde(default)]
+    policy: DuplicatePolicy,
+    #[serde(default)]
+    preference: DuplicatePreference,
+}
+
+impl DuplicateRules {
+    pub fn new(policy: DuplicatePolicy, preference: DuplicatePreference) -> Self {
+        DuplicateRules { policy, preference }
+    }
+    pub fn policy(&self) -> DuplicatePolicy {
+        self.policy
+    }
+    pub fn preference(&self) -> DuplicatePreference {
+        self.preference
+    }
+
+    /// Applies the rules to the tracks about to be written to a playlist.
+    ///
+    /// Duplicates are grouped by their [`FullTrackFingerprint`], or by base title and artists
+    /// when the preference compares explicit and clean versions. Each group is reduced to the
+    /// copy chosen by the preference, placed at the position of the group's earliest copy.
+    /// Tracks that cannot be fingerprinted (local files, tracks without an ISRC) are always kept.
+    ///
+    /// # Returns
+    /// The tracks in their original order, without duplicates unless the policy is `Keep`.
+    pub fn resolve(&self, tracks: Vec<FullTrack>) -> Vec<FullTrack> {
+        if self.policy == DuplicatePolicy::Keep {
+            return tracks;
+        }
+        let mut group_of_key: HashMap<GroupKey, usize> = HashMap::new();
+        let mut groups: Vec<Vec<FullTrack>> = Vec::new();
+        let mut slots: Vec<Slot> = Vec::with_capacity(tracks.len());
+        for track in tracks {
+            let Some(key) = self.group_key(&track) else {
+                slots.push(Slot::Single(Box::new(track)));
+                continue;
+            };
+            match group_of_key.get(&key) {
+                Some(&index) => groups[index].push(track),
+                None => {
+                    group_of_key.insert(key, groups.len());
+                    slots.push(Slot::Group(groups.len()));
+                    groups.push(vec![track]);
+                }
+            }
+        }
+
+        let mut removed = 0;
+        let resolved = slots
+            .into_iter()
+            .map(|slot| match slot {
+                Slot::Single(track) => *track,
+                Slot::Group(index) => {
+                    let group = &groups[index];
+                    removed += group.len() - 1;
+                    self.survivor(group)
+                }
+            })
+            .collect::<Vec<FullTrack>>();
+        debug!(duplicates_removed = removed, preference = ?self.preference);
+        resolved
+    }
+
+    /// Resolves `additions` and drops any that duplicate a track already in `existing`.
+    ///
+    /// The guard against the existing tracks uses [`PlaylistFingerprints::filter_duplicates`],
+    /// and additionally compares base titles and artists when the preference matches versions.
+    ///
+    /// # Returns
+    /// The additions that can be appended to the playlist, in their original order.
+    pub fn filter_additions(
+        &self,
+        existing: &[FullTrack],
+        additions: Vec<FullTrack>,
+    ) -> Vec<FullTrack> {
+        if self.policy == DuplicatePolicy::Keep {
+            return additions;
+        }
+        let additions = self.resolve(additions);
+        let existing_fps = PlaylistFingerprints::new(existing);
+        let allowed_ids = PlaylistFingerprints::new(&additions)
+            .filter_duplicates(existing_fps)
+            .into_iter()
+            .map(|fingerprint| fingerprint.id())
+            .collect::<HashSet<String>>();
+        let existing_versions = if self.preference.matches_versions() {
+            existing
+                .iter()
+                .map(FullTrackFingerprint::version_key_of)
+                .collect::<HashSet<(String, Vec<String>)>>()
+        } else {
+            HashSet::new()
+        };
+
+        additions
+            .into_iter()
+            .filter(|track| match FullTrackFingerprint::try_new(track) {
+                Some(fingerprint) => {
+                    allowed_ids.contains(&fingerprint.id())
+                        && !existing_versions.contains(&fingerprint.version_key())
+                }
+                None => true,
+            })
+            .collect()
+    }
+
+    fn group_key(&self, track: &FullTrack) -> Option<GroupKey> {
+        let fingerprint = FullTrackFingerprint::try_new(track)?;
+        if self.preference.matches_versions() {
+            Some(GroupKey::Version(fingerprint.version_key()))
+        } else {
+            Some(GroupKey::Recording(fingerprint))
+        }
+    }
+
+    fn survivor(&self, group: &[FullTrack]) -> FullTrack {
+        let preferred = match self.preference {
+            DuplicatePreference::KeepEarliest => None,
+            DuplicatePreference::PreferOriginalAlbum => group
+                .iter()
+                .find(|track| track.album.album_type.as_deref() != Some("compilation")),
+            DuplicatePreference::PreferExplicit => group.iter().find(|track| track.explicit),
+            DuplicatePreference::PreferClean => group.iter().find(|track| !track.explicit),
+        };
+        preferred.unwrap_or(&group[0]).clone()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum GroupKey {
+    Recording(FullTrackFingerprint),
+    Version((String, Vec<String>)),
+}
+
+enum Slot {
+    Single(Box<FullTrack>),
+    Group(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+
+    fn track(id: char, name: &str, album: &str, isrc: Option<&str>) -> FullTrack {
+        let id = id.to_string().repeat(22);
+        OfflineObjects::full_track(&id, name, "Artist", album, isrc, 200_000)
+    }
+
+    fn names(tracks: &[FullTrack]) -> Vec<String> {
+        tracks.iter().map(|track| track.name.clone()).collect()
+    }
+
+    fn remove(preference: DuplicatePreference) -> DuplicateRules {
+        DuplicateRules::new(DuplicatePolicy::Remove, preference)
+    }
+
+    #[test]
+    fn keep_policy_leaves_tracks_untouched() {
+        let song = track('A', "Song", "Album", Some("ISRC1"));
+        let rules = DuplicateRules::new(DuplicatePolicy::Keep, DuplicatePreference::KeepEarliest);
+        assert_eq!(rules.resolve(vec![song.clone(), song]).len(), 2);
+    }
+
+    #[test]
+    fn keep_earliest_keeps_first_position() {
+        let tracks = vec![
+            track('A', "Song", "Album", Some("ISRC1")),
+            track('B', "Other", "Album", Some("ISRC2")),
+            track('C', "Song", "Hits", Some("ISRC1")),
+        ];
+        let resolved = remove(DuplicatePreference::KeepEarliest).resolve(tracks);
+        assert_eq!(names(&resolved), vec!["Song", "Other"]);
+        assert_eq!(resolved[0].album.name, "Album");
+    }
+
+    #[test]
+    fn prefer_original_album_skips_compilations() {
+        let mut compilation = track('A', "Song", "Hits", Some("ISRC1"));
+        compilation.album.album_type = Some("compilation".to_string());
+        let original = track('B', "Song", "Album", Some("ISRC1"));
+        let resolved =
+            remove(DuplicatePreference::PreferOriginalAlbum).resolve(vec![compilation, original]);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].album.name, "Album");
+    }
+
+    #[test]
+    fn explicit_and_clean_preferences_match_versions_with_different_isrcs() {
+        let clean = track('A', "Song", "Album", Some("ISRC1"));
+        let mut explicit = track('B', "Song", "Album", Some("ISRC2"));
+        explicit.explicit = true;
+        let tracks = vec![clean, explicit];
+
+        let resolved = remove(DuplicatePreference::PreferExplicit).resolve(tracks.clone());
+        assert_eq!(resolved.len(), 1);
+        assert!(resolved[0].explicit);
+
+        let resolved = remove(DuplicatePreference::PreferClean).resolve(tracks.clone());
+        assert_eq!(resolved.len(), 1);
+        assert!(!resolved[0].explicit);
+
+        let resolved = remove(DuplicatePreference::KeepEarliest).resolve(tracks);
+        assert_eq!(resolved.len(), 2);
+    }
+
+    #[test]
+    fn filter_additions_drops_tracks_already_in_playlist() {
+        let existing = vec![track('A', "Song", "Album", Some("ISRC1"))];
+        let additions = vec![
+            track('B', "Song", "Hits", Some("ISRC1")),
+            track('C', "New", "Album", Some("ISRC3")),
+            track('D', "Local", "Album", None),
+        ];
+        let filtered =
+            remove(DuplicatePreference::KeepEarliest).filter_additions(&existing, additions);
+        assert_eq!(names(&filtered), vec!["New", "Local"]);
+    }
+}
@@ -1,3 +1,5 @@
+use rspotify::model::FullArtist;
+use rspotify::prelude::Id;
 use serde::Deserialize;
 use std::collections::HashSet;
 use std::path::PathBuf;
@@ -26,6 +28,21 @@ impl ArtistLite {
     pub fn genres_or_empty(&self) -> &[String] { &self.genres }
 }
 
+impl From<&FullArtist> for ArtistLite {
+    fn from(artist: &FullArtist) -> Self {
+        ArtistLite {
+            id: artist.id.id().to_string(),
+            name: artist.name.clone(),
+            popularity: Some(artist.popularity as i32),
+            genres: artist.genres.clone(),
+            followers: Followers { total: artist.followers.total as i64 },
+            external_urls: ExternalUrls {
+                spotify: artist.external_urls.get("spotify").cloned().unwrap_or_default(),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 struct Followers {
     #[serde(default)] total: i64
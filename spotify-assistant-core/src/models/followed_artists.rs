@@ -0,0 +1,232 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use rspotify::model::FullArtist;
+use rspotify::prelude::Id;
+use serde::{Deserialize, Serialize};
+
+/// A followed artist as recorded by one sync of the followed artists.
+///
+/// # Fields
+///
+/// * `artist_id` - The Spotify ID of the artist.
+/// * `name` - The name of the artist.
+/// * `followers` - The number of followers of the artist.
+/// * `popularity` - The popularity of the artist, between 0 and 100.
+/// * `genres` - The genres of the artist.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtistSnapshot {
+    pub artist_id: String,
+    pub name: String,
+    pub followers: u32,
+    pub popularity: u32,
+    pub genres: Vec<String>,
+}
+
+impl From<&FullArtist> for ArtistSnapshot {
+    fn from(artist: &FullArtist) -> Self {
+        ArtistSnapshot {
+            artist_id: artist.id.id().to_string(),
+            name: artist.name.clone(),
+            followers: artist.followers.total,
+            popularity: artist.popularity,
+            genres: artist.genres.clone(),
+        }
+    }
+}
+
+/// What changed about a followed artist between two syncs.
+///
+/// Variants:
+/// - `Followed`: The artist was followed.
+/// - `Unfollowed`: The artist was unfollowed.
+/// - `Popularity`: The popularity of the artist changed.
+/// - `Followers`: The follower count of the artist changed.
+/// - `Genres`: Genres were added to or removed from the artist.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "change")]
+pub enum ArtistChangeKind {
+    Followed,
+    Unfollowed,
+    Popularity { from: u32, to: u32 },
+    Followers { from: u32, to: u32 },
+    Genres { added: Vec<String>, removed: Vec<String> },
+}
+
+impl fmt::Display for ArtistChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArtistChangeKind::Followed => write!(f, "followed"),
+            ArtistChangeKind::Unfollowed => write!(f, "unfollowed"),
+            ArtistChangeKind::Popularity { from, to } => {
+                write!(f, "popularity {from} -> {to} ({:+})", i64::from(*to) - i64::from(*from))
+            }
+            ArtistChangeKind::Followers { from, to } => {
+                write!(f, "followers {from} -> {to} ({:+})", i64::from(*to) - i64::from(*from))
+            }
+            ArtistChangeKind::Genres { added, removed } => {
+                let changes = added
+                    .iter()
+                    .map(|genre| format!("+{genre}"))
+                    .chain(removed.iter().map(|genre| format!("-{genre}")))
+                    .collect::<Vec<String>>();
+                write!(f, "genres {}", changes.join(", "))
+            }
+        }
+    }
+}
+
+/// A change of one followed artist.
+///
+/// # Fields
+///
+/// * `artist_id` - The Spotify ID of the artist.
+/// * `name` - The name of the artist.
+/// * `kind` - What changed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ArtistChange {
+    pub artist_id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: ArtistChangeKind,
+}
+
+impl ArtistSnapshot {
+    /// Compares two syncs of the followed artists.
+    ///
+    /// # Returns
+    /// The follows and unfollows first, then the popularity, follower and genre changes of the
+    /// artists followed in both syncs. Each group is ordered by artist name.
+    pub fn changes(before: &[ArtistSnapshot], after: &[ArtistSnapshot]) -> Vec<ArtistChange> {
+        let before_by_id = before
+            .iter()
+            .map(|artist| (artist.artist_id.as_str(), artist))
+            .collect::<HashMap<&str, &ArtistSnapshot>>();
+        let after_by_id = after
+            .iter()
+            .map(|artist| (artist.artist_id.as_str(), artist))
+            .collect::<HashMap<&str, &ArtistSnapshot>>();
+        let change = |artist: &ArtistSnapshot, kind: ArtistChangeKind| ArtistChange {
+            artist_id: artist.artist_id.clone(),
+            name: artist.name.clone(),
+            kind,
+        };
+
+        let mut follows = Vec::new();
+        let mut updates = Vec::new();
+        for artist in after {
+            let Some(previous) = before_by_id.get(artist.artist_id.as_str()) else {
+                follows.push(change(artist, ArtistChangeKind::Followed));
+                continue;
+            };
+            if previous.popularity != artist.popularity {
+                updates.push(change(
+                    artist,
+                    ArtistChangeKind::Popularity {
+                        from: previous.popularity,
+                        to: artist.popularity,
+                    },
+                ));
+            }
+            if previous.followers != artist.followers {
+                updates.push(change(
+                    artist,
+                    ArtistChangeKind::Followers {
+                        from: previous.followers,
+                        to: artist.followers,
+                    },
+                ));
+            }
+            let added = artist
+                .genres
+                .iter()
+                .filter(|genre| !previous.genres.contains(genre))
+                .cloned()
+                .collect::<Vec<String>>();
+            let removed = previous
+                .genres
+                .iter()
+                .filter(|genre| !artist.genres.contains(genre))
+                .cloned()
+                .collect::<Vec<String>>();
+            if !added.is_empty() || !removed.is_empty() {
+                updates.push(change(artist, ArtistChangeKind::Genres { added, removed }));
+            }
+        }
+        for artist in before {
+            if !after_by_id.contains_key(artist.artist_id.as_str()) {
+                follows.push(change(artist, ArtistChangeKind::Unfollowed));
+            }
+        }
+        follows.sort_by_key(|change| change.name.to_lowercase());
+        updates.sort_by_key(|change| change.name.to_lowercase());
+        follows.extend(updates);
+        follows
+    }
+
+    /// How the number of followed artists per genre changed between two syncs.
+    ///
+    /// # Returns
+    /// The genres whose count changed with the difference, largest change first.
+    pub fn genre_shift(before: &[ArtistSnapshot], after: &[ArtistSnapshot]) -> Vec<(String, i64)> {
+        let mut shift: BTreeMap<&str, i64> = BTreeMap::new();
+        for genre in before.iter().flat_map(|artist| artist.genres.iter()) {
+            *shift.entry(genre).or_default() -= 1;
+        }
+        for genre in after.iter().flat_map(|artist| artist.genres.iter()) {
+            *shift.entry(genre).or_default() += 1;
+        }
+        let mut shift = shift
+            .into_iter()
+            .filter(|(_, difference)| *difference != 0)
+            .map(|(genre, difference)| (genre.to_string(), difference))
+            .collect::<Vec<(String, i64)>>();
+        shift.sort_by(|a, b| b.1.abs().cmp(&a.1.abs()).then_with(|| a.0.cmp(&b.0)));
+        shift
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artist(id: char, name: &str, followers: u32, popularity: u32, genres: &[&str]) -> ArtistSnapshot {
+        ArtistSnapshot {
+            artist_id: id.to_string().repeat(22),
+            name: name.to_string(),
+            followers,
+            popularity,
+            genres: genres.iter().map(|genre| genre.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn compares_two_syncs() {
+        let before = vec![
+            artist('A', "Alpha", 100, 40, &["rock"]),
+            artist('B', "Bravo", 200, 50, &["pop"]),
+        ];
+        let after = vec![
+            artist('A', "Alpha", 150, 45, &["rock", "indie rock"]),
+            artist('C', "Charlie", 10, 20, &["pop"]),
+        ];
+        let changes = ArtistSnapshot::changes(&before, &after)
+            .into_iter()
+            .map(|change| format!("{}: {}", change.name, change.kind))
+            .collect::<Vec<String>>();
+        assert_eq!(
+            changes,
+            vec![
+                "Bravo: unfollowed",
+                "Charlie: followed",
+                "Alpha: popularity 40 -> 45 (+5)",
+                "Alpha: followers 100 -> 150 (+50)",
+                "Alpha: genres +indie rock",
+            ]
+        );
+        assert_eq!(
+            ArtistSnapshot::genre_shift(&before, &after),
+            vec![("indie rock".to_string(), 1)]
+        );
+    }
+}
@@ -15,8 +15,8 @@ impl PlaylistFingerprints {
     pub fn new(tracks: &[FullTrack]) -> Self {
         let _new_span = debug_span!("new-fps").entered();
         let full_fp = tracks
-            .into_iter()
-            .map(FullTrackFingerprint::new)
+            .iter()
+            .filter_map(FullTrackFingerprint::try_new)
             .collect::<HashSet<FullTrackFingerprint>>();
         let (distinct_fp, duplicates_fp) = PlaylistFingerprints::distinct_fingerprints(&full_fp);
         PlaylistFingerprints {
@@ -85,7 +85,6 @@ pub struct FullTrackFingerprint {
     title: String,
     base_artists: Vec<String>,
     duration: i32,
-    #[allow(dead_code)]
     id: String,
 }
 
@@ -120,6 +119,59 @@ impl FullTrackFingerprint {
             id,
         }
     }
+    /// Builds a fingerprint without panicking on tracks that lack an ISRC or a track ID,
+    /// such as local files. Returns `None` for those tracks.
+    pub fn try_new(track: &FullTrack) -> Option<Self> {
+        if track.id.is_none() || !track.external_ids.contains_key("isrc") {
+            trace!(track = ?track.name, "Track cannot be fingerprinted");
+            return None;
+        }
+        Some(FullTrackFingerprint::new(track))
+    }
+    pub fn isrc(&self) -> Option<String> {
+        self.isrc.clone()
+    }
+    pub fn title(&self) -> String {
+        self.title.clone()
+    }
+    pub fn base_artists(&self) -> Vec<String> {
+        self.base_artists.clone()
+    }
+    pub fn duration(&self) -> i32 {
+        self.duration
+    }
+    pub fn id(&self) -> String {
+        self.id.clone()
+    }
+    /// Returns the base title and artists of the track. Different versions of the same song
+    /// (single, album, remaster, explicit/clean) share this key even though their ISRCs differ.
+    pub fn version_key(&self) -> (String, Vec<String>) {
+        (Self::base_title(&self.title), self.base_artists.clone())
+    }
+    /// Builds the same key as [`FullTrackFingerprint::version_key`] directly from a track, which
+    /// also works for tracks that cannot be fingerprinted.
+    pub fn version_key_of(track: &FullTrack) -> (String, Vec<String>) {
+        (
+            Self::base_title(&Self::normalize_title(&track.name)),
+            Self::lower_names(&track.artists),
+        )
+    }
+    /// Reduces a title to its base form by dropping bracketed suffixes and
+    /// " - Remastered 2011"-style tails.
+    pub fn base_title(title: &str) -> String {
+        let head = title.split(" - ").next().unwrap_or(title);
+        let mut base = String::with_capacity(head.len());
+        let mut depth = 0usize;
+        for character in head.chars() {
+            match character {
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth = depth.saturating_sub(1),
+                _ if depth == 0 => base.push(character),
+                _ => {}
+            }
+        }
+        base.split_whitespace().collect::<Vec<&str>>().join(" ")
+    }
     fn normalize_title(t: &str) -> String {
         t.to_lowercase()
     }
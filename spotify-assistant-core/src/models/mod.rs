@@ -1,4 +1,7 @@
 pub mod blacklist;
 pub mod configuration;
+pub mod duplicate_rules;
 pub mod filtering;
+pub mod followed_artists;
 pub mod full_track_fingerprint;
+pub mod smart_playlist;
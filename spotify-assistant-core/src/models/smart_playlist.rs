@@ -0,0 +1,663 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use rspotify::prelude::Id;
+use serde::{Deserialize, Serialize};
+use tracing::{Level, event};
+
+use crate::actions::playlists::combine::{LIKED_SONGS, PlaylistCombiner, SourceTrack};
+use crate::enums::combine::CombineOrder;
+use crate::enums::fs::ProjectDirectories;
+use crate::enums::track_collection::TrackCollection;
+
+/// The track fields a [`SmartRule`] can test.
+///
+/// Variants:
+/// - `Popularity`: The track popularity, from 0 to 100.
+/// - `Explicit`: Whether the track is explicit (`true`/`false`).
+/// - `Duration`: The track duration, e.g. `5m`, `4:30`, `270s` or `270000ms`. Bare numbers are seconds.
+/// - `Released`: The album release date, as `YYYY`, `YYYY-MM` or `YYYY-MM-DD`.
+/// - `AlbumType`: The album type (`album`, `single` or `compilation`).
+/// - `Artist`: Any of the track's artists, by name or ID.
+/// - `Added`: The date the track was added to the source, as `YYYY`, `YYYY-MM` or `YYYY-MM-DD`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SmartField {
+    Popularity,
+    Explicit,
+    Duration,
+    Released,
+    AlbumType,
+    Artist,
+    Added,
+}
+
+impl SmartField {
+    fn is_textual(&self) -> bool {
+        matches!(
+            self,
+            SmartField::Explicit | SmartField::AlbumType | SmartField::Artist
+        )
+    }
+}
+
+impl FromStr for SmartField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "popularity" => Ok(SmartField::Popularity),
+            "explicit" => Ok(SmartField::Explicit),
+            "duration" | "length" => Ok(SmartField::Duration),
+            "released" | "release" | "year" => Ok(SmartField::Released),
+            "album_type" | "album-type" | "type" => Ok(SmartField::AlbumType),
+            "artist" => Ok(SmartField::Artist),
+            "added" | "added_at" | "added-at" => Ok(SmartField::Added),
+            other => Err(format!("Unknown smart playlist field: {other}")),
+        }
+    }
+}
+
+impl fmt::Display for SmartField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SmartField::Popularity => "popularity",
+            SmartField::Explicit => "explicit",
+            SmartField::Duration => "duration",
+            SmartField::Released => "released",
+            SmartField::AlbumType => "album_type",
+            SmartField::Artist => "artist",
+            SmartField::Added => "added",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The comparison a [`SmartRule`] applies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    /// Operators in the order they are searched for, so that `<=` is found before `<`.
+    const OPERATORS: [(&'static str, Comparison); 7] = [
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("!=", Comparison::NotEqual),
+        ("==", Comparison::Equal),
+        ("=", Comparison::Equal),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+    ];
+
+    fn holds(&self, ordering: std::cmp::Ordering) -> bool {
+        use std::cmp::Ordering::{Equal, Greater, Less};
+        match self {
+            Comparison::Equal => ordering == Equal,
+            Comparison::NotEqual => ordering != Equal,
+            Comparison::Less => ordering == Less,
+            Comparison::LessOrEqual => ordering != Greater,
+            Comparison::Greater => ordering == Greater,
+            Comparison::GreaterOrEqual => ordering != Less,
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = match self {
+            Comparison::Equal => "=",
+            Comparison::NotEqual => "!=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+        };
+        write!(f, "{operator}")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum RuleValue {
+    Number(f64),
+    Flag(bool),
+    Date(String),
+    Text(String),
+}
+
+/// A single condition of a smart playlist, written as `<field> <operator> <value>`.
+///
+/// Rules are stored in their written form, e.g. `"popularity < 40"`, `"released >= 2010"`,
+/// `"explicit = false"` or `"duration < 5m"`. Text fields only support `=` and `!=`.
+///
+/// # Example
+/// ```
+/// use spotify_assistant_core::models::smart_playlist::SmartRule;
+/// let rule: SmartRule = "duration < 5m".parse().unwrap();
+/// assert_eq!(rule.to_string(), "duration < 5m");
+/// assert!("artist > Blur".parse::<SmartRule>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SmartRule {
+    field: SmartField,
+    comparison: Comparison,
+    raw_value: String,
+    value: RuleValue,
+}
+
+impl SmartRule {
+    pub fn field(&self) -> SmartField {
+        self.field
+    }
+    pub fn comparison(&self) -> Comparison {
+        self.comparison
+    }
+
+    fn matches(&self, facts: &TrackFacts) -> bool {
+        let ordering = match (&self.field, &self.value) {
+            (SmartField::Popularity, RuleValue::Number(value)) => {
+                f64::from(facts.popularity).total_cmp(value)
+            }
+            (SmartField::Duration, RuleValue::Number(value)) => {
+                (facts.duration_ms as f64).total_cmp(value)
+            }
+            (SmartField::Explicit, RuleValue::Flag(value)) => facts.explicit.cmp(value),
+            (SmartField::Released, RuleValue::Date(value)) => {
+                match facts.release_date.as_deref() {
+                    Some(release_date) => Self::compare_dates(release_date, value),
+                    // Without a release date no date comparison can hold.
+                    None => return false,
+                }
+            }
+            (SmartField::Added, RuleValue::Date(value)) => {
+                match facts.added_at.as_deref() {
+                    Some(added_at) => Self::compare_dates(added_at, value),
+                    // Without an addition date no date comparison can hold.
+                    None => return false,
+                }
+            }
+            (SmartField::AlbumType, RuleValue::Text(value)) => {
+                facts.album_type.to_lowercase().cmp(&value.to_lowercase())
+            }
+            (SmartField::Artist, RuleValue::Text(value)) => {
+                let value = value.to_lowercase();
+                let credited = facts.artist_ids.iter().any(|id| id.to_lowercase() == value)
+                    || facts
+                        .artist_names
+                        .iter()
+                        .any(|name| name.to_lowercase() == value);
+                if credited {
+                    std::cmp::Ordering::Equal
+                } else {
+                    std::cmp::Ordering::Less
+                }
+            }
+            _ => return false,
+        };
+        self.comparison.holds(ordering)
+    }
+
+    /// Compares a `YYYY[-MM[-DD]]` date with `value` at the precision of `value`, so that
+    /// `released <= 2015` includes every release of 2015.
+    fn compare_dates(date: &str, value: &str) -> std::cmp::Ordering {
+        let padded = Self::pad_date(date);
+        let precision = value.len().min(padded.len());
+        padded[..precision].cmp(value)
+    }
+
+    fn pad_date(date: &str) -> String {
+        match date.len() {
+            4 => format!("{date}-01-01"),
+            7 => format!("{date}-01"),
+            _ => date.chars().take(10).collect(),
+        }
+    }
+
+    fn parse_value(field: SmartField, value: &str) -> Result<RuleValue, String> {
+        let invalid = || format!("Invalid value for {field}: {value}");
+        match field {
+            SmartField::Popularity => value
+                .parse::<f64>()
+                .map(RuleValue::Number)
+                .map_err(|_| invalid()),
+            SmartField::Duration => Self::parse_duration_ms(value)
+                .map(RuleValue::Number)
+                .ok_or_else(invalid),
+            SmartField::Explicit => match value.to_lowercase().as_str() {
+                "true" | "yes" => Ok(RuleValue::Flag(true)),
+                "false" | "no" => Ok(RuleValue::Flag(false)),
+                _ => Err(invalid()),
+            },
+            SmartField::Released | SmartField::Added => {
+                let valid = [4, 7, 10].contains(&value.len())
+                    && value
+                        .chars()
+                        .enumerate()
+                        .all(|(index, character)| match index {
+                            4 | 7 => character == '-',
+                            _ => character.is_ascii_digit(),
+                        });
+                if valid {
+                    Ok(RuleValue::Date(value.to_string()))
+                } else {
+                    Err(invalid())
+                }
+            }
+            SmartField::AlbumType | SmartField::Artist => Ok(RuleValue::Text(value.to_string())),
+        }
+    }
+
+    fn parse_duration_ms(value: &str) -> Option<f64> {
+        let value = value.trim().to_lowercase();
+        if let Some((minutes, seconds)) = value.split_once(':') {
+            return Some((minutes.parse::<f64>().ok()? * 60.0 + seconds.parse::<f64>().ok()?) * 1000.0);
+        }
+        let (number, factor) = if let Some(number) = value.strip_suffix("ms") {
+            (number, 1.0)
+        } else if let Some(number) = value.strip_suffix("min") {
+            (number, 60_000.0)
+        } else if let Some(number) = value.strip_suffix('m') {
+            (number, 60_000.0)
+        } else if let Some(number) = value.strip_suffix('s') {
+            (number, 1000.0)
+        } else {
+            (value.as_str(), 1000.0)
+        };
+        number.trim().parse::<f64>().ok().map(|number| number * factor)
+    }
+}
+
+impl FromStr for SmartRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (index, operator, comparison) = Comparison::OPERATORS
+            .iter()
+            .filter_map(|(operator, comparison)| {
+                s.find(operator).map(|index| (index, *operator, *comparison))
+            })
+            .min_by_key(|(index, _, _)| *index)
+            .ok_or_else(|| format!("Missing comparison operator in rule: {s}"))?;
+        let field = s[..index].parse::<SmartField>()?;
+        let raw_value = s[index + operator.len()..].trim().to_string();
+        if raw_value.is_empty() {
+            return Err(format!("Missing value in rule: {s}"));
+        }
+        let ordered = !matches!(comparison, Comparison::Equal | Comparison::NotEqual);
+        if field.is_textual() && ordered {
+            return Err(format!("{field} only supports = and !="));
+        }
+        Ok(SmartRule {
+            field,
+            comparison,
+            value: Self::parse_value(field, &raw_value)?,
+            raw_value,
+        })
+    }
+}
+
+impl TryFrom<String> for SmartRule {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<SmartRule> for String {
+    fn from(rule: SmartRule) -> Self {
+        rule.to_string()
+    }
+}
+
+impl fmt::Display for SmartRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.field, self.comparison, self.raw_value)
+    }
+}
+
+/// The values of one track that rules are evaluated against.
+#[derive(Clone, Debug, Default)]
+struct TrackFacts {
+    popularity: u32,
+    explicit: bool,
+    duration_ms: i64,
+    release_date: Option<String>,
+    album_type: String,
+    artist_ids: Vec<String>,
+    artist_names: Vec<String>,
+    added_at: Option<String>,
+}
+
+impl TrackFacts {
+    /// Collects the facts of every track through the [`TrackCollection`] accessors.
+    fn collect(tracks: &[SourceTrack]) -> Vec<TrackFacts> {
+        let collection =
+            TrackCollection::FullTrack(tracks.iter().map(|track| track.track.clone()).collect());
+        let popularity = collection.popularity().unwrap_or_default();
+        let explicit = collection.explicit().unwrap_or_default();
+        let durations = collection.durations_ms().unwrap_or_default();
+        let album_types = collection.album_types().unwrap_or_default();
+        let artist_ids = collection.artist_ids().unwrap_or_default();
+        let artist_names = collection.artist_names().unwrap_or_default();
+
+        tracks
+            .iter()
+            .enumerate()
+            .map(|(index, track)| TrackFacts {
+                popularity: popularity.get(index).copied().unwrap_or_default(),
+                explicit: explicit.get(index).copied().unwrap_or_default(),
+                duration_ms: durations.get(index).copied().unwrap_or_default(),
+                // Read directly, as the collection reports a missing date as "unknown".
+                release_date: track
+                    .track
+                    .album
+                    .release_date
+                    .clone()
+                    .filter(|release_date| !release_date.is_empty()),
+                album_type: album_types.get(index).cloned().unwrap_or_default(),
+                artist_ids: artist_ids
+                    .get(index)
+                    .map(|ids| ids.iter().map(|id| id.id().to_string()).collect())
+                    .unwrap_or_default(),
+                artist_names: artist_names.get(index).cloned().unwrap_or_default(),
+                added_at: track
+                    .added_at
+                    .map(|added_at| added_at.format("%Y-%m-%d").to_string()),
+            })
+            .collect()
+    }
+}
+
+fn default_source() -> String {
+    LIKED_SONGS.to_string()
+}
+
+/// A saved smart playlist: a source, rules every track has to satisfy, and how to write the
+/// result.
+///
+/// # Fields
+///
+/// * `name` - The name of the Spotify playlist the result is written to.
+/// * `source` - The source playlist (name or ID), or `@liked` for the liked songs.
+/// * `rules` - The rules a track has to satisfy to be included.
+/// * `order` - The order the tracks are written in.
+/// * `limit` - The maximum number of tracks written, if any.
+///
+/// # Example
+/// ```toml
+/// [[playlist]]
+/// name = "Deep Cuts 2010-2015"
+/// source = "@liked"
+/// rules = ["released >= 2010", "released <= 2015", "popularity < 40", "explicit = false", "duration < 5m"]
+/// order = "added"
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SmartPlaylist {
+    pub name: String,
+    #[serde(default = "default_source")]
+    pub source: String,
+    #[serde(default)]
+    pub rules: Vec<SmartRule>,
+    #[serde(default)]
+    pub order: CombineOrder,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+impl SmartPlaylist {
+    pub fn new(name: String, source: String, rules: Vec<SmartRule>) -> Self {
+        SmartPlaylist {
+            name,
+            source,
+            rules,
+            order: CombineOrder::default(),
+            limit: None,
+        }
+    }
+
+    /// The source tracks that satisfy every rule, in source order.
+    pub fn matching(&self, tracks: &[SourceTrack]) -> Vec<SourceTrack> {
+        TrackFacts::collect(tracks)
+            .iter()
+            .zip(tracks)
+            .filter(|(facts, _)| self.rules.iter().all(|rule| rule.matches(facts)))
+            .map(|(_, track)| track.clone())
+            .collect()
+    }
+
+    /// Puts matching tracks in the definition's order and applies its limit.
+    pub fn arrange(&self, matching: Vec<SourceTrack>) -> Vec<rspotify::model::FullTrack> {
+        let mut ordered = PlaylistCombiner::order(matching, self.order);
+        if let Some(limit) = self.limit {
+            ordered.truncate(limit);
+        }
+        ordered
+    }
+
+    /// Selects the tracks the smart playlist holds for the given source tracks.
+    pub fn select(&self, tracks: &[SourceTrack]) -> Vec<rspotify::model::FullTrack> {
+        self.arrange(self.matching(tracks))
+    }
+
+    /// A one-line description of the definition, also used as the playlist description.
+    pub fn describe(&self) -> String {
+        let rules = if self.rules.is_empty() {
+            "all tracks".to_string()
+        } else {
+            self.rules
+                .iter()
+                .map(SmartRule::to_string)
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        format!("Smart playlist from {}: {}", self.source, rules)
+    }
+}
+
+/// The saved smart playlists, stored in `smart_playlists.toml` in the configuration directory.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SmartPlaylists {
+    #[serde(default, rename = "playlist")]
+    playlists: Vec<SmartPlaylist>,
+}
+
+impl SmartPlaylists {
+    /// The location of the smart playlist definitions.
+    pub fn file_path() -> PathBuf {
+        ProjectDirectories::Config.path().join("smart_playlists.toml")
+    }
+
+    /// Loads the saved definitions. A missing file means there are none yet.
+    ///
+    /// # Errors
+    /// Returns an error if the file exists but cannot be read or parsed, so that a broken file
+    /// is never silently overwritten.
+    pub fn load() -> io::Result<Self> {
+        let span = tracing::span!(Level::INFO, "SmartPlaylists.load");
+        let _enter = span.enter();
+
+        match fs::read_to_string(Self::file_path()) {
+            Ok(contents) => Self::parse(&contents),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                event!(Level::DEBUG, "No smart playlist definitions found");
+                Ok(SmartPlaylists::default())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Parses definitions written by [`Self::save`].
+    pub fn parse(contents: &str) -> io::Result<Self> {
+        toml::from_str(contents).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid smart playlist definitions in {}: {err}", Self::file_path().display()),
+            )
+        })
+    }
+
+    /// Writes the definitions back to [`Self::file_path`].
+    pub fn save(&self) -> io::Result<()> {
+        let contents = toml::to_string_pretty(self).map_err(io::Error::other)?;
+        let path = Self::file_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)
+    }
+
+    pub fn playlists(&self) -> &[SmartPlaylist] {
+        &self.playlists
+    }
+
+    /// Finds a definition by playlist name, ignoring case.
+    pub fn get(&self, name: &str) -> Option<&SmartPlaylist> {
+        self.playlists
+            .iter()
+            .find(|playlist| playlist.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Adds a definition, replacing any definition with the same name.
+    pub fn upsert(&mut self, definition: SmartPlaylist) {
+        match self
+            .playlists
+            .iter_mut()
+            .find(|playlist| playlist.name.eq_ignore_ascii_case(&definition.name))
+        {
+            Some(existing) => *existing = definition,
+            None => self.playlists.push(definition),
+        }
+    }
+
+    /// Removes the definition with the given name. Returns whether one was removed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.playlists.len();
+        self.playlists
+            .retain(|playlist| !playlist.name.eq_ignore_ascii_case(name));
+        self.playlists.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::offline::OfflineObjects;
+    use chrono::DateTime;
+
+    fn track(id: char, popularity: u32, release_date: &str, explicit: bool, duration_ms: u32) -> SourceTrack {
+        let id = id.to_string().repeat(22);
+        let mut track = OfflineObjects::full_track(&id, "Song", "Artist", "Album", None, duration_ms);
+        track.popularity = popularity;
+        track.explicit = explicit;
+        track.album.release_date = Some(release_date.to_string());
+        SourceTrack {
+            track,
+            added_at: DateTime::from_timestamp(1_600_000_000, 0),
+        }
+    }
+
+    fn rules(rules: &[&str]) -> Vec<SmartRule> {
+        rules.iter().map(|rule| rule.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn rules_round_trip_and_reject_invalid_input() {
+        let rule = "popularity<=40".parse::<SmartRule>().unwrap();
+        assert_eq!(rule.to_string(), "popularity <= 40");
+        assert_eq!(rule.comparison(), Comparison::LessOrEqual);
+        assert!("popularity 40".parse::<SmartRule>().is_err());
+        assert!("explicit = maybe".parse::<SmartRule>().is_err());
+        assert!("released >= 20".parse::<SmartRule>().is_err());
+        assert!("mood = happy".parse::<SmartRule>().is_err());
+    }
+
+    #[test]
+    fn select_applies_every_rule() {
+        let tracks = vec![
+            track('A', 30, "2012-05-01", false, 200_000),
+            track('B', 60, "2012-05-01", false, 200_000),
+            track('C', 30, "2016", false, 200_000),
+            track('D', 30, "2015-12", true, 200_000),
+            track('E', 30, "2015", false, 400_000),
+            track('F', 10, "2010", false, 100_000),
+        ];
+        let definition = SmartPlaylist::new(
+            "Deep Cuts".to_string(),
+            LIKED_SONGS.to_string(),
+            rules(&[
+                "released >= 2010",
+                "released <= 2015",
+                "popularity < 40",
+                "explicit = false",
+                "duration < 5m",
+            ]),
+        );
+        let selected = definition
+            .select(&tracks)
+            .into_iter()
+            .map(|track| track.id.unwrap().id().chars().next().unwrap())
+            .collect::<Vec<char>>();
+        assert_eq!(selected, vec!['A', 'F']);
+    }
+
+    #[test]
+    fn artist_added_and_limit_rules() {
+        let mut tracks = vec![track('A', 30, "2012", false, 200_000), track('B', 30, "2012", false, 200_000)];
+        tracks[1].added_at = None;
+        let mut undated = track('C', 30, "2012", false, 200_000);
+        undated.track.album.release_date = None;
+        let undated_rules = SmartPlaylist::new(
+            "Undated".to_string(),
+            LIKED_SONGS.to_string(),
+            rules(&["released <= 2015"]),
+        );
+        assert!(undated_rules.select(&[undated]).is_empty());
+        let mut definition = SmartPlaylist::new(
+            "Recent".to_string(),
+            LIKED_SONGS.to_string(),
+            rules(&["artist = artist", "added >= 2020-09"]),
+        );
+        assert_eq!(definition.select(&tracks).len(), 1);
+        definition.rules = rules(&["artist != Artist"]);
+        assert!(definition.select(&tracks).is_empty());
+        definition.rules = Vec::new();
+        definition.limit = Some(1);
+        assert_eq!(definition.select(&tracks).len(), 1);
+    }
+
+    #[test]
+    fn definitions_round_trip_through_toml() {
+        let mut definitions = SmartPlaylists::default();
+        let mut definition = SmartPlaylist::new(
+            "Deep Cuts".to_string(),
+            "Workout".to_string(),
+            rules(&["popularity < 40", "duration < 4:30"]),
+        );
+        definition.order = CombineOrder::AddedAt;
+        definitions.upsert(definition.clone());
+        definitions.upsert(definition);
+        let contents = toml::to_string_pretty(&definitions).unwrap();
+        assert!(contents.contains("[[playlist]]"));
+        assert!(contents.contains("order = \"added\""));
+        let parsed = SmartPlaylists::parse(&contents).unwrap();
+        assert_eq!(parsed, definitions);
+        assert_eq!(parsed.playlists().len(), 1);
+
+        let defaults = SmartPlaylists::parse("[[playlist]]\nname = \"All\"\n").unwrap();
+        assert_eq!(defaults.get("all").unwrap().source, LIKED_SONGS);
+        let broken = SmartPlaylists::parse("[[playlist]]\nrules = [\"mood = happy\"]\n").unwrap_err();
+        assert_eq!(broken.kind(), io::ErrorKind::InvalidData);
+        definitions.remove("DEEP CUTS");
+        assert!(definitions.playlists().is_empty());
+    }
+}
@@ -224,6 +224,14 @@ pub fn configuration_toml(env: &TestEnvironment) -> String {
 custom_release_radar = true
 query_playlist_for_blacklist = false
 
+[behavior.duplicates.default]
+policy = "remove"
+preference = "prefer_original_album"
+
+[behavior.duplicates.playlists."Workout"]
+policy = "remove"
+preference = "prefer_clean"
+
 [cli]
 default_shell = "bash"
 artist_id_format = "uri"
@@ -7,8 +7,8 @@
 #![allow(dead_code)]
 
 use rspotify::model::{
-    AlbumId, FullAlbum, FullArtist, SavedTrack, SimplifiedAlbum, SimplifiedArtist,
-    SimplifiedTrack,
+    AlbumId, FullAlbum, FullArtist, FullTrack, PlaylistItem, SavedTrack, SimplifiedAlbum,
+    SimplifiedArtist, SimplifiedPlaylist, SimplifiedTrack,
 };
 use rspotify::prelude::Id;
 use rspotify::{AuthCodeSpotify, Config, Credentials, OAuth};
@@ -257,4 +257,116 @@ impl OfflineObjects {
     pub fn sample_saved_tracks() -> Vec<SavedTrack> {
         vec![Self::sample_saved_track("one"), Self::sample_saved_track("two")]
     }
+
+    /// Builds a `FullTrack` with the fields most tests care about.
+    ///
+    /// `isrc` may be `None` to mimic local files or tracks without external IDs. The album ID is
+    /// derived from the album name so tracks sharing an album name share an album ID.
+    pub fn full_track(
+        id: &str,
+        name: &str,
+        artist_name: &str,
+        album_name: &str,
+        isrc: Option<&str>,
+        duration_ms: u32,
+    ) -> FullTrack {
+        let artist_id = format!("{:A<22}", artist_name.replace(' ', "").chars().take(22).collect::<String>());
+        let album_id = format!("{:B<22}", album_name.replace(' ', "").chars().take(22).collect::<String>());
+        let external_ids = match isrc {
+            Some(isrc) => json!({"isrc": isrc}),
+            None => json!({}),
+        };
+        serde_json::from_value(json!({
+            "album": {
+                "album_group": null,
+                "album_type": "album",
+                "artists": [{
+                    "external_urls": {"spotify": "https://example.com/artist"},
+                    "href": format!("https://api.spotify.com/v1/artists/{artist_id}"),
+                    "id": artist_id,
+                    "name": artist_name
+                }],
+                "available_markets": [],
+                "external_urls": {"spotify": "https://example.com/album"},
+                "href": format!("https://api.spotify.com/v1/albums/{album_id}"),
+                "id": album_id,
+                "images": [],
+                "name": album_name,
+                "release_date": "2024-01-01",
+                "release_date_precision": "day",
+                "restrictions": null
+            },
+            "artists": [{
+                "external_urls": {"spotify": "https://example.com/artist"},
+                "href": format!("https://api.spotify.com/v1/artists/{artist_id}"),
+                "id": artist_id,
+                "name": artist_name
+            }],
+            "available_markets": [],
+            "disc_number": 1,
+            "duration_ms": duration_ms,
+            "explicit": false,
+            "external_ids": external_ids,
+            "external_urls": {"spotify": "https://example.com/track"},
+            "href": format!("https://api.spotify.com/v1/tracks/{id}"),
+            "id": id,
+            "is_local": false,
+            "is_playable": true,
+            "linked_from": null,
+            "restrictions": null,
+            "name": name,
+            "popularity": 42,
+            "preview_url": null,
+            "track_number": 1,
+            "type": "track"
+        }))
+            .expect("valid FullTrack JSON")
+    }
+
+    /// Builds a liked song saved at `added_at`, an RFC 3339 timestamp.
+    ///
+    /// The track is named after `id`, its ID repeats `id` 22 times and its ISRC ends with `id`,
+    /// so every label is a distinct recording.
+    pub fn saved_track(id: char, added_at: &str) -> SavedTrack {
+        let label = id.to_string();
+        let isrc = format!("USRC1700000{id}");
+        serde_json::from_value(json!({
+            "added_at": added_at,
+            "track": Self::full_track(&label.repeat(22), &label, "Artist", "Album", Some(&isrc), 200_000),
+        }))
+            .expect("valid SavedTrack JSON")
+    }
+
+    /// Wraps a track into a `PlaylistItem`, as returned by the playlist items endpoint.
+    pub fn playlist_item(track: FullTrack) -> PlaylistItem {
+        serde_json::from_value(json!({
+            "added_at": "2024-01-01T00:00:00Z",
+            "added_by": null,
+            "is_local": false,
+            "track": track,
+        }))
+            .expect("valid PlaylistItem JSON")
+    }
+
+    /// Builds a `SimplifiedPlaylist` as returned by the current user's playlists endpoint.
+    pub fn simplified_playlist(id: &str, name: &str, owner_id: &str, public: bool) -> SimplifiedPlaylist {
+        serde_json::from_value(json!({
+            "collaborative": false,
+            "external_urls": {"spotify": format!("https://open.spotify.com/playlist/{id}")},
+            "href": format!("https://api.spotify.com/v1/playlists/{id}"),
+            "id": id,
+            "images": [],
+            "name": name,
+            "owner": {
+                "display_name": owner_id,
+                "external_urls": {},
+                "href": format!("https://api.spotify.com/v1/users/{owner_id}"),
+                "id": owner_id
+            },
+            "public": public,
+            "snapshot_id": "snapshot",
+            "tracks": {"href": format!("https://api.spotify.com/v1/playlists/{id}/tracks"), "total": 0}
+        }))
+            .expect("valid SimplifiedPlaylist JSON")
+    }
 }
@@ -3,6 +3,7 @@ use rspotify::prelude::OAuthClient;
 use rspotify::{AuthCodeSpotify, Config, Credentials, OAuth};
 use std::collections::HashSet;
 use std::env;
+use std::path::PathBuf;
 use tracing::{error, info, trace, trace_span};
 
 use crate::enums::fs::{ProjectDirectories, ProjectFiles};
@@ -144,6 +145,36 @@ pub trait Api {
         is_test: bool,
         scopes: Option<HashSet<String>>,
     ) -> impl Future<Output = AuthCodeSpotify> + Send {
+        Self::set_up_profile_client(is_test, scopes, None)
+    }
+
+    /// The token cache of a profile. The default profile uses `token_cache` in the cache
+    /// directory; a named profile uses `token_cache_<name>` next to it.
+    fn token_cache_path(profile: Option<&str>) -> PathBuf {
+        match profile {
+            Some(profile) => ProjectDirectories::Cache.path().join(format!("token_cache_{profile}")),
+            None => ProjectDirectories::Cache.path().join("token_cache"),
+        }
+    }
+
+    /// Sets up a Spotify client like [`Api::set_up_client`], authorized for a profile.
+    ///
+    /// Every profile keeps its own token cache (see [`Api::token_cache_path`]), so the first
+    /// use of a named profile prompts for authorization; log in with the account the profile
+    /// should stand for. `None` is the default profile used everywhere else.
+    ///
+    /// # Examples
+    /// ```no_run,ignore
+    /// use spotify_assistant_core::traits::apis::Api;
+    ///
+    /// let other_account = Api::set_up_profile_client(false, None, Some("work")).await;
+    /// ```
+    fn set_up_profile_client(
+        is_test: bool,
+        scopes: Option<HashSet<String>>,
+        profile: Option<&str>,
+    ) -> impl Future<Output = AuthCodeSpotify> + Send {
+        let cache_path = Self::token_cache_path(profile);
         async move {
             let suc_span = trace_span!("api-client");
             let _enter = suc_span.enter();
@@ -213,7 +244,7 @@ pub trait Api {
             };
 
             let config = Config {
-                cache_path: ProjectDirectories::Cache.path().join("token_cache"),
+                cache_path,
                 token_cached: true,
                 token_refreshing: true,
                 ..Default::default()
@@ -1,3 +1,6 @@
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
 /// Calculates the total number of combinations based on the given number of unique items.
 ///
 /// This function takes the number of unique items (`uniques`) as input and computes
@@ -80,6 +83,14 @@ fn separator_line<P: TerminalSizeProvider>(provider: &P) -> String {
     "-".repeat(width)
 }
 
+/// Returns the width of the terminal in columns, or 80 when it cannot be determined.
+pub fn terminal_width() -> usize {
+    DefaultTerminalSizeProvider
+        .dimensions()
+        .map(|(width, _)| width)
+        .unwrap_or(80)
+}
+
 pub fn print_separator() {
     let separator = separator_line(&DefaultTerminalSizeProvider);
     println!("{separator}");
@@ -94,6 +105,168 @@ pub fn format_duration(duration: chrono::TimeDelta) -> String {
     dur
 }
 
+/// Escapes a single CSV field.
+///
+/// Fields containing a comma, a double quote, or a line break are wrapped in double quotes and
+/// any embedded double quotes are doubled, following RFC 4180. Other fields are returned as-is.
+///
+/// # Example
+///
+/// ```
+/// use spotify_assistant_core::utilities::general::csv_field;
+/// assert_eq!(csv_field("plain"), "plain");
+/// assert_eq!(csv_field("a, \"b\""), "\"a, \"\"b\"\"\"");
+/// ```
+pub fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Joins already-unescaped values into a single CSV row using [`csv_field`].
+pub fn csv_row<S: AsRef<str>>(fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|field| csv_field(field.as_ref()))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Escapes the five XML special characters so text can be embedded in an XML document.
+///
+/// # Example
+///
+/// ```
+/// use spotify_assistant_core::utilities::general::xml_escape;
+/// assert_eq!(xml_escape("Rock & <Roll>"), "Rock &amp; &lt;Roll&gt;");
+/// ```
+pub fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+/// Turns an arbitrary name (such as a playlist name) into a safe file stem by replacing path
+/// separators and other characters that are invalid on common file systems with `_`.
+pub fn file_stem(name: &str) -> String {
+    let stem = name
+        .trim()
+        .chars()
+        .map(|character| match character {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            other if other.is_control() => '_',
+            other => other,
+        })
+        .collect::<String>();
+    if stem.is_empty() { "untitled".to_string() } else { stem }
+}
+
+/// Parses CSV text into records of unescaped fields.
+///
+/// Quoted fields may contain commas, doubled quotes and line breaks, following RFC 4180. Both
+/// `\n` and `\r\n` line endings are accepted and blank lines are skipped.
+///
+/// # Example
+///
+/// ```
+/// use spotify_assistant_core::utilities::general::parse_csv;
+/// let records = parse_csv("name,artist\n\"Song, Pt. 2\",Band\n");
+/// assert_eq!(records[1], vec!["Song, Pt. 2", "Band"]);
+/// ```
+pub fn parse_csv(contents: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut characters = contents.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '"' if in_quotes && characters.peek() == Some(&'"') => {
+                field.push('"');
+                characters.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => record.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes && characters.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|value| !value.is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+            }
+            other => field.push(other),
+        }
+    }
+    record.push(field);
+    if record.iter().any(|value| !value.is_empty()) {
+        records.push(record);
+    }
+    records
+}
+
+/// Reduces text to lowercase alphanumeric words for fuzzy comparisons.
+///
+/// Diacritics are stripped, punctuation becomes whitespace and runs of whitespace collapse to a
+/// single space, so `"Beyoncé – Halo!"` and `"beyonce halo"` compare equal.
+pub fn normalize_for_matching(text: &str) -> String {
+    text.nfd()
+        .filter(|character| !is_combining_mark(*character))
+        .map(|character| if character.is_alphanumeric() { character } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Scores how similar two strings are, from `0.0` (nothing in common) to `1.0` (equal after
+/// [`normalize_for_matching`]).
+///
+/// The score is the Levenshtein distance normalized by the length of the longer string.
+///
+/// # Example
+///
+/// ```
+/// use spotify_assistant_core::utilities::general::similarity;
+/// assert_eq!(similarity("Halo", "HALO!"), 1.0);
+/// assert!(similarity("Halo", "Hello") < 1.0);
+/// assert_eq!(similarity("", "Halo"), 0.0);
+/// ```
+pub fn similarity(left: &str, right: &str) -> f64 {
+    let left = normalize_for_matching(left).chars().collect::<Vec<char>>();
+    let right = normalize_for_matching(right).chars().collect::<Vec<char>>();
+    if left.is_empty() && right.is_empty() {
+        return 1.0;
+    }
+    if left.is_empty() || right.is_empty() {
+        return 0.0;
+    }
+    let mut previous = (0..=right.len()).collect::<Vec<usize>>();
+    for (i, left_character) in left.iter().enumerate() {
+        let mut current = vec![i + 1; right.len() + 1];
+        for (j, right_character) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_character != right_character);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    1.0 - previous[right.len()] as f64 / left.len().max(right.len()) as f64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,4 +324,31 @@ mod tests {
         let provider = FixedSizeProvider(None);
         assert_eq!(separator_line(&provider), "-".repeat(80));
     }
+
+    #[test]
+    fn csv_row_quotes_only_fields_that_need_it() {
+        let row = csv_row(&["Song", "Artist, Other", "12\" Mix"]);
+        assert_eq!(row, "Song,\"Artist, Other\",\"12\"\" Mix\"");
+    }
+
+    #[test]
+    fn file_stem_replaces_reserved_characters() {
+        assert_eq!(file_stem(" AC/DC: Live? "), "AC_DC_ Live_");
+        assert_eq!(file_stem("   "), "untitled");
+    }
+
+    #[test]
+    fn parse_csv_handles_quotes_and_line_breaks() {
+        let records = parse_csv("a,b\r\n\"x\"\"y\",\"line\nbreak\"\r\n\r\n,\n");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0], vec!["a", "b"]);
+        assert_eq!(records[1], vec!["x\"y", "line\nbreak"]);
+    }
+
+    #[test]
+    fn similarity_ignores_case_accents_and_punctuation() {
+        assert_eq!(similarity("Beyoncé – Halo!", "beyonce halo"), 1.0);
+        let score = similarity("Kitten", "Sitting");
+        assert!((score - (1.0 - 3.0 / 7.0)).abs() < f64::EPSILON);
+    }
 }
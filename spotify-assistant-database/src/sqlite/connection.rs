@@ -60,6 +60,10 @@ impl Sqweel {
             "liked_track_artists",
             SQLiteStatements::InitTables(InitTables::LikedTrackArtists).string(),
         );
+        init_table_statements.insert(
+            "playlist_versions",
+            SQLiteStatements::InitTables(InitTables::PlaylistVersions).string(),
+        );
 
         for (key, statement) in init_table_statements.iter() {
             let mut key_as_chars = key.chars();
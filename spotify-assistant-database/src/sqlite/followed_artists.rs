@@ -0,0 +1,290 @@
+#[cfg(feature = "rusqlite")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "rusqlite")]
+use rusqlite::{params, Connection, Error, OptionalExtension};
+#[cfg(feature = "rusqlite")]
+use tracing::{event, Level};
+
+#[cfg(feature = "rusqlite")]
+use spotify_assistant_core::actions::user::UserData;
+#[cfg(feature = "rusqlite")]
+use spotify_assistant_core::enums::fs::ProjectDirectories;
+#[cfg(feature = "rusqlite")]
+use spotify_assistant_core::models::followed_artists::{ArtistChange, ArtistSnapshot};
+
+#[cfg(feature = "rusqlite")]
+use crate::sqlite::connection::Sqweel;
+#[cfg(feature = "rusqlite")]
+use crate::sqlite::statements::{InitTables, Insert, SQLiteStatements, TableColumns};
+
+/// Separates the genres of an artist in the `genres` column.
+#[cfg(feature = "rusqlite")]
+const GENRE_SEPARATOR: &str = "; ";
+
+/// The changes of the followed artists between two syncs.
+///
+/// # Fields
+///
+/// * `from` - The sync the changes start from.
+/// * `to` - The latest sync.
+/// * `changes` - The follows, unfollows and popularity, follower and genre changes.
+/// * `genre_shift` - How the number of followed artists per genre changed.
+#[cfg(feature = "rusqlite")]
+pub struct FollowedArtistsChanges {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub changes: Vec<ArtistChange>,
+    pub genre_shift: Vec<(String, i64)>,
+}
+
+/// The history of the followed artists, kept in the `followed_artists` table.
+///
+/// Every sync inserts one row per followed artist, all sharing the `synced_at` of the sync,
+/// so earlier syncs stay available to compare against.
+#[cfg(feature = "rusqlite")]
+pub struct FollowedArtistsHistory {
+    conn: Connection,
+}
+
+#[cfg(feature = "rusqlite")]
+impl FollowedArtistsHistory {
+    /// Opens the history in the main database, creating the table when needed.
+    pub fn open() -> Result<Self, Error> {
+        let span = tracing::span!(Level::INFO, "FollowedArtistsHistory.open");
+        let _enter = span.enter();
+
+        if let Err(err) = std::fs::create_dir_all(ProjectDirectories::Data.path().join("databases")) {
+            event!(Level::ERROR, "Could not create the databases directory: {:?}", err);
+        }
+        Self::from_connection(Sqweel::new()?.conn)
+    }
+
+    /// Uses an open connection, creating the table when needed. A `followed_artists` table
+    /// from before syncs were recorded gets the missing columns added.
+    pub fn from_connection(conn: Connection) -> Result<Self, Error> {
+        conn.execute(&SQLiteStatements::InitTables(InitTables::FollowedArtists).string(), [])?;
+        let existing = conn
+            .prepare("select name from pragma_table_info('followed_artists')")?
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<String>, Error>>()?;
+        let properties = TableColumns::FollowedArtists.with_properties();
+        for column in TableColumns::FollowedArtists.names(false) {
+            if !existing.iter().any(|name| name == column) {
+                let definition = properties[column].replace(" not null", "");
+                event!(Level::INFO, "Adding the {} column to followed_artists", column);
+                conn.execute(&format!("alter table followed_artists add column {column} {definition}"), [])?;
+            }
+        }
+        Ok(FollowedArtistsHistory { conn })
+    }
+
+    /// Records a sync of the followed artists.
+    ///
+    /// # Returns
+    /// The number of artists recorded.
+    pub async fn record(&mut self, synced_at: DateTime<Utc>, artists: &[ArtistSnapshot]) -> Result<usize, Error> {
+        let span = tracing::span!(Level::INFO, "FollowedArtistsHistory.record");
+        let _enter = span.enter();
+
+        let statement = SQLiteStatements::Insert(Insert::FollowedArtists).async_string().await;
+        let transaction = self.conn.transaction()?;
+        {
+            let mut insert = transaction.prepare(&statement)?;
+            for artist in artists {
+                insert.execute(params![
+                    &artist.artist_id,
+                    &artist.name,
+                    artist.followers,
+                    artist.genres.join(GENRE_SEPARATOR),
+                    artist.popularity,
+                    synced_at,
+                ])?;
+            }
+        }
+        transaction.commit()?;
+        event!(Level::INFO, "Recorded {} followed artists", artists.len());
+        Ok(artists.len())
+    }
+
+    /// Fetches the followed artists from Spotify and records them as a new sync.
+    ///
+    /// # Returns
+    /// The time of the sync and the followed artists.
+    pub async fn sync(&mut self) -> Result<(DateTime<Utc>, Vec<ArtistSnapshot>), Error> {
+        let artists = UserData::new()
+            .await
+            .artists()
+            .await
+            .iter()
+            .map(ArtistSnapshot::from)
+            .collect::<Vec<ArtistSnapshot>>();
+        let synced_at = Utc::now();
+        self.record(synced_at, &artists).await?;
+        Ok((synced_at, artists))
+    }
+
+    /// The times of every recorded sync, oldest first.
+    pub fn syncs(&self) -> Result<Vec<DateTime<Utc>>, Error> {
+        self.conn
+            .prepare("select distinct synced_at from followed_artists where synced_at is not null order by synced_at")?
+            .query_map([], |row| row.get(0))?
+            .collect()
+    }
+
+    /// The followed artists as of the given sync.
+    pub fn snapshot(&self, synced_at: DateTime<Utc>) -> Result<Vec<ArtistSnapshot>, Error> {
+        self.conn
+            .prepare(
+                "select artist_id, name, followers, genres, popularity from followed_artists \
+                 where synced_at = ?1 order by name",
+            )?
+            .query_map(params![synced_at], Self::artist_from_row)?
+            .collect()
+    }
+
+    /// Every recorded state of the artists whose ID is `query` or whose name contains it,
+    /// ignoring case. Ordered by artist, then oldest sync first.
+    pub fn artist_history(&self, query: &str) -> Result<Vec<(DateTime<Utc>, ArtistSnapshot)>, Error> {
+        self.conn
+            .prepare(
+                "select artist_id, name, followers, genres, popularity, synced_at from followed_artists \
+                 where synced_at is not null and (artist_id = ?1 or lower(name) like '%' || lower(?1) || '%') \
+                 order by lower(name), artist_id, synced_at",
+            )?
+            .query_map(params![query.trim()], |row| Ok((row.get(5)?, Self::artist_from_row(row)?)))?
+            .collect()
+    }
+
+    /// Compares the latest sync with the last sync at or before `since`. Without a sync that
+    /// old, the earliest sync is used.
+    ///
+    /// # Returns
+    /// `None` when fewer than two syncs are recorded.
+    pub fn changes_since(&self, since: DateTime<Utc>) -> Result<Option<FollowedArtistsChanges>, Error> {
+        let span = tracing::span!(Level::INFO, "FollowedArtistsHistory.changes_since");
+        let _enter = span.enter();
+
+        let syncs = self.syncs()?;
+        let (Some(first), Some(latest)) = (syncs.first(), syncs.last()) else {
+            return Ok(None);
+        };
+        let from = self
+            .conn
+            .query_row(
+                "select max(synced_at) from followed_artists where synced_at <= ?1",
+                params![since],
+                |row| row.get::<_, Option<DateTime<Utc>>>(0),
+            )
+            .optional()?
+            .flatten()
+            .unwrap_or(*first);
+        if from == *latest {
+            return Ok(None);
+        }
+        let before = self.snapshot(from)?;
+        let after = self.snapshot(*latest)?;
+        Ok(Some(FollowedArtistsChanges {
+            from,
+            to: *latest,
+            changes: ArtistSnapshot::changes(&before, &after),
+            genre_shift: ArtistSnapshot::genre_shift(&before, &after),
+        }))
+    }
+
+    fn artist_from_row(row: &rusqlite::Row) -> Result<ArtistSnapshot, Error> {
+        let genres: Option<String> = row.get(3)?;
+        Ok(ArtistSnapshot {
+            artist_id: row.get(0)?,
+            name: row.get(1)?,
+            followers: row.get::<_, Option<u32>>(2)?.unwrap_or_default(),
+            popularity: row.get::<_, Option<u32>>(4)?.unwrap_or_default(),
+            genres: genres
+                .unwrap_or_default()
+                .split(GENRE_SEPARATOR)
+                .filter(|genre| !genre.is_empty())
+                .map(str::to_string)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "rusqlite"))]
+mod tests {
+    use super::*;
+    use spotify_assistant_core::models::followed_artists::ArtistChangeKind;
+
+    fn artist(artist_id: &str, popularity: u32, genres: &[&str]) -> ArtistSnapshot {
+        ArtistSnapshot {
+            artist_id: artist_id.to_string(),
+            name: format!("Artist {artist_id}"),
+            followers: 100,
+            popularity,
+            genres: genres.iter().map(|genre| genre.to_string()).collect(),
+        }
+    }
+
+    fn sync_time(day: u32) -> DateTime<Utc> {
+        format!("2024-01-{day:02}T12:00:00Z").parse().unwrap()
+    }
+
+    fn history() -> FollowedArtistsHistory {
+        FollowedArtistsHistory::from_connection(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn adds_missing_columns_to_a_legacy_table() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "create table followed_artists (id integer primary key, name varchar(60) not null, \
+             followers integer, genres varchar(60), popularity integer)",
+            [],
+        )
+        .unwrap();
+        conn.execute("insert into followed_artists (name) values ('Legacy')", []).unwrap();
+
+        let history = FollowedArtistsHistory::from_connection(conn).unwrap();
+        let columns = history
+            .conn
+            .prepare("select name from pragma_table_info('followed_artists')")
+            .unwrap()
+            .query_map([], |row| row.get::<_, String>(0))
+            .unwrap()
+            .collect::<Result<Vec<String>, Error>>()
+            .unwrap();
+        assert!(columns.contains(&"artist_id".to_string()));
+        assert!(columns.contains(&"synced_at".to_string()));
+        // Legacy rows carry no sync and are left out of the history.
+        assert!(history.syncs().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn records_syncs_and_round_trips_genres() {
+        let mut history = history();
+        let artists = vec![artist("a", 40, &["indie rock", "shoegaze"]), artist("b", 60, &[])];
+        assert_eq!(history.record(sync_time(1), &artists).await.unwrap(), 2);
+
+        assert_eq!(history.syncs().unwrap(), vec![sync_time(1)]);
+        assert_eq!(history.snapshot(sync_time(1)).unwrap(), artists);
+        let rows = history.artist_history("ARTIST A").unwrap();
+        assert_eq!(rows, vec![(sync_time(1), artists[0].clone())]);
+    }
+
+    #[tokio::test]
+    async fn changes_since_starts_from_the_last_sync_before_the_date() {
+        let mut history = history();
+        assert!(history.changes_since(sync_time(1)).unwrap().is_none());
+        history.record(sync_time(1), &[artist("a", 10, &[])]).await.unwrap();
+        history.record(sync_time(5), &[artist("a", 20, &[])]).await.unwrap();
+        history.record(sync_time(9), &[artist("a", 30, &[])]).await.unwrap();
+
+        let changes = history.changes_since(sync_time(6)).unwrap().unwrap();
+        assert_eq!((changes.from, changes.to), (sync_time(5), sync_time(9)));
+        assert_eq!(changes.changes[0].kind, ArtistChangeKind::Popularity { from: 20, to: 30 });
+
+        // Without a sync that old, the earliest one is used.
+        let changes = history.changes_since(sync_time(1) - chrono::Duration::days(30)).unwrap().unwrap();
+        assert_eq!(changes.from, sync_time(1));
+        // Starting at the latest sync leaves nothing to compare.
+        assert!(history.changes_since(sync_time(10)).unwrap().is_none());
+    }
+}
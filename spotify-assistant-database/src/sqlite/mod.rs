@@ -1,3 +1,5 @@
 pub mod connection;
 pub mod querying;
 pub mod statements;
+pub mod followed_artists;
+pub mod playlist_versions;
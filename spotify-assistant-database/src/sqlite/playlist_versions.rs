@@ -0,0 +1,236 @@
+#[cfg(feature = "rusqlite")]
+use std::fs;
+#[cfg(feature = "rusqlite")]
+use std::path::Path;
+
+#[cfg(feature = "rusqlite")]
+use rspotify::prelude::Id;
+#[cfg(feature = "rusqlite")]
+use rusqlite::types::Type;
+#[cfg(feature = "rusqlite")]
+use rusqlite::{params, Connection, Error, OptionalExtension};
+#[cfg(feature = "rusqlite")]
+use tracing::{event, Level};
+
+#[cfg(feature = "rusqlite")]
+use spotify_assistant_core::actions::exploration::playlist::PlaylistXplr;
+#[cfg(feature = "rusqlite")]
+use spotify_assistant_core::actions::playlists::compare::PlaylistDiff;
+#[cfg(feature = "rusqlite")]
+use spotify_assistant_core::actions::playlists::export::PlaylistExport;
+#[cfg(feature = "rusqlite")]
+use spotify_assistant_core::actions::playlists::history::{PlaylistHistory, TrackedPlaylist};
+#[cfg(feature = "rusqlite")]
+use spotify_assistant_core::actions::playlists::user::UserPlaylists;
+#[cfg(feature = "rusqlite")]
+use spotify_assistant_core::enums::fs::ProjectDirectories;
+
+#[cfg(feature = "rusqlite")]
+use crate::sqlite::connection::Sqweel;
+#[cfg(feature = "rusqlite")]
+use crate::sqlite::statements::{InitTables, Insert, SQLiteStatements};
+
+/// The recorded versions of the owned playlists, kept in the `playlist_versions` table.
+///
+/// Every row holds one version of a playlist as the JSON of a [`PlaylistExport`], along with
+/// its `snapshot_id` and the time it was recorded.
+#[cfg(feature = "rusqlite")]
+pub struct PlaylistVersions {
+    conn: Connection,
+}
+
+#[cfg(feature = "rusqlite")]
+impl PlaylistVersions {
+    /// Opens the versions in the main database, creating the table when needed. Versions
+    /// stored as JSON files by earlier releases are imported for playlists without rows.
+    pub async fn open() -> Result<Self, Error> {
+        let span = tracing::span!(Level::INFO, "PlaylistVersions.open");
+        let _enter = span.enter();
+
+        if let Err(err) = fs::create_dir_all(ProjectDirectories::Data.path().join("databases")) {
+            event!(Level::ERROR, "Could not create the databases directory: {:?}", err);
+        }
+        let versions = Self::from_connection(Sqweel::new()?.conn)?;
+        let imported = versions.import_directory(&PlaylistHistory::legacy_directory()).await?;
+        if imported > 0 {
+            event!(Level::INFO, "Imported {} playlist versions from JSON files", imported);
+        }
+        Ok(versions)
+    }
+
+    /// Uses an open connection, creating the table when needed.
+    pub fn from_connection(conn: Connection) -> Result<Self, Error> {
+        conn.execute(&SQLiteStatements::InitTables(InitTables::PlaylistVersions).string(), [])?;
+        Ok(PlaylistVersions { conn })
+    }
+
+    /// Imports the JSON versions of a directory laid out as `<playlist id>/<version>.json`.
+    /// Playlists that already have recorded versions are left alone.
+    ///
+    /// # Returns
+    /// The number of versions imported.
+    pub async fn import_directory(&self, directory: &Path) -> Result<usize, Error> {
+        let Ok(entries) = fs::read_dir(directory) else {
+            return Ok(0);
+        };
+        let mut imported = 0;
+        for playlist_directory in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+            let Some(playlist_id) = playlist_directory.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if !playlist_directory.is_dir() || self.latest(playlist_id)?.is_some() {
+                continue;
+            }
+            for version in PlaylistHistory::load_directory(&playlist_directory) {
+                if self.record(&version).await? {
+                    imported += 1;
+                }
+            }
+        }
+        Ok(imported)
+    }
+
+    /// Records `version` unless its snapshot ID matches the latest recorded version.
+    ///
+    /// # Returns
+    /// Whether the version was recorded.
+    pub async fn record(&self, version: &PlaylistExport) -> Result<bool, Error> {
+        let span = tracing::span!(Level::INFO, "PlaylistVersions.record");
+        let _enter = span.enter();
+
+        let latest = PlaylistHistory::new(self.latest(&version.id)?.into_iter().collect());
+        if !latest.is_new(version) {
+            return Ok(false);
+        }
+        let json = version
+            .to_json()
+            .map_err(|err| Error::ToSqlConversionFailure(Box::new(err)))?;
+        let statement = SQLiteStatements::Insert(Insert::PlaylistVersions).async_string().await;
+        self.conn.execute(
+            &statement,
+            params![
+                &version.id,
+                &version.name,
+                &version.snapshot_id,
+                PlaylistHistory::recorded_at(version),
+                json,
+            ],
+        )?;
+        Ok(true)
+    }
+
+    /// The most recently recorded version of the playlist, if any.
+    pub fn latest(&self, playlist_id: &str) -> Result<Option<PlaylistExport>, Error> {
+        self.conn
+            .query_row(
+                "select version from playlist_versions where playlist_id = ?1 \
+                 order by recorded_at desc, id desc limit 1",
+                params![playlist_id],
+                Self::version_from_row,
+            )
+            .optional()
+    }
+
+    /// Every recorded version of the playlist.
+    pub fn history(&self, playlist_id: &str) -> Result<PlaylistHistory, Error> {
+        let versions = self
+            .conn
+            .prepare("select version from playlist_versions where playlist_id = ?1 order by recorded_at, id")?
+            .query_map(params![playlist_id], Self::version_from_row)?
+            .collect::<Result<Vec<PlaylistExport>, Error>>()?;
+        Ok(PlaylistHistory::new(versions))
+    }
+
+    /// Records a new version of every owned playlist whose snapshot ID changed.
+    ///
+    /// Playlists are only loaded when their snapshot ID differs from the latest recorded
+    /// version, so running this regularly is cheap.
+    ///
+    /// # Returns
+    /// One entry per owned playlist, or the first database error encountered.
+    pub async fn track_owned(&self) -> Result<Vec<TrackedPlaylist>, Error> {
+        let span = tracing::span!(Level::INFO, "PlaylistVersions.track_owned");
+        let _enter = span.enter();
+
+        let owned = UserPlaylists::new().await.get_owned_playlists().await;
+        let mut tracked = Vec::with_capacity(owned.len());
+        for playlist in owned {
+            let previous = self.latest(playlist.id.id())?;
+            let history = PlaylistHistory::new(previous.clone().into_iter().collect());
+            if !history.is_outdated(&playlist) {
+                tracked.push(TrackedPlaylist {
+                    name: playlist.name,
+                    recorded: false,
+                    diff: None,
+                });
+                continue;
+            }
+            let (xplr, items) = PlaylistXplr::with_items(playlist.id.clone(), false).await;
+            let version = PlaylistExport::from_playlist(&xplr, items);
+            let recorded = self.record(&version).await?;
+            if recorded {
+                event!(Level::INFO, "Recorded a new version of {}", playlist.name);
+            }
+            tracked.push(TrackedPlaylist {
+                name: playlist.name,
+                recorded,
+                diff: previous
+                    .filter(|_| recorded)
+                    .map(|previous| PlaylistDiff::new(&previous, &version)),
+            });
+        }
+        Ok(tracked)
+    }
+
+    fn version_from_row(row: &rusqlite::Row) -> Result<PlaylistExport, Error> {
+        let json: String = row.get(0)?;
+        PlaylistExport::from_json(&json).map_err(|err| Error::FromSqlConversionFailure(0, Type::Text, Box::new(err)))
+    }
+}
+
+#[cfg(all(test, feature = "rusqlite"))]
+mod tests {
+    use super::*;
+    use spotify_assistant_core::enums::playlist_format::PlaylistFormat;
+
+    fn version(snapshot_id: &str, exported_at: &str) -> PlaylistExport {
+        let mut version = PlaylistExport::new("playlist".to_string(), "Road Trip".to_string(), Vec::new());
+        version.snapshot_id = Some(snapshot_id.to_string());
+        version.exported_at = exported_at.to_string();
+        version
+    }
+
+    #[tokio::test]
+    async fn records_only_changed_snapshots() {
+        let versions = PlaylistVersions::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+        assert!(versions.record(&version("s1", "2024-01-01T10:00:00+00:00")).await.unwrap());
+        assert!(!versions.record(&version("s1", "2024-01-02T10:00:00+00:00")).await.unwrap());
+        assert!(versions.record(&version("s2", "2024-02-01T10:00:00+00:00")).await.unwrap());
+
+        let history = versions.history("playlist").unwrap();
+        assert_eq!(
+            history.versions().iter().map(|version| version.snapshot_id.as_deref()).collect::<Vec<_>>(),
+            vec![Some("s1"), Some("s2")]
+        );
+        assert_eq!(versions.latest("playlist").unwrap(), Some(version("s2", "2024-02-01T10:00:00+00:00")));
+        assert!(versions.history("other").unwrap().versions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn imports_legacy_json_versions_once() {
+        let directory = tempfile::tempdir().unwrap();
+        for (snapshot_id, exported_at, file_name) in [
+            ("s1", "2024-01-01T10:00:00+00:00", "20240101T100000.000Z.json"),
+            ("s2", "2024-02-01T10:00:00+00:00", "20240201T100000.000Z.json"),
+        ] {
+            version(snapshot_id, exported_at)
+                .export(&directory.path().join("playlist").join(file_name), PlaylistFormat::Json)
+                .unwrap();
+        }
+
+        let versions = PlaylistVersions::from_connection(Connection::open_in_memory().unwrap()).unwrap();
+        assert_eq!(versions.import_directory(directory.path()).await.unwrap(), 2);
+        assert_eq!(versions.import_directory(directory.path()).await.unwrap(), 0);
+        assert_eq!(versions.history("playlist").unwrap().versions().len(), 2);
+    }
+}
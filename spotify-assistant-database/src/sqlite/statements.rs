@@ -23,7 +23,7 @@ impl SQLiteStatements {
                         table_columns = TableColumns::Tracks.with_properties();
                     }
                     InitTables::FollowedArtists => {
-                        table_columns = TableColumns::Artists.with_properties();
+                        table_columns = TableColumns::FollowedArtists.with_properties();
                     }
                     InitTables::LikedTrackArtists => {
                         table_columns = TableColumns::Artists.with_properties();
@@ -31,6 +31,9 @@ impl SQLiteStatements {
                     InitTables::LikedTracks => {
                         table_columns = TableColumns::Tracks.with_properties();
                     }
+                    InitTables::PlaylistVersions => {
+                        table_columns = TableColumns::PlaylistVersions.with_properties();
+                    }
                 }
                 init.new_table(table_columns)
             }
@@ -51,6 +54,8 @@ pub enum TableColumns {
     Playlists,
     Tracks,
     Artists,
+    FollowedArtists,
+    PlaylistVersions,
 }
 
 #[cfg(feature = "rusqlite")]
@@ -111,6 +116,21 @@ impl TableColumns {
                 table_columns.insert("popularity", "integer");
                 table_columns
             }
+            TableColumns::FollowedArtists => {
+                table_columns.insert("artist_id", "varchar(30) not null");
+                table_columns.insert("followers", "integer");
+                table_columns.insert("genres", "varchar(255)");
+                table_columns.insert("popularity", "integer");
+                table_columns.insert("synced_at", "datetime not null"); // One value per sync
+                table_columns
+            }
+            TableColumns::PlaylistVersions => {
+                table_columns.insert("playlist_id", "varchar(30) not null");
+                table_columns.insert("snapshot_id", "varchar(60)");
+                table_columns.insert("recorded_at", "datetime not null");
+                table_columns.insert("version", "text not null"); // The version as JSON
+                table_columns
+            }
         }
     }
     pub fn names(&self, primary_key: bool) -> Vec<&str> {
@@ -167,6 +187,12 @@ impl TableColumns {
             TableColumns::Artists => {
                 vec!["followers", "genres", "popularity"]
             }
+            TableColumns::FollowedArtists => {
+                vec!["artist_id", "name", "followers", "genres", "popularity", "synced_at"]
+            }
+            TableColumns::PlaylistVersions => {
+                vec!["playlist_id", "name", "snapshot_id", "recorded_at", "version"]
+            }
         };
         if primary_key {
             table_names.insert(0, "id");
@@ -183,6 +209,7 @@ pub enum InitTables {
     LikedTrackArtists,
     FollowedArtists,
     LikedTracks,
+    PlaylistVersions,
 }
 
 #[cfg(feature = "rusqlite")]
@@ -249,7 +276,7 @@ impl InitTables {
                 )
             }
             InitTables::FollowedArtists => {
-                let ordered_columns = TableColumns::Artists.names(true);
+                let ordered_columns = TableColumns::FollowedArtists.names(true);
                 (
                     "followed_artists",
                     ordered_columns
@@ -259,6 +286,17 @@ impl InitTables {
                         .join(",\n"),
                 )
             }
+            InitTables::PlaylistVersions => {
+                let ordered_columns = TableColumns::PlaylistVersions.names(true);
+                (
+                    "playlist_versions",
+                    ordered_columns
+                        .iter()
+                        .map(|key| format!("{} {}", key, table_columns.get(key).unwrap()))
+                        .collect::<Vec<String>>()
+                        .join(",\n"),
+                )
+            }
         };
         format!(
             "create table if not exists {} ({})",
@@ -275,6 +313,7 @@ pub enum Insert {
     LikedTrackArtists,
     FollowedArtists,
     LikedTracks,
+    PlaylistVersions,
 }
 
 #[cfg(feature = "rusqlite")]
@@ -350,7 +389,7 @@ impl Insert {
                 )
             }
             Insert::FollowedArtists => {
-                let column_names = TableColumns::Artists.names(false);
+                let column_names = TableColumns::FollowedArtists.names(false);
                 format!(
                     "insert into followed_artists ({}) values ({})",
                     column_names
@@ -383,6 +422,23 @@ impl Insert {
                         .join(", ")
                 )
             }
+            Insert::PlaylistVersions => {
+                let column_names = TableColumns::PlaylistVersions.names(false);
+                format!(
+                    "insert into playlist_versions ({}) values ({})",
+                    column_names
+                        .iter()
+                        .map(|key| key.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    column_names
+                        .iter()
+                        .enumerate()
+                        .map(|(index, _)| format!("?{}", index + 1))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            }
         }
     }
 }